@@ -14,7 +14,7 @@ use steel::{
     scene::SceneManager,
     time::Time,
     transform::Transform,
-    ui::EguiContext,
+    ui::{anchored_rect, EguiContext},
 };
 use winit::event::VirtualKeyCode;
 
@@ -173,20 +173,32 @@ fn main_menu_system(
     asset_manager: UniqueView<AssetManager>,
 ) {
     for _ in main_menu_component.iter() {
+        // list all scenes except this menu scene, so that a new level only needs a new scene file
+        let scenes = asset_manager
+            .get_assets_of_type("scene")
+            .into_iter()
+            .filter(|(scene, _)| Some(*scene) != scene_manager.current_scene())
+            .map(|(scene, path)| (scene, path.file_stem().unwrap().to_string_lossy()))
+            .collect::<Vec<_>>();
         egui::CentralPanel::default().show(&egui_ctx, |ui| {
-            let available_size = ui.available_size();
-            let button_center = egui::pos2(available_size.x / 2.0, available_size.y / 2.0);
             let button_size = egui::vec2(200.0, 100.0);
-            let button_rect = egui::Rect::from_center_size(button_center, button_size);
-            if ui
-                .put(
-                    button_rect,
-                    egui::Button::new(egui::RichText::new("Start Game").size(30.0)),
+            let top = -(scenes.len() as f32 - 1.0) * button_size.y * 0.6;
+            for (i, (scene, name)) in scenes.iter().enumerate() {
+                let button_rect = anchored_rect(
+                    ui.max_rect(),
+                    egui::Align2::CENTER_CENTER,
+                    button_size,
+                    egui::Vec2::ZERO,
                 )
-                .clicked()
-            {
-                if let Some(game_scene) = asset_manager.get_asset_id("game.scene") {
-                    scene_manager.switch_scene(game_scene);
+                .translate(egui::vec2(0.0, top + i as f32 * button_size.y * 1.2));
+                if ui
+                    .put(
+                        button_rect,
+                        egui::Button::new(egui::RichText::new(format!("Start {name}")).size(30.0)),
+                    )
+                    .clicked()
+                {
+                    scene_manager.switch_scene(*scene);
                 }
             }
         });
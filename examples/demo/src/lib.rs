@@ -22,7 +22,7 @@ pub fn create() -> Box<dyn App> {
         .register_component::<TagComponent>()
         .register_component::<RayTracingInOneWeekend>()
         .add_system(
-            Schedule::PreUpdate,
+            Schedule::SceneLoaded,
             ray_tracing_in_one_weekend::generate_scene_system,
         )
         .add_system(Schedule::PostUpdate, test_system)
@@ -18,7 +18,7 @@ use steel::{
     transform::Transform,
 };
 
-/// Add this compoent to the scene, then the scene of RayTracingInOneWeekend will be generated under this entity.
+/// Add this compoent to the scene, then the scene of RayTracingInOneWeekend will be generated under this entity after the scene is loaded.
 #[derive(Component, Edit, Default)]
 pub struct RayTracingInOneWeekend;
 
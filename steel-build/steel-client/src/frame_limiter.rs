@@ -0,0 +1,40 @@
+use std::time::{Duration, Instant};
+
+/// FrameLimiter limits the frame rate to a target fps by making the event loop wait between frames.
+pub struct FrameLimiter {
+    /// The minimum duration of a frame, None means that the frame rate is not limited.
+    frame_time: Option<Duration>,
+    /// The time when next frame can be started.
+    next_frame: Instant,
+}
+
+impl FrameLimiter {
+    pub fn new() -> Self {
+        FrameLimiter {
+            frame_time: None,
+            next_frame: Instant::now(),
+        }
+    }
+
+    /// Set the target frame rate, None or zero means that the frame rate is not limited.
+    pub fn set_target_fps(&mut self, target_fps: Option<u32>) {
+        self.frame_time = target_fps
+            .filter(|fps| *fps > 0)
+            .map(|fps| Duration::from_secs_f64(1.0 / fps as f64));
+        self.next_frame = Instant::now();
+    }
+
+    /// Get the time to wait until before starting next frame, returns None if next frame can be started now.
+    pub fn wait_until(&self) -> Option<Instant> {
+        self.frame_time?;
+        (Instant::now() < self.next_frame).then_some(self.next_frame)
+    }
+
+    /// Called when a frame is started to schedule next frame. If the frames are late,
+    /// next frame is scheduled from now so that late frames are not made up by a burst of frames.
+    pub fn frame_started(&mut self) {
+        if let Some(frame_time) = self.frame_time {
+            self.next_frame = (self.next_frame + frame_time).max(Instant::now());
+        }
+    }
+}
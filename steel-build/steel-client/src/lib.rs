@@ -1,18 +1,32 @@
 //! The game client for the [steel game engine](https://github.com/SSSxCCC/steel).
 
+mod frame_limiter;
+mod logger;
+
 use egui_winit_vulkano::{Gui, GuiConfig};
+use frame_limiter::FrameLimiter;
 use glam::UVec2;
 use std::{error::Error, path::Path};
 use steel_common::{
-    app::{Command, DrawInfo, InitInfo, UpdateInfo},
+    app::{Command, DrawInfo, FullscreenMode, InitInfo, LogConfig, UpdateInfo, WindowRequest},
     asset::{AssetId, AssetInfo},
     platform::Platform,
 };
-use vulkano::{format::Format, image::ImageUsage};
-use vulkano_util::window::{VulkanoWindows, WindowDescriptor};
+use vulkano::{
+    format::Format,
+    image::ImageUsage,
+    swapchain::{CompositeAlpha, CompositeAlphas, PresentMode, SurfaceInfo, SwapchainCreateInfo},
+};
+use vulkano_util::{
+    context::VulkanoContext,
+    renderer::VulkanoWindowRenderer,
+    window::{VulkanoWindows, WindowDescriptor},
+};
 use winit::{
+    dpi::PhysicalSize,
     event::{Event, WindowEvent},
-    event_loop::{ControlFlow, EventLoop, EventLoopBuilder},
+    event_loop::{ControlFlow, EventLoop, EventLoopBuilder, EventLoopWindowTarget},
+    window::Fullscreen,
 };
 
 #[cfg(target_os = "android")]
@@ -21,30 +35,31 @@ use winit::platform::android::activity::AndroidApp;
 #[cfg(target_os = "android")]
 #[no_mangle]
 fn android_main(app: AndroidApp) {
-    android_logger::init_once(
-        android_logger::Config::default().with_max_level(log::LevelFilter::Trace),
-    );
     use winit::platform::android::EventLoopBuilderExtAndroid;
     let event_loop = EventLoopBuilder::new()
         .with_android_app(app.clone())
         .build();
-    let platform = Platform::new(app);
-    _main(event_loop, platform);
+    let platform = Platform::new(app.clone());
+    _main(event_loop, platform, |log_config| {
+        logger::init_android(&app, log_config)
+    });
 }
 
 #[cfg(not(target_os = "android"))]
 #[allow(dead_code)]
 fn main() {
-    env_logger::builder()
-        .filter_level(log::LevelFilter::Trace)
-        .parse_default_env()
-        .init();
     let event_loop = EventLoopBuilder::new().build();
     let platform = Platform::new_client();
-    _main(event_loop, platform);
+    _main(event_loop, platform, logger::init_desktop);
 }
 
-fn _main(event_loop: EventLoop<()>, platform: Platform) {
+fn _main(event_loop: EventLoop<()>, platform: Platform, init_logger: impl FnOnce(LogConfig)) {
+    // app, the logger is initialized with the log config of app as early as possible
+    let mut app = steel::create();
+    let mut log_config = LogConfig::default();
+    app.command(Command::GetLogConfig(&mut log_config));
+    init_logger(log_config);
+
     // graphics
     let (context, ray_tracing_supported) = steel_common::create_context();
     let mut windows = VulkanoWindows::default();
@@ -55,8 +70,11 @@ fn _main(event_loop: EventLoop<()>, platform: Platform) {
     // egui
     let mut gui = None;
 
-    // app
-    let mut app = steel::create();
+    // the transparent background requested by the game, which is used when the window is created
+    let mut transparent = false;
+
+    // limit the frame rate to the target fps requested by the game
+    let mut frame_limiter = FrameLimiter::new();
 
     // insert all assets into AssetManager
     let insert_asset_fn = |asset_info_file: &Path| -> Result<(), Box<dyn Error>> {
@@ -96,16 +114,17 @@ fn _main(event_loop: EventLoop<()>, platform: Platform) {
     event_loop.run(move |event, event_loop, control_flow| match event {
         Event::Resumed => {
             log::debug!("Event::Resumed");
-            windows.create_window(
-                &event_loop,
-                &context,
-                &WindowDescriptor::default(),
-                |info| {
-                    info.image_format = Format::B8G8R8A8_UNORM; // for egui, see https://github.com/hakolao/egui_winit_vulkano
-                    info.image_usage |= ImageUsage::STORAGE;
-                },
-            );
-            let renderer = windows.get_primary_renderer().unwrap();
+            let mut window_request = WindowRequest::default();
+            app.command(Command::TakeWindowRequest(&mut window_request));
+            if let Some(t) = window_request.transparent {
+                transparent = t;
+            }
+            if let Some(target_fps) = window_request.target_fps {
+                frame_limiter.set_target_fps(target_fps);
+            }
+            create_window(&mut windows, event_loop, &context, transparent);
+            let renderer = windows.get_primary_renderer_mut().unwrap();
+            apply_window_request(renderer, window_request);
             log::info!("Swapchain image format: {:?}", renderer.swapchain_format());
             gui = Some(Gui::new(
                 &event_loop,
@@ -146,13 +165,14 @@ fn _main(event_loop: EventLoop<()>, platform: Platform) {
                 }
                 _ => (),
             }
-            // Warning: event.to_static() may drop some events, like ScaleFactorChanged
+            // Warning: event.to_static() may drop some events, like ScaleFactorChanged which is only passed to gui above
             // TODO: find a way to deliver all events to WinitInputHelper
             if let Some(event) = event.to_static() {
                 events.push(event);
             }
         }
         Event::RedrawRequested(_) => {
+            frame_limiter.frame_started();
             app.command(Command::UpdateInput(&events));
             events.clear();
             if let Some(renderer) = windows.get_primary_renderer_mut() {
@@ -170,12 +190,23 @@ fn _main(event_loop: EventLoop<()>, platform: Platform) {
                     ctx: &gui.egui_ctx,
                 });
 
+                let mut window_request = WindowRequest::default();
+                app.command(Command::TakeWindowRequest(&mut window_request));
+                if let Some(t) = window_request.transparent {
+                    transparent = t; // applied when the window is created next time
+                }
+                if let Some(target_fps) = window_request.target_fps {
+                    frame_limiter.set_target_fps(target_fps);
+                }
+                apply_window_request(renderer, window_request);
+
                 gpu_future = app.draw(DrawInfo {
                     before_future: gpu_future,
                     context: &context,
                     renderer: &renderer,
                     image: renderer.swapchain_image_view(),
                     window_size: UVec2::from_array(renderer.swapchain_image_size()),
+                    msaa_samples: 1,
                     editor_info: None,
                 });
 
@@ -186,9 +217,125 @@ fn _main(event_loop: EventLoop<()>, platform: Platform) {
         }
         Event::MainEventsCleared => {
             if let Some(renderer) = windows.get_primary_renderer() {
-                renderer.window().request_redraw()
+                if let Some(next_frame) = frame_limiter.wait_until() {
+                    *control_flow = ControlFlow::WaitUntil(next_frame);
+                } else {
+                    *control_flow = ControlFlow::Poll;
+                    renderer.window().request_redraw()
+                }
             }
         }
         _ => (),
     });
 }
+
+/// Set the swapchain image format and usage which are required by egui and steel.
+fn modify_swapchain(info: &mut SwapchainCreateInfo) {
+    info.image_format = Format::B8G8R8A8_UNORM; // for egui, see https://github.com/hakolao/egui_winit_vulkano
+    info.image_usage |= ImageUsage::STORAGE;
+}
+
+/// Like [modify_swapchain], but also blend the window with the desktop by pre-multiplied alpha.
+fn modify_swapchain_pre_multiplied(info: &mut SwapchainCreateInfo) {
+    modify_swapchain(info);
+    info.composite_alpha = CompositeAlpha::PreMultiplied;
+}
+
+/// Like [modify_swapchain], but also blend the window with the desktop by post-multiplied alpha.
+fn modify_swapchain_post_multiplied(info: &mut SwapchainCreateInfo) {
+    modify_swapchain(info);
+    info.composite_alpha = CompositeAlpha::PostMultiplied;
+}
+
+/// Create the game window. If transparent is true, the window is created again with a composite alpha mode
+/// which blends the window with the desktop, because the supported composite alpha modes can only be
+/// queried after the surface is created. The window stays opaque if no such composite alpha mode is supported.
+fn create_window(
+    windows: &mut VulkanoWindows,
+    event_loop: &EventLoopWindowTarget<()>,
+    context: &VulkanoContext,
+    transparent: bool,
+) {
+    let window_descriptor = WindowDescriptor {
+        transparent,
+        ..Default::default()
+    };
+    let window_id =
+        windows.create_window(event_loop, context, &window_descriptor, modify_swapchain);
+    if !transparent {
+        return;
+    }
+    let renderer = windows.get_primary_renderer().unwrap();
+    let supported_composite_alpha = renderer
+        .graphics_queue()
+        .device()
+        .physical_device()
+        .surface_capabilities(&renderer.surface(), SurfaceInfo::default())
+        .map(|capabilities| capabilities.supported_composite_alpha)
+        .unwrap_or(CompositeAlphas::empty());
+    let modify: fn(&mut SwapchainCreateInfo) = if supported_composite_alpha
+        .intersects(CompositeAlphas::PRE_MULTIPLIED)
+    {
+        modify_swapchain_pre_multiplied
+    } else if supported_composite_alpha.intersects(CompositeAlphas::POST_MULTIPLIED) {
+        modify_swapchain_post_multiplied
+    } else {
+        log::warn!("create_window: transparent window is not supported, supported_composite_alpha={supported_composite_alpha:?}");
+        return;
+    };
+    windows.remove_renderer(window_id);
+    windows.create_window(event_loop, context, &window_descriptor, modify);
+}
+
+/// Apply the window changes requested by the game to the game window.
+fn apply_window_request(renderer: &mut VulkanoWindowRenderer, request: WindowRequest) {
+    let window = renderer.window();
+    if let Some(title) = request.title {
+        window.set_title(&title);
+    }
+    if let Some(size) = request.size {
+        window.set_inner_size(PhysicalSize::new(size.x, size.y));
+    }
+    if let Some(fullscreen) = request.fullscreen {
+        let fullscreen = match fullscreen {
+            FullscreenMode::Windowed => None,
+            FullscreenMode::Borderless => Some(Fullscreen::Borderless(None)),
+            FullscreenMode::Exclusive => Some(
+                window
+                    .current_monitor()
+                    .and_then(|monitor| {
+                        monitor.video_modes().max_by_key(|mode| {
+                            (mode.size().width * mode.size().height, mode.refresh_rate_millihertz())
+                        })
+                    })
+                    .map(Fullscreen::Exclusive)
+                    .unwrap_or_else(|| {
+                        log::warn!("apply_window_request: no video mode for exclusive fullscreen, fall back to borderless");
+                        Fullscreen::Borderless(None)
+                    }),
+            ),
+        };
+        window.set_fullscreen(fullscreen);
+    }
+    if let Some(vsync) = request.vsync {
+        let present_mode = if vsync {
+            PresentMode::Fifo // Fifo is always supported
+        } else {
+            let supported_present_modes: Vec<_> = renderer
+                .graphics_queue()
+                .device()
+                .physical_device()
+                .surface_present_modes(&renderer.surface(), SurfaceInfo::default())
+                .map(|present_modes| present_modes.collect())
+                .unwrap_or_default();
+            [PresentMode::Mailbox, PresentMode::Immediate]
+                .into_iter()
+                .find(|present_mode| supported_present_modes.contains(present_mode))
+                .unwrap_or_else(|| {
+                    log::warn!("apply_window_request: can not disable vsync because no present mode without vsync is supported");
+                    PresentMode::Fifo
+                })
+        };
+        renderer.set_present_mode(present_mode);
+    }
+}
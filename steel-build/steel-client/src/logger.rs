@@ -0,0 +1,175 @@
+use log::{LevelFilter, Log, Metadata, Record};
+use std::{
+    fs::{self, File, OpenOptions},
+    io::Write,
+    path::PathBuf,
+    sync::Mutex,
+    time::{SystemTime, UNIX_EPOCH},
+};
+use steel_common::app::LogConfig;
+
+/// The max size in bytes of the log file, the log file is rotated when it is going to exceed this size.
+const MAX_LOG_FILE_SIZE: u64 = 10 * 1024 * 1024;
+/// The number of rotated log files to keep, which are named like "steel.log.1", "steel.log.2".
+const MAX_ROTATED_LOG_FILES: u32 = 3;
+
+/// The default log level, which is Trace in debug build and Info in release build.
+fn default_level() -> LevelFilter {
+    if cfg!(debug_assertions) {
+        LevelFilter::Trace
+    } else {
+        LevelFilter::Info
+    }
+}
+
+/// A log file which is rotated when it is too large.
+struct RotatingFile {
+    path: PathBuf,
+    file: File,
+    size: u64,
+}
+
+impl RotatingFile {
+    fn open(path: PathBuf) -> std::io::Result<Self> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let file = OpenOptions::new().create(true).append(true).open(&path)?;
+        let size = file.metadata()?.len();
+        Ok(RotatingFile { path, file, size })
+    }
+
+    fn rotated_path(&self, i: u32) -> PathBuf {
+        let mut path = self.path.clone().into_os_string();
+        path.push(format!(".{i}"));
+        path.into()
+    }
+
+    /// Rename "steel.log" to "steel.log.1", "steel.log.1" to "steel.log.2" and so on,
+    /// the oldest one is overwritten, then create a new empty "steel.log".
+    fn rotate(&mut self) -> std::io::Result<()> {
+        for i in (1..MAX_ROTATED_LOG_FILES).rev() {
+            let from = self.rotated_path(i);
+            if from.exists() {
+                fs::rename(from, self.rotated_path(i + 1))?;
+            }
+        }
+        fs::rename(&self.path, self.rotated_path(1))?;
+        self.file = File::create(&self.path)?;
+        self.size = 0;
+        Ok(())
+    }
+
+    fn write_line(&mut self, line: &str) -> std::io::Result<()> {
+        if self.size > 0 && self.size + line.len() as u64 > MAX_LOG_FILE_SIZE {
+            self.rotate()?;
+        }
+        self.file.write_all(line.as_bytes())?;
+        self.size += line.len() as u64;
+        Ok(())
+    }
+}
+
+/// ClientLogger forwards log records to the platform logger, and also writes them to a rotating log file if it is set.
+struct ClientLogger {
+    inner: Box<dyn Log>,
+    file: Option<Mutex<RotatingFile>>,
+}
+
+impl Log for ClientLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        self.inner.enabled(metadata)
+    }
+
+    fn log(&self, record: &Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+        self.inner.log(record);
+        if let Some(file) = &self.file {
+            let time = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default();
+            let line = format!(
+                "[{}.{:03} {} {}] {}\n",
+                time.as_secs(),
+                time.subsec_millis(),
+                record.level(),
+                record.target(),
+                record.args()
+            );
+            if let Ok(mut file) = file.lock() {
+                let _ = file.write_line(&line);
+            }
+        }
+    }
+
+    fn flush(&self) {
+        self.inner.flush();
+        if let Some(file) = &self.file {
+            if let Ok(mut file) = file.lock() {
+                let _ = file.file.flush();
+            }
+        }
+    }
+}
+
+/// Set ClientLogger as the global logger, log_file is not used if it can not be opened.
+fn init(inner: Box<dyn Log>, max_level: LevelFilter, log_file: Option<PathBuf>) {
+    let mut open_error = None;
+    let file = log_file.and_then(|path| match RotatingFile::open(path.clone()) {
+        Ok(file) => Some(Mutex::new(file)),
+        Err(e) => {
+            open_error = Some((path, e));
+            None
+        }
+    });
+    if log::set_boxed_logger(Box::new(ClientLogger { inner, file })).is_ok() {
+        log::set_max_level(max_level);
+    }
+    if let Some((path, e)) = open_error {
+        log::warn!(
+            "logger::init: failed to open log file {}, error: {e}",
+            path.display()
+        );
+    }
+}
+
+/// Init logger on desktop with the log config of the game. The log level is overridden by STEEL_LOG environment
+/// variable, or RUST_LOG if STEEL_LOG is not set, which has the same syntax as RUST_LOG of env_logger.
+/// The log file is overridden by STEEL_LOG_FILE environment variable.
+#[cfg(not(target_os = "android"))]
+pub fn init_desktop(log_config: LogConfig) {
+    let filter_env = if std::env::var_os("STEEL_LOG").is_some() {
+        "STEEL_LOG"
+    } else {
+        "RUST_LOG"
+    };
+    let logger = env_logger::Builder::new()
+        .filter_level(log_config.level.unwrap_or_else(default_level))
+        .parse_env(env_logger::Env::new().filter(filter_env))
+        .build();
+    let max_level = logger.filter();
+    let log_file = std::env::var_os("STEEL_LOG_FILE")
+        .map(PathBuf::from)
+        .or(log_config.file);
+    init(Box::new(logger), max_level, log_file);
+}
+
+/// Init logger on android with the log config of the game. Logs are written to logcat by android_logger,
+/// and also written to the log file in the internal data directory of the app, which is "logs/steel.log" by default.
+#[cfg(target_os = "android")]
+pub fn init_android(app: &winit::platform::android::activity::AndroidApp, log_config: LogConfig) {
+    let max_level = log_config.level.unwrap_or_else(default_level);
+    let logger = android_logger::AndroidLogger::new(
+        android_logger::Config::default().with_max_level(max_level),
+    );
+    let log_file = app.internal_data_path().map(|dir| {
+        dir.join(
+            log_config
+                .file
+                .unwrap_or_else(|| PathBuf::from("logs").join("steel.log")),
+        )
+    });
+    init(Box::new(logger), max_level, log_file);
+}
@@ -1,11 +1,11 @@
 use crate::{
     asset::AssetId,
     camera::SceneCamera,
-    data::{EntitiesData, EntityIdWithPath, PrefabData, SceneData, WorldData},
+    data::{Data, EntitiesData, EntityIdPath, EntityIdWithPath, PrefabData, SceneData, WorldData},
     platform::Platform,
 };
-use glam::UVec2;
-use shipyard::EntityId;
+use glam::{Quat, UVec2, Vec3};
+use shipyard::{EntityId, World};
 use std::{
     collections::HashMap,
     path::{Path, PathBuf},
@@ -23,6 +23,13 @@ pub trait App {
     fn draw(&mut self, info: DrawInfo) -> Box<dyn GpuFuture>;
     fn command(&self, cmd: Command);
     fn command_mut(&mut self, cmd: CommandMut);
+    /// Run f with direct access to the ecs world, so that you can run any shipyard query without adding a new [Command].
+    /// Be careful when using this function:
+    /// 1. In steel-editor, the app is loaded from a dynamic library, component and unique types are only
+    ///    guaranteed to match the types in the world if they come from the same compiled steel-engine and game code.
+    /// 2. Do not delete uniques which are added by steel-engine, and do not keep any borrow of the world after f returns.
+    /// 3. Changes bypass the validation of [Command] and [CommandMut], like the limits of [crate::data::Data].
+    fn with_world(&mut self, f: &mut dyn FnMut(&mut World));
 }
 
 /// The InitInfo contains some initialization data, and is passed to [App::init].
@@ -52,6 +59,9 @@ pub struct DrawInfo<'a> {
     pub image: Arc<ImageView>,
     /// The window size, this is the pixel size of the image we will draw.
     pub window_size: UVec2,
+    /// The number of samples per pixel for multisample anti-aliasing of the rasterization pipeline,
+    /// 1 means no multisampling. Unsupported values are lowered to the closest supported sample count.
+    pub msaa_samples: u32,
     /// If editor_info is some, we are drawing for the editor window.
     pub editor_info: Option<EditorInfo<'a>>,
 }
@@ -60,23 +70,60 @@ pub struct DrawInfo<'a> {
 /// and is contained in DrawInfo, which is passed to [App::draw] every frame.
 pub struct EditorInfo<'a> {
     pub camera: &'a SceneCamera,
+    /// If some, only this entity and its descendants are drawn, which is useful to debug rendering of an entity.
+    pub solo_entity: Option<EntityId>,
 }
 
 /// Command is sent by editor through [App::command] method to read the game world.
 pub enum Command<'a> {
     Save(&'a mut WorldData),
+    /// Like [Command::Save], but transient uniques are skipped, used to save scene files.
+    SaveScene(&'a mut WorldData),
+    /// Save the current world with persistent uniques as [SceneData] to a file at path, which is useful
+    /// for save games. path, out_saved.
+    SaveSceneToFile(&'a Path, &'a mut bool),
 
     GetEntityCount(&'a mut usize),
+    /// Get the entity count, component counts, collider count, and draw counts of the world, used by the scene statistics window.
+    GetSceneStats(&'a mut SceneStats),
+    /// Get the axis-aligned bounding box (min, max) enclosing everything drawn in last frame and all 2D colliders,
+    /// which is useful to frame the whole scene. The out_bounds is None if there is nothing. out_bounds.
+    GetWorldBounds(&'a mut Option<(Vec3, Vec3)>),
     /// window_index (WindowIndex::GAME or WindowIndex::SCENE), screen_position, out_eid.
     GetEntityAtScreen(usize, UVec2, &'a mut EntityId),
+    /// Like [Command::GetEntityAtScreen], but also get the world position at screen position by reading depth.
+    /// The out_position is None if nothing is drawn there or the window is drawn by ray tracing.
+    /// window_index, screen_position, out_eid, out_position.
+    GetEntityAndPositionAtScreen(usize, UVec2, &'a mut EntityId, &'a mut Option<Vec3>),
+    /// Find all component values that reference an entity, useful to warn before destroying the entity.
+    /// eid, out_references: Vec<(entity, component_name, value_name)>.
+    FindReferencesTo(EntityId, &'a mut Vec<(EntityId, String, String)>),
+    /// Get the data of an entity and all its descendants, the entity is kept as the first entity in out_entities_data,
+    /// which is empty if the entity does not exist. eid, out_entities_data.
+    GetEntitiesDataOfEntity(EntityId, &'a mut EntitiesData),
 
     GetComponents(&'a mut Vec<&'static str>),
+    /// Get the names of all registered uniques.
+    GetUniques(&'a mut Vec<String>),
+    /// Get the data of the default value of a component by component name, used to reset component in editor.
+    GetDefaultComponentData(&'a str, &'a mut Option<Data>),
+    /// Get the data of the default value of a unique by unique name, used to reset unique in editor.
+    /// The default value is the value added by SteelApp::add_and_register_unique, None if there is no default value.
+    GetDefaultUniqueData(&'a str, &'a mut Option<Data>),
 
     UpdateInput(&'a Vec<WindowEvent<'static>>),
 
     ResetTime,
 
+    /// Take the window changes requested by the game since last time, used by steel-client to apply them to the game window.
+    TakeWindowRequest(&'a mut WindowRequest),
+    /// Get the logger settings of the game, used by steel-client to initialize the logger before [App::init].
+    GetLogConfig(&'a mut LogConfig),
+
     GetAssetPath(AssetId, &'a mut Option<PathBuf>),
+    /// Get all assets of an asset type like "scene" with their paths relative to the root asset directory,
+    /// sorted by path, see [crate::data::Limit::asset_type]. asset_type, out_assets.
+    GetAssetsOfType(&'a str, &'a mut Vec<(AssetId, PathBuf)>),
     GetAssetContent(AssetId, &'a mut Option<Arc<Vec<u8>>>),
     AssetIdExists(AssetId, &'a mut bool),
     InsertAsset(AssetId, PathBuf),
@@ -85,6 +132,8 @@ pub enum Command<'a> {
     UpdateAssetPath(AssetId, PathBuf),
 
     GetPrefabData(AssetId, &'a mut Option<Arc<PrefabData>>),
+    /// Get where an entity comes from if it is created from a prefab, out_prefab_info is None if it is not. eid, out_prefab_info.
+    GetPrefabInfo(EntityId, &'a mut Option<PrefabInfo>),
     /// prefab_root_entity, prefab_asset, prefab_root_entity_to_nested_prefabs_index
     CreatePrefab(EntityId, AssetId, HashMap<EntityId, u64>),
     /// prefab_root_entity, prefab_asset, entity_id_to_prefab_entity_id_with_path
@@ -94,22 +143,120 @@ pub enum Command<'a> {
 /// CommandMut is sent by editor through [App::command] method to modify the game world.
 pub enum CommandMut<'a> {
     Load(&'a WorldData),
+    /// Apply entities_data onto the world without touching other entities, which is more efficient than
+    /// [CommandMut::Load] when only a few entities are changed. Components of existing entities are updated
+    /// or created, and missing entities are added with new ids like [CommandMut::AddEntities].
+    /// entities_data, old_id_to_new_id map of the added entities.
+    LoadEntities(&'a EntitiesData, &'a mut HashMap<EntityId, EntityId>),
     Reload(&'a SceneData),
+    /// Clear the world and load it from a [SceneData] file at path which is saved by [Command::SaveSceneToFile],
+    /// the current scene is unchanged. path, out_loaded.
+    LoadSceneFromFile(&'a Path, &'a mut bool),
+    /// Destroy all entities and reset uniques to their registered defaults without reloading any asset.
+    ClearScene,
     SetCurrentScene(Option<AssetId>),
+    /// Reset a unique to its default value like [CommandMut::ClearScene] does, see [Command::GetDefaultUniqueData].
+    /// Does nothing if the unique has no default value.
+    ResetUnique(&'a str),
 
-    CreateEntity,
+    /// Create a new entity at position, the entity is named "Entity N" which is not used by other entities.
+    CreateEntity(Vec3),
     /// entities_data, old_id_to_new_id map
     AddEntities(&'a EntitiesData, &'a mut HashMap<EntityId, EntityId>),
+    /// Duplicate an entity and its descendants with new entity ids, the new entity is attached next to the
+    /// original entity. Returns the new entity id, or EntityId::dead() if the original entity does not exist.
+    DuplicateEntity(EntityId, &'a mut EntityId),
+    /// Create the entities of a prefab with the root entity at position, rotation, and scale, which is useful
+    /// for spawning bullets or enemies at runtime. Returns the root entity, or EntityId::dead() if failed.
+    /// prefab_asset, position, rotation, scale, out_root_entity.
+    InstantiatePrefab(AssetId, Vec3, Quat, Vec3, &'a mut EntityId),
     DestroyEntity(EntityId),
     ClearEntity,
 
     CreateComponent(EntityId, &'static str),
+    /// Set the values of one component of an entity directly, which is more efficient than [CommandMut::Load]
+    /// for targeted edits. The values are validated by the limits of the component. Does nothing if the
+    /// entity does not have the component. entity, component_name, component_data.
+    SetComponentData(EntityId, &'a str, &'a Data),
     DestroyComponent(EntityId, &'a String),
 
     // attached_entity, parent, before
     AttachBefore(EntityId, EntityId, EntityId),
     // attached_entity, parent, after
     AttachAfter(EntityId, EntityId, EntityId),
+    /// Swap an entity with its previous sibling without changing its parent,
+    /// does nothing if the entity is already the first child.
+    MoveEntityUp(EntityId),
+    /// Swap an entity with its next sibling without changing its parent,
+    /// does nothing if the entity is already the last child.
+    MoveEntityDown(EntityId),
+}
+
+/// The WindowRequest contains the changes of the game window requested by the game through steel::window::Window unique,
+/// which is taken by steel-client through [Command::TakeWindowRequest] and applied to the winit window.
+/// None means that there is no change requested. The requests are ignored in editor.
+#[derive(Default)]
+pub struct WindowRequest {
+    pub title: Option<String>,
+    /// The requested inner size of the window in pixels.
+    pub size: Option<UVec2>,
+    pub fullscreen: Option<FullscreenMode>,
+    /// The requested vertical synchronization, vsync off uses mailbox or immediate present mode if supported.
+    pub vsync: Option<bool>,
+    /// The requested transparent background, which only takes effect when the window is created.
+    /// A transparent window is blended with the desktop by the alpha of the drawn image if supported.
+    pub transparent: Option<bool>,
+    /// The requested target frame rate, Some(None) means that the frame rate is not limited.
+    pub target_fps: Option<Option<u32>>,
+}
+
+/// The LogConfig contains the logger settings of the game, which is taken by steel-client through [Command::GetLogConfig].
+/// The settings are ignored in editor.
+#[derive(Clone, Debug, Default)]
+pub struct LogConfig {
+    /// The max log level, None means Trace in debug build and Info in release build.
+    /// On desktop, STEEL_LOG or RUST_LOG environment variable overrides it.
+    pub level: Option<log::LevelFilter>,
+    /// The path of the rotating log file that logs are also written to. A relative path is relative to the working
+    /// directory on desktop, or the internal data directory of the app on android. None means no log file on desktop,
+    /// or "logs/steel.log" on android. On desktop, STEEL_LOG_FILE environment variable overrides it.
+    pub file: Option<PathBuf>,
+}
+
+/// The SceneStats contains some statistics about the world, which is filled by [Command::GetSceneStats].
+#[derive(Clone, Debug, Default)]
+pub struct SceneStats {
+    pub entity_count: usize,
+    /// (component_name, entity_count) of all registered components, sorted by entity_count in descending order.
+    pub component_counts: Vec<(String, usize)>,
+    /// The number of colliders in the 2D physics world, 0 if 2D physics is not used.
+    pub collider_count: usize,
+    /// (shape_name, draw_count) of the shapes submitted to the canvas in last frame, like "Rectangle" and "Model".
+    pub draw_counts: Vec<(String, usize)>,
+}
+
+/// The PrefabInfo describes the prefab that an entity is created from, which is filled by [Command::GetPrefabInfo].
+#[derive(Clone, Debug)]
+pub struct PrefabInfo {
+    /// The asset id of the prefab.
+    pub asset: AssetId,
+    /// The entity id index of this entity in the prefab.
+    pub entity_index: u64,
+    /// The entity id path of the nested prefab in the prefab that this entity belongs to, empty if not nested.
+    pub entity_path: EntityIdPath,
+    /// The root entity of the prefab instance in the world.
+    pub root_entity: EntityId,
+}
+
+/// The fullscreen mode of the game window.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum FullscreenMode {
+    #[default]
+    Windowed,
+    /// Borderless window which covers the whole monitor.
+    Borderless,
+    /// Exclusive fullscreen with the video mode of the monitor, fall back to borderless if there is no video mode.
+    Exclusive,
 }
 
 /// Helper struct to define window index constants: WindowIndex::GAME and WindowIndex::SCENE.
@@ -1,6 +1,16 @@
 use crate::data::{Data, Limit, Value};
 use glam::{Quat, Vec3};
 
+/// The forward direction vector of a camera with rotation, which is shared by [SceneCamera] and the cameras in game.
+pub fn camera_direction(rotation: Quat) -> Vec3 {
+    let rotation = rotation.to_scaled_axis(); // x: pitch, y: yaw, z: roll
+    Vec3::new(
+        rotation.y.sin() * rotation.x.cos(),
+        rotation.x.sin(),
+        -rotation.y.cos() * rotation.x.cos(),
+    )
+}
+
 /// Camera info for scene window.
 #[derive(Debug, Clone, Copy)]
 pub struct SceneCamera {
@@ -30,6 +40,52 @@ impl SceneCamera {
         };
     }
 
+    /// The camera forward direction vector, see [camera_direction].
+    pub fn direction(&self) -> Vec3 {
+        camera_direction(self.rotation)
+    }
+
+    /// The point that this camera is looking at, which is the center of the view at a sensible depth:
+    /// * For orthographic camera, this is the camera position on the z = 0 plane.
+    /// * For perspective camera, this is [SceneCamera::FOCUS_DISTANCE] in front of the camera.
+    pub fn focus_point(&self) -> Vec3 {
+        match self.settings {
+            CameraSettings::Orthographic { .. } => self.position.truncate().extend(0.0),
+            CameraSettings::Perspective { .. } => {
+                self.position + self.direction() * Self::FOCUS_DISTANCE
+            }
+        }
+    }
+
+    /// The distance from perspective camera to its focus point.
+    pub const FOCUS_DISTANCE: f32 = 10.0;
+
+    /// The min view size of [SceneCamera::frame].
+    pub const MIN_FRAME_SIZE: f32 = 1.0;
+
+    /// Move the camera so that the axis-aligned bounding box from min to max is in view, keeping the rotation:
+    /// * For orthographic camera, the camera is centered on the box and the view size is set to the box size.
+    /// * For perspective camera, the camera is moved back along its direction until the box's bounding sphere fits the fov.
+    ///
+    /// The view size is at least [SceneCamera::MIN_FRAME_SIZE], so that a flat or empty box does not make the view degenerate.
+    pub fn frame(&mut self, min: Vec3, max: Vec3) {
+        const MARGIN: f32 = 1.1;
+        let center = (min + max) / 2.0;
+        let size = (max - min) * MARGIN;
+        match &mut self.settings {
+            CameraSettings::Orthographic { width, height, .. } => {
+                self.position = center.truncate().extend(self.position.z);
+                *width = size.x.max(Self::MIN_FRAME_SIZE);
+                *height = size.y.max(Self::MIN_FRAME_SIZE);
+            }
+            CameraSettings::Perspective { fov, .. } => {
+                let radius = size.length().max(Self::MIN_FRAME_SIZE) / 2.0;
+                let distance = radius / (*fov / 2.0).sin();
+                self.position = center - self.direction() * distance;
+            }
+        }
+    }
+
     /// Create a [Data] that contains all values in self.
     pub fn get_data(&self) -> Data {
         let mut data = Data::new()
@@ -68,7 +124,8 @@ pub enum CameraSettings {
     Perspective {
         /// The y fov radians.
         fov: f32,
-        /// Must be greater than zero.
+        /// Must be greater than zero. Depth precision mostly depends on near,
+        /// so keep it as large as possible to avoid z-fighting for distant geometry.
         near: f32,
         /// Must be greater than zero.
         far: f32,
@@ -6,7 +6,7 @@ use shipyard::EntityId;
 use std::{
     borrow::Cow,
     collections::{HashMap, HashSet},
-    ops::RangeInclusive,
+    ops::{Range, RangeInclusive},
     sync::Arc,
 };
 
@@ -16,6 +16,9 @@ pub enum Limit {
     /// Limit i32 value to a range.
     /// Int32Range can be used in IVec types and VecInt32 to apply to all values.
     Int32Range(RangeInclusive<i32>),
+    /// Limit i32 value to a range and to multiples of a step from the range start, for example a grid size
+    /// in multiples of 8. The step must be greater than 0. See [Limit::snap_to_step].
+    Int32RangeStep(RangeInclusive<i32>, i32),
     /// Limit i32 value to serval values and use String to display them.
     Int32Enum(Vec<(i32, String)>),
     /// Limit i64 value to a range.
@@ -42,6 +45,9 @@ pub enum Limit {
     Vec4Color,
     /// Display String in multiline text edit.
     StringMultiline,
+    /// Limit String value to serval values, steel-editor shows them in a combo box.
+    /// A value which is not in the list is kept and is shown as missing.
+    StringEnum(Vec<String>),
     /// The value can not be changed.
     ReadOnly,
     /// Limit each element in a Vec2/Vec3/Vec4 to a different range.
@@ -50,6 +56,33 @@ pub enum Limit {
     IVecRange(Vec<Option<RangeInclusive<i32>>>),
     /// Limit each element in a UVec2/UVec3/UVec4 to a different range.
     UVecRange(Vec<Option<RangeInclusive<u32>>>),
+    /// Limit AssetId value to assets whose file extensions are in this list, extensions are lowercase without dot.
+    /// steel-editor only shows these assets when selecting. See [Limit::asset_type].
+    AssetExtensions(Vec<String>),
+}
+
+impl Limit {
+    /// Create a [Limit::AssetExtensions] of an asset type, which can be "texture", "model", "scene" or "prefab".
+    /// Other asset_type is regarded as a file extension.
+    pub fn asset_type(asset_type: &str) -> Limit {
+        let extensions: &[&str] = match asset_type {
+            "texture" | "image" => &["png", "jpg", "jpeg", "bmp", "tga", "gif", "webp"],
+            "model" => &["obj"],
+            "scene" => &["scene"],
+            "prefab" => &["prefab"],
+            extension => &[extension],
+        };
+        Limit::AssetExtensions(extensions.iter().map(|e| e.to_string()).collect())
+    }
+
+    /// Snap v to the nearest value of start + n * step inside range, which is the rule of [Limit::Int32RangeStep].
+    pub fn snap_to_step(v: i32, range: &RangeInclusive<i32>, step: i32) -> i32 {
+        let (start, end) = (*range.start() as i64, *range.end() as i64);
+        let step = step.max(1) as i64;
+        let n = ((v as i64 - start) as f64 / step as f64).round().max(0.0) as i64;
+        let n = n.min((end - start).max(0) / step);
+        (start + n * step) as i32
+    }
 }
 
 /// Value is a data which stores in component or unique.
@@ -86,6 +119,220 @@ pub enum Value {
     VecString(Vec<String>),
     VecEntity(Vec<EntityId>),
     VecAsset(Vec<AssetId>),
+    /// A min-max pair of f32, min is always less than or equal to max.
+    /// Float32Range limit can be used to apply to both min and max.
+    RangeFloat32(MinMax<f32>),
+    /// A min-max pair of i32, min is always less than or equal to max.
+    /// Int32Range limit can be used to apply to both min and max.
+    RangeInt32(MinMax<i32>),
+}
+
+/// MinMax is a pair of min and max values, which is useful for tunables like spawn delay between min and max.
+/// It is stored in [Value::RangeFloat32] or [Value::RangeInt32], which guarantees that min <= max.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq)]
+pub struct MinMax<T> {
+    pub min: T,
+    pub max: T,
+}
+
+impl<T: PartialOrd> MinMax<T> {
+    /// Create a new MinMax, min and max are swapped if min > max.
+    pub fn new(min: T, max: T) -> Self {
+        if min > max {
+            MinMax { min: max, max: min }
+        } else {
+            MinMax { min, max }
+        }
+    }
+
+    /// Returns true if v is in [min, max].
+    pub fn contains(&self, v: &T) -> bool {
+        *v >= self.min && *v <= self.max
+    }
+}
+
+impl<T: PartialOrd> From<Range<T>> for MinMax<T> {
+    fn from(range: Range<T>) -> Self {
+        MinMax::new(range.start, range.end)
+    }
+}
+
+impl<T> From<MinMax<T>> for Range<T> {
+    fn from(min_max: MinMax<T>) -> Self {
+        min_max.min..min_max.max
+    }
+}
+
+impl Value {
+    /// Clamp this value into its limit and replace NaN or infinite floats with 0.
+    /// Returns true if this value is changed.
+    pub fn validate(&mut self, limit: Option<&Limit>) -> bool {
+        fn clamp<T: PartialOrd + Copy>(v: &mut T, range: &RangeInclusive<T>) -> bool {
+            let old = *v;
+            if *v < *range.start() {
+                *v = *range.start();
+            } else if *v > *range.end() {
+                *v = *range.end();
+            }
+            *v != old
+        }
+        fn step(v: &mut i32, range: &RangeInclusive<i32>, step: i32) -> bool {
+            let old = *v;
+            *v = Limit::snap_to_step(*v, range, step);
+            *v != old
+        }
+        fn finite32(v: &mut f32) -> bool {
+            if v.is_finite() {
+                false
+            } else {
+                *v = 0.0;
+                true
+            }
+        }
+        fn finite64(v: &mut f64) -> bool {
+            if v.is_finite() {
+                false
+            } else {
+                *v = 0.0;
+                true
+            }
+        }
+        fn float32(
+            v: &mut f32,
+            limit: Option<&Limit>,
+            range: Option<&RangeInclusive<f32>>,
+        ) -> bool {
+            let mut changed = finite32(v);
+            // rotation values are not wrapped, because wrapping each component of a
+            // scaled axis rotation separately changes the rotation
+            if let Some(Limit::Float32Range(range)) = limit {
+                changed |= clamp(v, range);
+            }
+            if let Some(range) = range {
+                changed |= clamp(v, range);
+            }
+            changed
+        }
+        fn float32s<'a>(vs: impl IntoIterator<Item = &'a mut f32>, limit: Option<&Limit>) -> bool {
+            let mut changed = false;
+            for (i, v) in vs.into_iter().enumerate() {
+                let range = match limit {
+                    Some(Limit::VecRange(ranges)) => ranges.get(i).and_then(|r| r.as_ref()),
+                    _ => None,
+                };
+                changed |= float32(v, limit, range);
+            }
+            changed
+        }
+        fn int32s<'a>(vs: impl IntoIterator<Item = &'a mut i32>, limit: Option<&Limit>) -> bool {
+            let mut changed = false;
+            for (i, v) in vs.into_iter().enumerate() {
+                changed |= match limit {
+                    Some(Limit::Int32Range(range)) => clamp(v, range),
+                    Some(Limit::Int32RangeStep(range, s)) => step(v, range, *s),
+                    Some(Limit::IVecRange(ranges)) => ranges
+                        .get(i)
+                        .and_then(|r| r.as_ref())
+                        .is_some_and(|range| clamp(v, range)),
+                    _ => false,
+                };
+            }
+            changed
+        }
+        fn uint32s<'a>(vs: impl IntoIterator<Item = &'a mut u32>, limit: Option<&Limit>) -> bool {
+            let mut changed = false;
+            for (i, v) in vs.into_iter().enumerate() {
+                changed |= match limit {
+                    Some(Limit::UInt32Range(range)) => clamp(v, range),
+                    Some(Limit::UVecRange(ranges)) => ranges
+                        .get(i)
+                        .and_then(|r| r.as_ref())
+                        .is_some_and(|range| clamp(v, range)),
+                    _ => false,
+                };
+            }
+            changed
+        }
+
+        match self {
+            Value::Int32(v) => match limit {
+                Some(Limit::Int32Enum(int_enum)) => {
+                    if !int_enum.is_empty() && !int_enum.iter().any(|(i, _)| i == v) {
+                        *v = int_enum[0].0;
+                        true
+                    } else {
+                        false
+                    }
+                }
+                _ => int32s([v], limit),
+            },
+            Value::Int64(v) => match limit {
+                Some(Limit::Int64Range(range)) => clamp(v, range),
+                _ => false,
+            },
+            Value::UInt32(v) => uint32s([v], limit),
+            Value::UInt64(v) => match limit {
+                Some(Limit::UInt64Range(range)) => clamp(v, range),
+                _ => false,
+            },
+            Value::Float32(v) => float32(v, limit, None),
+            Value::Float64(v) => {
+                let mut changed = finite64(v);
+                if let Some(Limit::Float64Range(range)) = limit {
+                    changed |= clamp(v, range);
+                }
+                changed
+            }
+            Value::Vec2(v) => float32s(v.as_mut(), limit),
+            Value::Vec3(v) => float32s(v.as_mut(), limit),
+            Value::Vec4(v) => float32s(v.as_mut(), limit),
+            Value::IVec2(v) => int32s(v.as_mut(), limit),
+            Value::IVec3(v) => int32s(v.as_mut(), limit),
+            Value::IVec4(v) => int32s(v.as_mut(), limit),
+            Value::UVec2(v) => uint32s(v.as_mut(), limit),
+            Value::UVec3(v) => uint32s(v.as_mut(), limit),
+            Value::UVec4(v) => uint32s(v.as_mut(), limit),
+            Value::VecInt32(v) => int32s(v, limit),
+            Value::VecInt64(v) => match limit {
+                Some(Limit::Int64Range(range)) => {
+                    v.iter_mut().fold(false, |c, v| clamp(v, range) | c)
+                }
+                _ => false,
+            },
+            Value::VecUInt32(v) => uint32s(v, limit),
+            Value::VecUInt64(v) => match limit {
+                Some(Limit::UInt64Range(range)) => {
+                    v.iter_mut().fold(false, |c, v| clamp(v, range) | c)
+                }
+                _ => false,
+            },
+            Value::VecFloat32(v) => v.iter_mut().fold(false, |c, v| float32(v, limit, None) | c),
+            Value::VecFloat64(v) => v.iter_mut().fold(false, |c, v| {
+                let mut changed = finite64(v);
+                if let Some(Limit::Float64Range(range)) = limit {
+                    changed |= clamp(v, range);
+                }
+                changed | c
+            }),
+            Value::RangeFloat32(v) => {
+                let mut changed = float32s([&mut v.min, &mut v.max], limit);
+                if v.min > v.max {
+                    v.max = v.min;
+                    changed = true;
+                }
+                changed
+            }
+            Value::RangeInt32(v) => {
+                let mut changed = int32s([&mut v.min, &mut v.max], limit);
+                if v.min > v.max {
+                    v.max = v.min;
+                    changed = true;
+                }
+                changed
+            }
+            _ => false,
+        }
+    }
 }
 
 /// Data contains all [Value] with [Limit] in a component or unique.
@@ -142,6 +389,35 @@ impl Data {
         self.values.get(name.as_ref())
     }
 
+    /// Check values in self against limits, returns a fixed copy of self if any value is
+    /// out of its limit or is a NaN or infinite float, see [Value::validate].
+    /// The second return value contains the names of the fixed values.
+    pub fn validate(&self, limits: &HashMap<String, Limit>) -> Option<(Data, Vec<String>)> {
+        let mut fixed: Option<(Data, Vec<String>)> = None;
+        for (name, value) in &self.values {
+            if matches!(
+                value,
+                Value::Bool(_)
+                    | Value::String(_)
+                    | Value::Entity(_)
+                    | Value::Asset(_)
+                    | Value::VecBool(_)
+                    | Value::VecString(_)
+                    | Value::VecEntity(_)
+                    | Value::VecAsset(_)
+            ) {
+                continue;
+            }
+            let mut value = value.clone();
+            if value.validate(limits.get(name)) {
+                let (data, names) = fixed.get_or_insert_with(|| (self.clone(), Vec::new()));
+                data.values.insert(name.clone(), value);
+                names.push(name.clone());
+            }
+        }
+        fixed
+    }
+
     /// Cut useless data in self before saving to file:
     /// 1. Erase generation value of [EntityId].
     /// 2. Skip read only values if cut_read_only is true.
@@ -364,6 +640,17 @@ impl EntitiesData {
         roots
     }
 
+    /// Sort entities by entity index so that they are saved to file in a stable order. The first entity
+    /// is not moved if keep_first is true, because the first entity may be the root entity of a prefab.
+    pub fn sort_by_index(&mut self, keep_first: bool) {
+        let first = keep_first
+            .then(|| self.get_index(0).map(|(&e, _)| e))
+            .flatten();
+        self.sort_by(|e1, _, e2, _| {
+            (Some(*e1) != first, e1.index()).cmp(&(Some(*e2) != first, e2.index()))
+        });
+    }
+
     /// Checks if all entities are directly or indirectly attached to the first entity.
     /// The returned map is a mapping of entities to whether they pass the check.
     fn check_attached_to_first_entity(&self) -> HashMap<EntityId, bool> {
@@ -450,6 +737,8 @@ impl UniquesData {
             let unique_data_cut = unique_data.cut(cut_read_only);
             uniques_data_cut.insert(unique_name.clone(), unique_data_cut);
         }
+        // sort by name so that uniques are saved to file in a stable order
+        uniques_data_cut.sort_keys();
         uniques_data_cut
     }
 
@@ -541,6 +830,48 @@ impl WorldData {
             uniques: self.uniques.cut(),
         }
     }
+
+    /// Find all component values that reference `eid`, returns a list of (entity, component_name, value_name).
+    /// Entities that will be destroyed together with `eid` (itself and its descendants) are skipped,
+    /// hierarchy and prefab components are also skipped because they are maintained by engine.
+    pub fn find_references_to(&self, eid: EntityId) -> Vec<(EntityId, String, String)> {
+        // collect eid and all its descendants
+        let mut destroyed = HashSet::new();
+        let mut entities_to_visit = vec![eid];
+        while let Some(e) = entities_to_visit.pop() {
+            if destroyed.insert(e) {
+                if let Some(entity_data) = self.entities.get(&e) {
+                    entities_to_visit.extend(entity_data.children().into_iter().flatten());
+                }
+            }
+        }
+
+        let mut references = Vec::new();
+        for (e, entity_data) in &self.entities {
+            if destroyed.contains(e) {
+                continue;
+            }
+            for (component_name, component_data) in &entity_data.components {
+                if component_name == "Parent"
+                    || component_name == "Children"
+                    || component_name == "Prefab"
+                {
+                    continue;
+                }
+                for (value_name, value) in &component_data.values {
+                    let is_reference = match value {
+                        Value::Entity(v) => *v == eid,
+                        Value::VecEntity(v) => v.contains(&eid),
+                        _ => false,
+                    };
+                    if is_reference {
+                        references.push((*e, component_name.clone(), value_name.clone()));
+                    }
+                }
+            }
+        }
+        references
+    }
 }
 
 /// SceneData is a compressed version of [WorldData].
@@ -570,9 +901,12 @@ impl SceneData {
         get_prefab_data_fn: impl Fn(AssetId) -> Option<Arc<PrefabData>> + Copy,
         prefab_data_override: Option<(EntityId, Arc<PrefabData>)>,
     ) -> Self {
+        // there is no root entity in world, so that all entities are sorted
+        let mut entities = world_data.entities.clone();
+        entities.sort_by_index(false);
         let (prefab_data, prefab_root_entity_to_nested_prefabs_index) =
             PrefabData::new_with_prefab_data_override(
-                &world_data.entities,
+                &entities,
                 get_prefab_data_fn,
                 prefab_data_override,
             );
@@ -602,6 +936,7 @@ impl SceneData {
     pub fn cut(&mut self) {
         self.entities.cut();
         self.uniques = self.uniques.cut();
+        self.unique_id_paths.sort_keys();
     }
 
     /// Convert [SceneData] to [WorldData]. Also return a map that maps every
@@ -678,11 +1013,16 @@ impl PrefabData {
         entities: &EntitiesData,
         get_prefab_data_fn: impl Fn(AssetId) -> Option<Arc<PrefabData>> + Copy,
     ) -> (Self, HashMap<EntityId, u64>) {
-        Self::new_with_prefab_data_override(entities, get_prefab_data_fn, None)
+        // the first entity is the root, so that it is kept first
+        let mut entities = entities.clone();
+        entities.sort_by_index(true);
+        Self::new_with_prefab_data_override(&entities, get_prefab_data_fn, None)
     }
 
     /// This function has an extra parameter 'prefab_data_override' compared to [Self::new].
     /// prefab_data_override defines a different [PrefabData] for an prefab root entity, this is used for updating a prefab.
+    /// The entities should be sorted by [EntitiesData::sort_by_index], so that the same entities always create the same
+    /// prefab data, which is important for clean diffs of scene and prefab files in version control.
     pub fn new_with_prefab_data_override(
         entities: &EntitiesData,
         get_prefab_data_fn: impl Fn(AssetId) -> Option<Arc<PrefabData>> + Copy,
@@ -1169,6 +1509,7 @@ impl PrefabData {
         self.prepare_entity_map_recursive(
             get_prefab_data_fn,
             &mut Vec::new(),
+            &mut Vec::new(),
             &mut 0,
             &mut HashSet::new(),
             &mut entity_map,
@@ -1179,6 +1520,7 @@ impl PrefabData {
     fn prepare_entity_map_recursive(
         &self,
         get_prefab_data_fn: impl Fn(AssetId) -> Option<Arc<PrefabData>> + Copy,
+        prefab_stack: &mut Vec<AssetId>,
         id_path_prefix: &mut Vec<u64>,
         entity_id_index: &mut u64,
         deleted_entities: &mut HashSet<EntityIdWithPath>,
@@ -1204,17 +1546,25 @@ impl PrefabData {
             }
         }
         for (i, &asset_id) in self.nested_prefabs.iter().enumerate() {
+            // stop the recursion if a prefab nests itself
+            if prefab_stack.contains(&asset_id) {
+                log::error!("PrefabData::prepare_entity_map: prefab nests itself: {prefab_stack:?} -> {asset_id:?}");
+                continue;
+            }
             if let Some(prefab_data) = get_prefab_data_fn(asset_id) {
+                prefab_stack.push(asset_id);
                 id_path_prefix.push(i as u64);
                 Self::prepare_entity_map_recursive(
                     &prefab_data,
                     get_prefab_data_fn,
+                    prefab_stack,
                     id_path_prefix,
                     entity_id_index,
                     deleted_entities,
                     entity_map,
                 );
                 id_path_prefix.pop();
+                prefab_stack.pop();
             }
         }
     }
@@ -1411,10 +1761,68 @@ impl PrefabData {
             }
         }
 
+        // make sure that the updated prefab data does not nest itself
+        if let Some(cycle) =
+            new_prefab_data.find_nested_prefab_cycle(Some(self_prefab_asset), get_prefab_data_fn)
+        {
+            return Err(format!(
+                "PrefabData::update: prefab nests itself: {cycle:?}"
+            ));
+        }
+
         // return the updated prefab data
         Ok((new_prefab_data, entity_id_to_prefab_entity_id_with_path))
     }
 
+    /// Find a chain of nested prefabs that nest themselves, for example [A, B, A] means that
+    /// prefab A nests prefab B and prefab B nests prefab A. self_prefab_asset is the asset id of self prefab if known.
+    /// Returns None if there is no such chain.
+    pub fn find_nested_prefab_cycle(
+        &self,
+        self_prefab_asset: Option<AssetId>,
+        get_prefab_data_fn: impl Fn(AssetId) -> Option<Arc<PrefabData>> + Copy,
+    ) -> Option<Vec<AssetId>> {
+        Self::find_nested_prefab_cycle_recursive(
+            &self.nested_prefabs,
+            &mut self_prefab_asset.into_iter().collect(),
+            &mut HashSet::new(),
+            get_prefab_data_fn,
+        )
+    }
+
+    fn find_nested_prefab_cycle_recursive(
+        nested_prefabs: &[AssetId],
+        prefab_stack: &mut Vec<AssetId>,
+        checked_prefabs: &mut HashSet<AssetId>,
+        get_prefab_data_fn: impl Fn(AssetId) -> Option<Arc<PrefabData>> + Copy,
+    ) -> Option<Vec<AssetId>> {
+        for &prefab in nested_prefabs {
+            if let Some(i) = prefab_stack.iter().position(|p| *p == prefab) {
+                let mut cycle = prefab_stack[i..].to_vec();
+                cycle.push(prefab);
+                return Some(cycle);
+            }
+            if checked_prefabs.contains(&prefab) {
+                continue;
+            }
+            if let Some(prefab_data) = get_prefab_data_fn(prefab) {
+                prefab_stack.push(prefab);
+                let cycle = Self::find_nested_prefab_cycle_recursive(
+                    &prefab_data.nested_prefabs,
+                    prefab_stack,
+                    checked_prefabs,
+                    get_prefab_data_fn,
+                );
+                if cycle.is_some() {
+                    return cycle;
+                }
+                prefab_stack.pop();
+                checked_prefabs.insert(prefab);
+            }
+        }
+        None
+    }
+
     /// Get all nested prefab asset ids in this prefab, including nested prefabs of nested prefabs.
     fn all_nested_prefabs(
         &self,
@@ -1,5 +1,6 @@
 use shipyard::Unique;
 use std::{
+    collections::HashSet,
     error::Error,
     path::{Path, PathBuf},
 };
@@ -9,12 +10,17 @@ use std::{
 #[derive(Unique)]
 pub struct Platform {
     project_path: PathBuf,
+    /// The extra asset directories added by [Platform::add_asset_dir].
+    extra_asset_dirs: Vec<PathBuf>,
 }
 
 impl Platform {
     /// Used by steel-editor in desktop build.
     pub fn new_editor(project_path: PathBuf) -> Self {
-        Platform { project_path }
+        Platform {
+            project_path,
+            extra_asset_dirs: Vec::new(),
+        }
     }
 
     /// Used by steel-client. steel-client in desktop build can use relative path
@@ -22,43 +28,83 @@ impl Platform {
     pub fn new_client() -> Self {
         Platform {
             project_path: PathBuf::new(),
+            extra_asset_dirs: Vec::new(),
         }
     }
 
+    /// Add an extra asset directory, which is used by steel-editor to share assets between projects.
+    /// Asset paths in an extra asset directory are relative to the extra asset directory itself,
+    /// and an asset path is searched in the root asset directory first, then extra asset directories
+    /// in the order they are added. Extra asset directories are merged into the root asset directory
+    /// when exporting, so that steel-client does not need this.
+    pub fn add_asset_dir(&mut self, dir: impl Into<PathBuf>) {
+        self.extra_asset_dirs.push(dir.into());
+    }
+
     /// Read an asset file to string, path is relative to the root asset directory.
     pub fn read_asset_to_string(&self, path: impl AsRef<Path>) -> Result<String, Box<dyn Error>> {
-        Ok(std::fs::read_to_string(self.asset_dir().join(path))?)
+        Ok(std::fs::read_to_string(self.asset_file(path))?)
     }
 
     /// Read an asset file to bytes, path is relative to the root asset directory.
     pub fn read_asset(&self, path: impl AsRef<Path>) -> Result<Vec<u8>, Box<dyn Error>> {
-        Ok(std::fs::read(self.asset_dir().join(path))?)
+        Ok(std::fs::read(self.asset_file(path))?)
     }
 
-    /// List all files in asset directory.
+    /// List all files in the root asset directory and extra asset directories.
+    /// If a file path exists in more than one asset directory, only the first one is listed.
     pub fn list_asset_files(&self) -> Result<Vec<PathBuf>, Box<dyn Error>> {
         let mut out_vec = Vec::new();
-        self.list_asset_files_recursive(self.asset_dir(), &mut out_vec)?;
+        let mut listed = HashSet::new();
+        for asset_dir in self.asset_dirs() {
+            let mut files = Vec::new();
+            Self::list_asset_files_recursive(&asset_dir, asset_dir.clone(), &mut files)?;
+            for file in files {
+                if listed.insert(file.clone()) {
+                    out_vec.push(file);
+                } else {
+                    log::warn!(
+                        "Platform::list_asset_files: {} in {} is shadowed by a previous asset directory",
+                        file.display(),
+                        asset_dir.display()
+                    );
+                }
+            }
+        }
         Ok(out_vec)
     }
 
     fn list_asset_files_recursive(
-        &self,
+        asset_dir: &Path,
         dir: PathBuf,
         out_vec: &mut Vec<PathBuf>,
     ) -> Result<(), Box<dyn Error>> {
         for entry in std::fs::read_dir(dir)? {
             let entry = entry?;
             if entry.file_type()?.is_dir() {
-                self.list_asset_files_recursive(entry.path(), out_vec)?;
+                Self::list_asset_files_recursive(asset_dir, entry.path(), out_vec)?;
             } else if entry.file_type()?.is_file() {
-                let relative_path = entry.path().strip_prefix(self.asset_dir())?.to_path_buf();
+                let relative_path = entry.path().strip_prefix(asset_dir)?.to_path_buf();
                 out_vec.push(relative_path);
             }
         }
         Ok(())
     }
 
+    /// Get the absolute path of an asset file in the first asset directory which contains it,
+    /// or in the root asset directory if no asset directory contains it.
+    fn asset_file(&self, path: impl AsRef<Path>) -> PathBuf {
+        self.asset_dirs()
+            .map(|asset_dir| asset_dir.join(&path))
+            .find(|file| file.is_file())
+            .unwrap_or_else(|| self.asset_dir().join(path))
+    }
+
+    /// The root asset directory followed by extra asset directories.
+    fn asset_dirs(&self) -> impl Iterator<Item = PathBuf> + '_ {
+        std::iter::once(self.asset_dir()).chain(self.extra_asset_dirs.iter().cloned())
+    }
+
     fn asset_dir(&self) -> PathBuf {
         self.project_path.join("asset")
     }
@@ -0,0 +1,122 @@
+use notify::{
+    event::{CreateKind, DataChange, ModifyKind, RemoveKind},
+    Event, EventKind,
+};
+use std::{
+    collections::HashMap,
+    ffi::OsStr,
+    fs,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc::Sender,
+        Arc,
+    },
+    thread,
+    time::{Duration, SystemTime},
+};
+
+/// AssetPoller is a fallback of the file watcher for the platforms or file systems where the watcher does not work,
+/// such as network drives and some containers. It rescans the asset directory periodically in a background thread,
+/// compares the modified times of asset files, and sends the same events as the watcher through the channel,
+/// so that they are handled by the same code path. The background thread stops when AssetPoller is dropped.
+pub struct AssetPoller {
+    stop: Arc<AtomicBool>,
+}
+
+impl AssetPoller {
+    /// The time between two scans of the asset directory.
+    const INTERVAL: Duration = Duration::from_secs(1);
+
+    /// Create an AssetPoller which scans asset_dir in a background thread and sends file change events to sender.
+    pub fn new(asset_dir: PathBuf, sender: Sender<Event>) -> Self {
+        let stop = Arc::new(AtomicBool::new(false));
+        let thread_stop = stop.clone();
+        if let Err(e) = thread::Builder::new()
+            .name("asset_poller".into())
+            .spawn(move || Self::run(asset_dir, sender, thread_stop))
+        {
+            log::error!("AssetPoller::new: spawn thread error: {e}");
+        }
+        AssetPoller { stop }
+    }
+
+    /// Rescan the asset directory every AssetPoller::INTERVAL until stop is set or the receiver is dropped,
+    /// and send create, modify, and remove events for the changed asset files.
+    fn run(asset_dir: PathBuf, sender: Sender<Event>, stop: Arc<AtomicBool>) {
+        let mut files = Self::scan(&asset_dir);
+        loop {
+            thread::sleep(Self::INTERVAL);
+            if stop.load(Ordering::Relaxed) {
+                return;
+            }
+            let new_files = Self::scan(&asset_dir);
+            for (path, modified) in &new_files {
+                let kind = match files.get(path) {
+                    None => EventKind::Create(CreateKind::File),
+                    Some(old_modified) if old_modified != modified => {
+                        EventKind::Modify(ModifyKind::Data(DataChange::Any))
+                    }
+                    _ => continue,
+                };
+                if !Self::send(&sender, kind, path) {
+                    return;
+                }
+            }
+            for path in files.keys() {
+                if !new_files.contains_key(path)
+                    && !Self::send(&sender, EventKind::Remove(RemoveKind::File), path)
+                {
+                    return;
+                }
+            }
+            files = new_files;
+        }
+    }
+
+    /// Send an event, returns false if the receiver is dropped.
+    fn send(sender: &Sender<Event>, kind: EventKind, path: &Path) -> bool {
+        sender
+            .send(Event::new(kind).add_path(path.to_path_buf()))
+            .is_ok()
+    }
+
+    /// Collect the modified times of all asset files in dir recursively.
+    fn scan(dir: &Path) -> HashMap<PathBuf, SystemTime> {
+        let mut files = HashMap::new();
+        Self::scan_recursive(dir, &mut files);
+        files
+    }
+
+    fn scan_recursive(dir: &Path, files: &mut HashMap<PathBuf, SystemTime>) {
+        let entries = match fs::read_dir(dir) {
+            Ok(entries) => entries,
+            Err(e) => {
+                log::warn!(
+                    "AssetPoller::scan_recursive: read {} error: {e}",
+                    dir.display()
+                );
+                return;
+            }
+        };
+        for entry in entries.filter_map(|entry| entry.ok()) {
+            let path = entry.path();
+            let Ok(metadata) = entry.metadata() else {
+                continue;
+            };
+            if metadata.is_dir() {
+                Self::scan_recursive(&path, files);
+            } else if metadata.is_file() && path.extension() != Some(OsStr::new("asset")) {
+                if let Ok(modified) = metadata.modified() {
+                    files.insert(path, modified);
+                }
+            }
+        }
+    }
+}
+
+impl Drop for AssetPoller {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+    }
+}
@@ -1,5 +1,6 @@
 //! The editor for the [steel game engine](https://github.com/SSSxCCC/steel).
 
+mod asset_poller;
 mod locale;
 mod project;
 mod ui;
@@ -112,23 +113,11 @@ fn _main(event_loop: EventLoop<()>) {
                     ..Default::default()
                 },
             ));
-            // load fonts to support displaying chinese in egui
-            let mut fonts = egui::FontDefinitions::default();
-            fonts.font_data.insert(
-                "msyh".to_owned(),
-                egui::FontData::from_static(include_bytes!("../fonts/msyh.ttc")),
-            );
-            fonts
-                .families
-                .get_mut(&egui::FontFamily::Proportional)
-                .unwrap()
-                .insert(0, "msyh".to_owned());
-            fonts
-                .families
-                .get_mut(&egui::FontFamily::Monospace)
-                .unwrap()
-                .push("msyh".to_owned());
-            gui_editor.as_ref().unwrap().egui_ctx.set_fonts(fonts);
+            let egui_ctx = &gui_editor.as_ref().unwrap().egui_ctx;
+            egui_ctx.set_fonts(utils::editor_fonts(local_data.font_path.as_deref()));
+            // set zoom factor in options directly so that it takes effect before the first frame
+            egui_ctx.options_mut(|options| options.zoom_factor = local_data.zoom_factor);
+            egui_ctx.set_visuals(local_data.theme.visuals());
         }
         Event::Suspended => {
             log::debug!("Event::Suspended");
@@ -153,15 +142,25 @@ fn _main(event_loop: EventLoop<()>) {
                         renderer.resize()
                     }
                 }
-                WindowEvent::ScaleFactorChanged { .. } => {
-                    log::debug!("WindowEvent::ScaleFactorChanged");
+                WindowEvent::ScaleFactorChanged { scale_factor, .. } => {
+                    log::debug!("WindowEvent::ScaleFactorChanged: {scale_factor}");
                     if let Some(renderer) = windows.get_primary_renderer_mut() {
                         renderer.resize()
                     }
+                    // this event is dropped by event.to_static() below, so we pass it to the game gui here
+                    // even if the game is not running, otherwise the pixels per point of the game gui is outdated,
+                    // which misplaces the game gui and the cursor positions adjusted by adjust_event_for_window
+                    if let Some(gui) = gui.as_mut() {
+                        gui.update(&event);
+                    }
+                }
+                WindowEvent::DroppedFile(ref file) => {
+                    log::debug!("WindowEvent::DroppedFile: {}", file.display());
+                    project.import_dropped_file(file);
                 }
                 _ => (),
             }
-            // Warning: event.to_static() may drop some events, like ScaleFactorChanged
+            // Warning: event.to_static() may drop some events, like ScaleFactorChanged which is handled above
             // TODO: find a way to deliver all events to WinitInputHelper
             if let Some(mut event) = event.to_static() {
                 events.push(event.clone());
@@ -198,6 +197,14 @@ fn _main(event_loop: EventLoop<()>) {
                     e.command(Command::Save(&mut world_data));
                     world_data
                 });
+                // world data edited in editor ui is compared with this to update the dirty flag of current scene,
+                // which is only cloned when there is user input because the world can not be edited without input
+                let world_data_before_ui =
+                    if project.is_running() || !may_edit_world(&events, &input_editor) {
+                        None
+                    } else {
+                        world_data.clone()
+                    };
                 editor.ui(
                     gui_editor,
                     &mut gui,
@@ -210,6 +217,11 @@ fn _main(event_loop: EventLoop<()>) {
                     &input_editor,
                     &mut scene_camera,
                 );
+                project.update_dirty(
+                    world_data_before_ui.as_ref(),
+                    world_data.as_ref(),
+                    &mut window_title,
+                );
 
                 let is_running = project.is_running();
                 if let Some(app) = project.app() {
@@ -255,61 +267,49 @@ fn _main(event_loop: EventLoop<()>) {
                         ctx: &gui.egui_ctx,
                     });
 
+                    // Draw game window and scene window one after another on gpu,
+                    // because they may use the same resources like textures and models.
+                    let mut draw_future = vulkano::sync::now(context.device().clone()).boxed();
+
                     if let Some(image) = editor.game_window().image() {
-                        let draw_future = app.draw(DrawInfo {
-                            before_future: vulkano::sync::now(context.device().clone()).boxed(),
+                        let game_draw_future = app.draw(DrawInfo {
+                            before_future: draw_future,
                             context: &context,
                             renderer: &renderer,
                             image: image.clone(),
                             window_size: editor.game_window().pixel(),
+                            msaa_samples: local_data.msaa_samples,
                             editor_info: None,
                         });
-                        // There is a display abnormal problem, it seems like that this image displayed without finishing drawing.
-                        // We wait for draw future here to avoid this problem.
-                        // TODO: fix this display problem.
-                        draw_future
-                            .then_signal_fence_and_flush()
-                            .unwrap()
-                            .wait(None)
-                            .unwrap();
-                        let gui_future = gui.draw_on_image(
-                            vulkano::sync::now(context.device().clone()).boxed(), // TODO: use draw_future
-                            image.clone(),
-                        );
-                        let copy_future = editor.game_window().copy_image(
+                        let gui_future = gui.draw_on_image(game_draw_future, image.clone());
+                        draw_future = editor.game_window().copy_image(
                             &command_buffer_allocator,
                             context.graphics_queue().clone(),
                             gui_future,
                         );
-                        gpu_future = gpu_future.join(copy_future).boxed();
                     }
 
                     if let Some(image) = editor.scene_window().image() {
-                        let draw_future = app.draw(DrawInfo {
-                            before_future: vulkano::sync::now(context.device().clone()).boxed(),
+                        let scene_draw_future = app.draw(DrawInfo {
+                            before_future: draw_future,
                             context: &context,
                             renderer: &renderer,
                             image: image.clone(),
                             window_size: editor.scene_window().pixel(),
+                            msaa_samples: local_data.msaa_samples,
                             editor_info: Some(EditorInfo {
                                 camera: &scene_camera,
+                                solo_entity: editor.solo_entity(),
                             }),
                         });
-                        // There is a crash problem "access to a resource has been denied".
-                        // We wait for draw future here to avoid this problem.
-                        // TODO: fix this crash problem.
-                        draw_future
-                            .then_signal_fence_and_flush()
-                            .unwrap()
-                            .wait(None)
-                            .unwrap();
-                        let copy_future = editor.scene_window().copy_image(
+                        draw_future = editor.scene_window().copy_image(
                             &command_buffer_allocator,
                             context.graphics_queue().clone(),
-                            vulkano::sync::now(context.device().clone()).boxed(), // TODO: use draw_future
+                            scene_draw_future,
                         );
-                        gpu_future = gpu_future.join(copy_future).boxed();
                     }
+
+                    gpu_future = gpu_future.join(draw_future).boxed();
                 }
 
                 gpu_future = gui_editor.draw_on_image(gpu_future, renderer.swapchain_image_view());
@@ -327,6 +327,22 @@ fn _main(event_loop: EventLoop<()>) {
     });
 }
 
+/// Whether the user input in events may edit the world through editor ui, see [Project::update_dirty].
+fn may_edit_world(events: &[WindowEvent<'static>], input: &WinitInputHelper) -> bool {
+    events.iter().any(|event| match event {
+        WindowEvent::KeyboardInput { .. }
+        | WindowEvent::ReceivedCharacter(_)
+        | WindowEvent::Ime(_)
+        | WindowEvent::MouseInput { .. }
+        | WindowEvent::MouseWheel { .. }
+        | WindowEvent::Touch(_)
+        | WindowEvent::DroppedFile(_) => true,
+        // dragging values or entities
+        WindowEvent::CursorMoved { .. } => (0..3).any(|button| input.mouse_held(button)),
+        _ => false,
+    })
+}
+
 fn adjust_event_for_window(
     event: &mut WindowEvent<'static>,
     window_position: Vec2,
@@ -369,6 +385,7 @@ fn adjust_event_for_window(
         | WindowEvent::AxisMotion { .. }
         | WindowEvent::ThemeChanged(_)
         | WindowEvent::Occluded(_) => (),
+        // scale factor changes are passed to the game gui directly when they happen, see the event loop
         WindowEvent::ScaleFactorChanged { .. } => {
             unreachable!("Static event can't be about scale factor changing")
         }
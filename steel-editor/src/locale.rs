@@ -30,6 +30,13 @@ impl Texts {
         .add("Open Project", "Open Project", "打开项目")
         .add("Browse", "Browse", "浏览")
         .add("Open", "Open", "打开")
+        .add(
+            "The folder is not empty and is not a steel project. Create a steel project in it anyway?",
+            "The folder is not empty and is not a steel project. Create a steel project in it anyway?",
+            "该文件夹不为空且不是steel项目，仍然要在其中创建steel项目吗？",
+        )
+        .add("Create Anyway", "Create Anyway", "仍然创建")
+        .add("The path is not a folder.", "The path is not a folder.", "该路径不是文件夹。")
         .add("Compile error!", "Compile error!", "编译错误！")
         .add(
             "Compile error message",
@@ -44,6 +51,7 @@ impl Texts {
         .add("Open", "Open", "打开")
         .add("Close", "Close", "关闭")
         .add("Compile", "Compile", "编译")
+        .add("Release Build", "Release Build", "发布模式编译")
         .add("Export", "Export", "导出")
         .add("Scene", "Scene", "场景")
         .add("Save", "Save", "保存")
@@ -53,6 +61,17 @@ impl Texts {
         .add("Run", "Run", "运行")
         .add("Start", "Start", "开始")
         .add("Stop", "Stop", "停止")
+        .add(
+            "Spawn Prefab by Clicking",
+            "Spawn Prefab by Clicking",
+            "点击生成预制体",
+        )
+        .add(
+            "Choose a prefab, then click in Scene window to spawn it, spawned entities are discarded when running stops",
+            "Choose a prefab, then click in Scene window to spawn it, spawned entities are discarded when running stops",
+            "选择一个预制体，然后在场景窗口中点击来生成它，生成的实体会在停止运行时被丢弃",
+        )
+        .add("Stop Spawning Prefab", "Stop Spawning Prefab", "停止生成预制体")
         .add(
             "Switch to Game Window on Start",
             "Switch to Game Window on Start",
@@ -76,10 +95,15 @@ impl Texts {
         .add("Delete", "Delete", "删除")
         .add("Asset", "Asset", "资产")
         .add("Introduction", "Introduction", "介绍")
+        .add("Migrate Scenes And Prefabs", "Migrate Scenes And Prefabs", "迁移场景和预制体")
+        .add("Import All Assets", "Import All Assets", "导入所有资产")
+        .add("Import Asset Ids", "Import Asset Ids", "导入资产ID")
+        .add("Export Asset Ids", "Export Asset Ids", "导出资产ID")
+        .add("Rename/Move Asset", "Rename/Move Asset", "重命名/移动资产")
         .add("Asset System Introduction", "Asset System Introduction", "资产系统介绍")
         .add("Asset Introduction",
-            "The Steel game engine has a simple asset system. Scenes, images, prefabs and other files used in the game are stored in the \"asset\" directory of the game project directory. Next to each asset file is an additional file with the same name but with the \".asset\" suffix added, which stores the ID of the asset. Each asset is loaded by its ID, not its file path. Therefore, if you need to move/rename an asset, you need to move/rename its corresponding \".asset\" file as well.",
-            "Steel游戏引擎有一个简单的资产系统，场景、图像、预制件等游戏要用到的文件都保存在游戏项目目录的“asset”目录下。每个资产文件旁边都有一个同名的额外添加了“.asset”后缀的文件，这里面保存了这个资产的ID。每个资产是通过ID加载的，而不是其文件路径。因此如果你需要移动/重命名一个资产，你需要同时也移动/重命名其对应的“.asset”文件。",
+            "The Steel game engine has a simple asset system. Scenes, images, prefabs and other files used in the game are stored in the \"asset\" directory of the game project directory. Next to each asset file is an additional file with the same name but with the \".asset\" suffix added, which stores the ID of the asset. Each asset is loaded by its ID, not its file path. Therefore, if you need to move/rename an asset, you need to move/rename its corresponding \".asset\" file as well, or use \"Asset -> Rename/Move Asset\" menu which does this for you.",
+            "Steel游戏引擎有一个简单的资产系统，场景、图像、预制件等游戏要用到的文件都保存在游戏项目目录的“asset”目录下。每个资产文件旁边都有一个同名的额外添加了“.asset”后缀的文件，这里面保存了这个资产的ID。每个资产是通过ID加载的，而不是其文件路径。因此如果你需要移动/重命名一个资产，你需要同时也移动/重命名其对应的“.asset”文件，或者使用“资产 -> 重命名/移动资产”菜单自动完成这些操作。",
         )
         .add("Select", "Select", "选择")
         .add("Save Prefab", "Save Prefab", "保存预制件")
@@ -91,6 +115,56 @@ impl Texts {
         .add("Settings", "Settings", "设置")
         .add("Reset", "Reset", "重置")
         .add("Scene Camera", "Scene Camera", "场景相机")
+        .add("Frame All", "Frame All", "框选全部")
+        .add("Solo Render", "Solo Render", "单独渲染")
+        .add(
+            "Only draw the selected entity and its descendants",
+            "Only draw the selected entity and its descendants",
+            "只绘制选中的实体及其后代",
+        )
+        .add("Scene Statistics", "Scene Statistics", "场景统计")
+        .add("Colliders", "Colliders", "碰撞体")
+        .add("Draws", "Draws", "绘制")
+        .add("2D Transform Editing", "2D Transform Editing", "2D变换编辑")
+        .add("Lock", "Lock", "锁定")
+        .add("Apply To All Selected", "Apply To All Selected", "应用到所有选中")
+        .add("Mixed", "Mixed", "不一致")
+        .add("Reset to Default", "Reset to Default", "重置为默认值")
+        .add("Anti-aliasing", "Anti-aliasing", "抗锯齿")
+        .add("Off", "Off", "关闭")
+        .add("Theme", "Theme", "主题")
+        .add("Dark", "Dark", "深色")
+        .add("Light", "Light", "浅色")
+        .add("Font", "Font", "字体")
+        .add("Choose Font File", "Choose Font File", "选择字体文件")
+        .add("Default Font", "Default Font", "默认字体")
+        .add("Copy", "Copy", "复制")
+        .add("Key Bindings", "Key Bindings", "快捷键")
+        .add("Start Running", "Start Running", "开始运行")
+        .add("Reset Scene Camera", "Reset Scene Camera", "重置场景相机")
+        .add("Toggle Ray Tracing", "Toggle Ray Tracing", "切换光线追踪")
+        .add("Move Entity Up", "Move Entity Up", "上移实体")
+        .add("Move Entity Down", "Move Entity Down", "下移实体")
+        .add("Press a key...", "Press a key...", "请按下按键...")
+        .add("Restore Defaults", "Restore Defaults", "恢复默认")
+        .add("Confirm", "Confirm", "确认")
+        .add("Don't ask again", "Don't ask again", "不再询问")
+        .add("Ok", "Ok", "确定")
+        .add("Cancel", "Cancel", "取消")
+        .add("Confirm Destructive Actions", "Confirm Destructive Actions", "确认破坏性操作")
+        .add("Delete entity {} and its children?", "Delete entity {} and its children?", "删除实体{}及其子实体？")
+        .add("Close the project? Unsaved changes will be lost.", "Close the project? Unsaved changes will be lost.", "关闭项目？未保存的修改将会丢失。")
+        .add("Create a new scene? Unsaved changes will be lost.", "Create a new scene? Unsaved changes will be lost.", "新建场景？未保存的修改将会丢失。")
+        .add("Load the scene? Unsaved changes will be lost.", "Load the scene? Unsaved changes will be lost.", "加载场景？未保存的修改将会丢失。")
+        .add("Unsaved Changes", "Unsaved Changes", "未保存的修改")
+        .add("Current scene has unsaved changes. Save it?", "Current scene has unsaved changes. Save it?", "当前场景有未保存的修改，是否保存？")
+        .add("Don't Save", "Don't Save", "不保存")
+        .add("None", "None", "无")
+        .add("Clear", "Clear", "清除")
+        .add("Move Up", "Move Up", "上移")
+        .add("Move Down", "Move Down", "下移")
+        .add("Remove", "Remove", "移除")
+        .add("Drag an entity here from Entities panel", "Drag an entity here from Entities panel", "从实体面板拖动一个实体到这里")
     }
 
     fn add(mut self, key: &'static str, eng: &'static str, chs: &'static str) -> Self {
@@ -1,5 +1,9 @@
-use crate::utils::LocalData;
+use crate::{
+    asset_poller::AssetPoller,
+    utils::{LocalData, ProjectPrefs},
+};
 use egui_winit_vulkano::Gui;
+use indexmap::IndexMap;
 use libloading::{Library, Symbol};
 use log::{LevelFilter, Log, SetLoggerError};
 use notify::{
@@ -7,20 +11,23 @@ use notify::{
     Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher,
 };
 use regex::Regex;
+use serde::{Deserialize, Serialize};
 use shipyard::EntityId;
 use std::{
+    collections::BTreeMap,
     error::Error,
+    ffi::OsStr,
     fs,
     path::{Path, PathBuf},
     sync::{
-        mpsc::{Receiver, TryRecvError},
+        mpsc::{Receiver, Sender, TryRecvError},
         Arc,
     },
 };
 use steel_common::{
     app::{App, Command, CommandMut, InitInfo},
     asset::{AssetId, AssetIdType, AssetInfo},
-    data::{PrefabData, SceneData, WorldData},
+    data::{Data, Limit, PrefabData, SceneData, Value, WorldData},
     platform::Platform,
 };
 use vulkano_util::context::VulkanoContext;
@@ -35,10 +42,13 @@ struct ProjectCompiledState {
     scene_data: SceneData,
     running: bool,
 
-    /// Watch for file changes in asset folder.
+    /// Watch for file changes in asset folder, None if the watcher failed to initialize.
+    #[allow(unused)]
+    watcher: Option<RecommendedWatcher>,
+    /// Rescan asset folder periodically in a background thread if the watcher failed to initialize.
     #[allow(unused)]
-    watcher: RecommendedWatcher,
-    /// Receiver for file change events from watcher.
+    poller: Option<AssetPoller>,
+    /// Receiver for file change events from watcher or poller.
     receiver: Receiver<Event>,
     /// Last EventKind::Modify(ModifyKind::Name(RenameMode::From)),
     /// to be used with upcoming EventKind::Modify(ModifyKind::Name(RenameMode::To)).
@@ -48,7 +58,7 @@ struct ProjectCompiledState {
 impl ProjectCompiledState {
     fn save_scene(&self, prefab_data_override: Option<(EntityId, Arc<PrefabData>)>) -> SceneData {
         let mut world_data = WorldData::default();
-        self.app.command(Command::Save(&mut world_data));
+        self.app.command(Command::SaveScene(&mut world_data));
         let get_prefab_data_fn = |prefab_asset: AssetId| {
             let mut prefab_data = None;
             self.app
@@ -67,7 +77,59 @@ impl ProjectCompiledState {
 
 struct ProjectState {
     path: PathBuf,
+    /// The editor preferences of this project.
+    prefs: ProjectPrefs,
     compiled: Option<ProjectCompiledState>,
+    /// True if current scene has unsaved changes.
+    dirty: bool,
+    /// The world structure of last frame, see [Project::update_dirty].
+    last_world_structure: Option<WorldStructure>,
+    /// The dirty flag shown in window title.
+    title_dirty: bool,
+}
+
+/// The entities, their components and their hierarchy in a [WorldData], which is compared between frames
+/// to detect changes made by commands like creating and deleting entities, see [Project::update_dirty].
+#[derive(PartialEq)]
+struct WorldStructure(Vec<EntityStructure>, StructureValues);
+
+/// An entity id with the names of its components and their [StructureValues].
+type EntityStructure = (EntityId, Vec<(String, StructureValues)>);
+
+/// The values of a component or unique which is a part of the world structure, None if it is not.
+type StructureValues = Option<IndexMap<String, Value>>;
+
+impl WorldStructure {
+    /// The components whose values are a part of the world structure.
+    const STRUCTURE_COMPONENTS: [&'static str; 3] = ["Parent", "Children", "Prefab"];
+
+    fn new(world_data: &WorldData) -> Self {
+        let mut entities = world_data
+            .entities
+            .iter()
+            .map(|(eid, entity_data)| {
+                let components = entity_data
+                    .components
+                    .iter()
+                    .map(|(component_name, data)| {
+                        let data = Self::STRUCTURE_COMPONENTS
+                            .contains(&component_name.as_str())
+                            .then(|| data.values.clone());
+                        (component_name.clone(), data)
+                    })
+                    .collect();
+                (*eid, components)
+            })
+            .collect::<Vec<_>>();
+        entities.sort_by_key(|(eid, _)| eid.inner());
+        WorldStructure(
+            entities,
+            world_data
+                .uniques
+                .get("Hierarchy")
+                .map(|data| data.values.clone()),
+        )
+    }
 }
 
 pub struct Project {
@@ -95,6 +157,7 @@ impl Project {
             );
             project.open(
                 local_data.last_open_project_path.clone(),
+                false,
                 local_data,
                 window_title,
                 gui_game,
@@ -111,18 +174,22 @@ impl Project {
             assert!(local_data.open_last_project_on_start);
             let scene_data = compiled.save_scene(None);
             local_data.scene_asset_and_data = Some((compiled.scene, scene_data));
+            local_data.scene_dirty = self.is_dirty();
             local_data.save();
         }
     }
 
+    /// Open the project in path. Project files are created if they do not exist, but if path is a non-empty folder
+    /// which is not a steel project, the project is not opened unless force is true, see [Project::check_folder].
     pub fn open(
         &mut self,
         path: PathBuf,
+        force: bool,
         local_data: &mut LocalData,
         window_title: &mut Option<String>,
         gui_game: &mut Option<Gui>,
     ) {
-        match Self::_open(&path) {
+        match Self::_open(&path, force) {
             Err(error) => {
                 local_data.open_last_project_on_start = false;
                 local_data.save();
@@ -140,18 +207,63 @@ impl Project {
                 }
                 local_data.save();
 
-                *window_title = Some(format!("Steel Editor ({})", path.display()));
+                *window_title = Some(Self::window_title(&path, false));
 
                 *gui_game = None; // destroy Gui struct before release dynlib to fix egui crash problem
                 self.state = Some(ProjectState {
+                    prefs: ProjectPrefs::load(&path),
                     path,
                     compiled: None,
+                    dirty: false,
+                    last_world_structure: None,
+                    title_dirty: false,
                 });
             }
         }
     }
 
-    fn _open(path: &PathBuf) -> Result<(), Box<dyn Error>> {
+    /// Check whether path is a new folder, an existing steel project, or an unrelated folder.
+    pub fn check_folder(path: impl AsRef<Path>) -> ProjectFolder {
+        let path = path.as_ref();
+        if !path.exists() {
+            return ProjectFolder::New;
+        }
+        if !path.is_dir() {
+            return ProjectFolder::NotFolder;
+        }
+        if fs::read_to_string(path.join("Cargo.toml"))
+            .is_ok_and(|cargo_toml| cargo_toml.contains("steel-engine"))
+        {
+            return ProjectFolder::Steel;
+        }
+        if fs::read_dir(path).is_ok_and(|mut entries| entries.next().is_none()) {
+            ProjectFolder::New
+        } else {
+            ProjectFolder::Other
+        }
+    }
+
+    fn _open(path: &PathBuf, force: bool) -> Result<(), Box<dyn Error>> {
+        match Self::check_folder(path) {
+            ProjectFolder::New | ProjectFolder::Steel => (),
+            ProjectFolder::NotFolder => {
+                return Err(
+                    ProjectError::new(format!("{} is not a folder", path.display())).boxed(),
+                )
+            }
+            ProjectFolder::Other if !force => {
+                return Err(ProjectError::new(format!(
+                    "{} is not empty and is not a steel project",
+                    path.display()
+                ))
+                .boxed())
+            }
+            ProjectFolder::Other => log::warn!(
+                "Create steel project in non-empty folder: {}",
+                path.display()
+            ),
+        }
+
         if !path.is_dir() {
             fs::create_dir_all(&path)?;
             log::debug!("Created directory: {}", path.display());
@@ -269,21 +381,29 @@ impl Project {
             } else if let Some(scene_asset_and_data) = local_data.scene_asset_and_data.clone() {
                 load_from_local_data = true;
                 (scene, scene_data) = scene_asset_and_data;
+                state.dirty = local_data.scene_dirty;
                 local_data.save();
             }
+            // entity ids may change after reloading scene data, so we do not compare with the old world structure
+            state.last_world_structure = None;
 
             *gui_game = None; // destroy Gui struct before release dynlib to fix egui crash problem
             state.compiled = None; // prevent steel.dll from being loaded twice at same time
 
-            let lib_path = PathBuf::from("target/debug/steel.dll");
+            let release = local_data.release_build;
+            let profile = if release { "release" } else { "debug" };
+            let lib_path = PathBuf::from(format!("target/{profile}/steel.dll"));
             if lib_path.exists() {
                 fs::remove_file(&lib_path)?;
             }
 
-            Self::_handle_pdb_file(Regex::new(r"^steel\.pdb$").unwrap())?;
-            Self::_handle_pdb_file(Regex::new(r"^steel_proc-.*\.pdb$").unwrap())?;
+            Self::_handle_pdb_file(profile, Regex::new(r"^steel\.pdb$").unwrap())?;
+            Self::_handle_pdb_file(profile, Regex::new(r"^steel_proc-.*\.pdb$").unwrap())?;
 
-            Self::_modify_files_while_compiling(&state.path, None, Self::_build_steel_dynlib)?;
+            let cargo_features = Self::cargo_features(&state.path);
+            Self::_modify_files_while_compiling(&state.path, None, || {
+                Self::_build_steel_dynlib(release, &cargo_features)
+            })?;
 
             let library: Library = unsafe { Library::new(&lib_path)? };
 
@@ -292,10 +412,19 @@ impl Project {
             > = unsafe { library.get(b"setup_logger")? };
             setup_logger_fn(log::logger(), log::max_level())?;
 
+            // re-seed reload-sensitive statics if the global state of the previous load is still alive
+            let on_library_loaded_fn: Symbol<fn()> = unsafe { library.get(b"on_library_loaded")? };
+            on_library_loaded_fn();
+
             let create_app_fn: Symbol<fn() -> Box<dyn App>> = unsafe { library.get(b"create")? };
             let mut app = create_app_fn();
+            let extra_asset_dirs = Self::extra_asset_dirs(&state.path);
+            let mut platform = Platform::new_editor(state.path.clone());
+            for extra_asset_dir in &extra_asset_dirs {
+                platform.add_asset_dir(extra_asset_dir);
+            }
             app.init(InitInfo {
-                platform: Platform::new_editor(state.path.clone()),
+                platform,
                 context,
                 ray_tracing_supported: self.ray_tracing_supported,
                 scene: None,
@@ -307,24 +436,28 @@ impl Project {
 
             // init a watcher to monitor file changes for asset system
             let (sender, receiver) = std::sync::mpsc::channel();
-            let mut watcher = notify::recommended_watcher(move |result| match result {
-                Ok(event) => {
-                    if let Err(e) = sender.send(event) {
-                        log::error!("Send watch event error: {e}");
-                    }
-                }
-                Err(e) => log::error!("Watch error: {e}"),
-            })
-            .unwrap();
             let abs_asset_dir = state.path.join("asset");
-            watcher
-                .watch(&abs_asset_dir, RecursiveMode::Recursive)
-                .unwrap();
+            let (watcher, poller) = match Self::_watch_asset_dir(&abs_asset_dir, sender.clone()) {
+                Ok(watcher) => (Some(watcher), None),
+                Err(e) => {
+                    log::warn!("Project::_watch_asset_dir error: {e}, fall back to polling");
+                    (None, Some(AssetPoller::new(abs_asset_dir.clone(), sender)))
+                }
+            };
 
             // process assets
             if let Err(e) = Self::_scan_asset_dir(abs_asset_dir, &app) {
                 log::warn!("Project::_scan_asset_dir error: {e}");
             }
+            // extra asset directories are not watched, their changes are applied after next compile
+            for extra_asset_dir in extra_asset_dirs {
+                if let Err(e) = Self::_scan_asset_dir(&extra_asset_dir, &app) {
+                    log::warn!(
+                        "Project::_scan_asset_dir {} error: {e}",
+                        extra_asset_dir.display()
+                    );
+                }
+            }
 
             // create ProjectCompiledState
             state.compiled = Some(ProjectCompiledState {
@@ -334,6 +467,7 @@ impl Project {
                 scene_data,
                 running: false,
                 watcher,
+                poller,
                 receiver,
                 last_rename_from_event: None,
             });
@@ -353,8 +487,8 @@ impl Project {
     /// There is a problem with compilation failure due to the pdb file being locked:
     /// https://developercommunity.visualstudio.com/t/pdb-is-locked-even-after-dll-is-unloaded/690640
     /// We avoid this problem by rename it so that compiler can generate a new pdb file.
-    fn _handle_pdb_file(pdb_file_regex: Regex) -> Result<(), Box<dyn Error>> {
-        let pdb_dir = PathBuf::from("target/debug/deps");
+    fn _handle_pdb_file(profile: &str, pdb_file_regex: Regex) -> Result<(), Box<dyn Error>> {
+        let pdb_dir = PathBuf::from(format!("target/{profile}/deps"));
         if !pdb_dir.exists() {
             return Ok(()); // currently no pdb file exists
         }
@@ -400,14 +534,15 @@ impl Project {
         Ok(())
     }
 
-    fn _build_steel_dynlib() -> Result<(), Box<dyn Error>> {
-        log::info!("$ cargo build -p steel-dynlib");
-        std::process::Command::new("cargo")
-            .arg("build")
-            .arg("-p")
-            .arg("steel-dynlib")
-            .spawn()?
-            .wait()?; // TODO: non-blocking wait
+    fn _build_steel_dynlib(release: bool, cargo_features: &[String]) -> Result<(), Box<dyn Error>> {
+        let mut command = std::process::Command::new("cargo");
+        command.arg("build").arg("-p").arg("steel-dynlib");
+        if release {
+            command.arg("--release");
+        }
+        Self::_add_cargo_features(&mut command, cargo_features);
+        log::info!("$ {command:?}");
+        command.spawn()?.wait()?; // TODO: non-blocking wait
         Ok(())
     }
 
@@ -419,7 +554,7 @@ impl Project {
     fn _modify_files_while_compiling(
         project_path: impl AsRef<Path>,
         init_scene: Option<AssetId>,
-        compile_fn: fn() -> Result<(), Box<dyn Error>>,
+        compile_fn: impl FnOnce() -> Result<(), Box<dyn Error>>,
     ) -> Result<(), Box<dyn Error>> {
         let cargo_toml_paths = [
             PathBuf::from("steel-build/steel-client/Cargo.toml"),
@@ -545,6 +680,23 @@ impl Project {
         compile_result
     }
 
+    /// Create a watcher which sends the file change events in asset_dir to sender.
+    fn _watch_asset_dir(
+        asset_dir: &Path,
+        sender: Sender<Event>,
+    ) -> Result<RecommendedWatcher, notify::Error> {
+        let mut watcher = notify::recommended_watcher(move |result| match result {
+            Ok(event) => {
+                if let Err(e) = sender.send(event) {
+                    log::error!("Send watch event error: {e}");
+                }
+            }
+            Err(e) => log::error!("Watch error: {e}"),
+        })?;
+        watcher.watch(asset_dir, RecursiveMode::Recursive)?;
+        Ok(watcher)
+    }
+
     /// Scan asset folder to:
     /// 1. Create ".asset" file for normal asset files.
     /// 2. Delete ".asset" file if its corresponding asset file not exists.
@@ -594,10 +746,17 @@ impl Project {
     /// 1. If an asset file is added/created, add/create asset info file, and insert the asset into AssetManager.
     /// 2. If an asset file is removed/deleted, remove/delete asset info file, and delete the asset in AssetManager.
     /// 3. When a file is moved, two events are generated: remove and create, so 1 and 2 have handled file moving.
-    /// This will create a new asset id for the moved file! Therefore if user want to keep the asset id
-    /// after moving a file, the user must move its corresponding ".asset" file at the same time!
+    ///    This will create a new asset id for the moved file! Therefore if user want to keep the asset id
+    ///    after moving a file, the user must move its corresponding ".asset" file at the same time!
     /// 4. If an asset file is renamed, rename asset info file, and update the asset path in AssetManager.
     /// 5. If an asset file is modified, clear the asset cache in AssetManager.
+    ///
+    /// Or use [Project::rename_asset] to move or rename an asset file, which always keeps the asset id.
+    ///
+    /// The events come from the watcher, or from the poller if the watcher failed to initialize.
+    /// The poller can not detect renames, it reports a renamed file as removed and created, so 4 never happens
+    /// and a renamed asset gets a new id unless its ".asset" file is renamed with it. When the poller is active,
+    /// only [Project::rename_asset] keeps asset ids for renames made through steel-editor.
     pub fn maintain_asset_dir(&mut self) {
         let asset_dir = self.asset_dir();
         if let Some(compiled) = self.compiled_mut() {
@@ -738,6 +897,7 @@ impl Project {
             Self::_export_asset(
                 self.asset_dir()
                     .expect("self.asset_dir() must be some if self.state is some"),
+                Self::extra_asset_dirs(&state.path),
                 state.path.join("build/windows/asset"),
             )?;
 
@@ -745,11 +905,10 @@ impl Project {
             if exe_path.exists() {
                 fs::remove_file(&exe_path)?;
             }
-            Self::_modify_files_while_compiling(
-                &state.path,
-                Some(scene),
-                Self::_build_steel_client_desktop,
-            )?;
+            let cargo_features = Self::cargo_features(&state.path);
+            Self::_modify_files_while_compiling(&state.path, Some(scene), || {
+                Self::_build_steel_client_desktop(&cargo_features)
+            })?;
             if !exe_path.exists() {
                 return Err(
                     ProjectError::new(format!("No output file: {}", exe_path.display())).boxed(),
@@ -767,16 +926,17 @@ impl Project {
         }
     }
 
-    fn _build_steel_client_desktop() -> Result<(), Box<dyn Error>> {
-        log::info!("$ cargo build -p steel-client -F desktop");
-        std::process::Command::new("cargo")
+    fn _build_steel_client_desktop(cargo_features: &[String]) -> Result<(), Box<dyn Error>> {
+        let mut command = std::process::Command::new("cargo");
+        command
             .arg("build")
             .arg("-p")
             .arg("steel-client")
             .arg("-F")
-            .arg("desktop")
-            .spawn()?
-            .wait()?; // TODO: non-blocking wait
+            .arg("desktop");
+        Self::_add_cargo_features(&mut command, cargo_features);
+        log::info!("$ {command:?}");
+        command.spawn()?.wait()?; // TODO: non-blocking wait
         Ok(())
     }
 
@@ -801,6 +961,7 @@ impl Project {
             Self::_export_asset(
                 self.asset_dir()
                     .expect("self.asset_dir() must be some if self.state is some"),
+                Self::extra_asset_dirs(&state.path),
                 PathBuf::from("steel-build/android-project/app/src/main/assets"),
             )?;
 
@@ -814,11 +975,10 @@ impl Project {
             if so_path.exists() {
                 fs::remove_file(&so_path)?;
             }
-            Self::_modify_files_while_compiling(
-                &state.path,
-                Some(scene),
-                Self::_build_steel_client_android,
-            )?;
+            let cargo_features = Self::cargo_features(&state.path);
+            Self::_modify_files_while_compiling(&state.path, Some(scene), || {
+                Self::_build_steel_client_android(&cargo_features)
+            })?;
             if !so_path.exists() {
                 return Err(
                     ProjectError::new(format!("No output file: {}", so_path.display())).boxed(),
@@ -867,9 +1027,9 @@ impl Project {
         }
     }
 
-    fn _build_steel_client_android() -> Result<(), Box<dyn Error>> {
-        log::info!("$ cargo ndk -t arm64-v8a -o steel-build/android-project/app/src/main/jniLibs/ build -p steel-client");
-        std::process::Command::new("cargo")
+    fn _build_steel_client_android(cargo_features: &[String]) -> Result<(), Box<dyn Error>> {
+        let mut command = std::process::Command::new("cargo");
+        command
             .arg("ndk")
             .arg("-t")
             .arg("arm64-v8a")
@@ -877,17 +1037,34 @@ impl Project {
             .arg("steel-build/android-project/app/src/main/jniLibs/")
             .arg("build")
             .arg("-p")
-            .arg("steel-client")
-            .spawn()?
-            .wait()?; // TODO: non-blocking wait
+            .arg("steel-client");
+        Self::_add_cargo_features(&mut command, cargo_features);
+        log::info!("$ {command:?}");
+        command.spawn()?.wait()?; // TODO: non-blocking wait
         Ok(())
     }
 
-    fn _export_asset(src: PathBuf, dst: PathBuf) -> std::io::Result<()> {
+    /// Enable cargo_features of steel-project crate for a cargo build command.
+    fn _add_cargo_features(command: &mut std::process::Command, cargo_features: &[String]) {
+        if !cargo_features.is_empty() {
+            let features = cargo_features
+                .iter()
+                .map(|feature| format!("steel-project/{feature}"))
+                .collect::<Vec<_>>()
+                .join(",");
+            command.arg("-F").arg(features);
+        }
+    }
+
+    /// Copy asset directory and extra asset directories into dst, files in previous directories are not overwritten.
+    fn _export_asset(src: PathBuf, extra_srcs: Vec<PathBuf>, dst: PathBuf) -> std::io::Result<()> {
         if dst.is_dir() {
             fs::remove_dir_all(&dst)?;
         }
-        Self::_copy_dir_all(src, dst)?;
+        Self::_copy_dir_all(src, &dst)?;
+        for extra_src in extra_srcs {
+            Self::_copy_dir_all(extra_src, &dst)?;
+        }
         Ok(())
     }
 
@@ -900,6 +1077,14 @@ impl Project {
                 Self::_copy_dir_all(entry.path(), dst.as_ref().join(entry.file_name()))?;
             } else {
                 let dst = dst.as_ref().join(entry.file_name());
+                if dst.exists() {
+                    log::warn!(
+                        "Skip exporting {} because {} already exists",
+                        entry.path().display(),
+                        dst.display()
+                    );
+                    continue;
+                }
                 fs::copy(entry.path(), &dst)?;
                 log::debug!("Exported: {}", dst.display());
             }
@@ -907,6 +1092,64 @@ impl Project {
         Ok(())
     }
 
+    /// Read the cargo features of the project at project_path from [CARGO_FEATURES_FILE], which is a json array
+    /// of feature names of the project crate. These features are enabled when the editor compiles or exports the project.
+    fn cargo_features(project_path: &Path) -> Vec<String> {
+        let file = project_path.join(CARGO_FEATURES_FILE);
+        if !file.exists() {
+            return Vec::new();
+        }
+        let features = fs::read_to_string(&file)
+            .map_err(|e| e.to_string())
+            .and_then(|s| serde_json::from_str::<Vec<String>>(&s).map_err(|e| e.to_string()));
+        match features {
+            Ok(features) => features,
+            Err(e) => {
+                log::error!(
+                    "Project::cargo_features: failed to read {}, error: {e}",
+                    file.display()
+                );
+                Vec::new()
+            }
+        }
+    }
+
+    /// Read the extra asset directories of the project at project_path from [ASSET_DIRS_FILE], which is
+    /// a json array of directory paths, relative paths are relative to project_path. Assets in extra asset
+    /// directories can be shared between projects, see [Platform::add_asset_dir].
+    fn extra_asset_dirs(project_path: &Path) -> Vec<PathBuf> {
+        let file = project_path.join(ASSET_DIRS_FILE);
+        if !file.exists() {
+            return Vec::new();
+        }
+        let dirs = fs::read_to_string(&file)
+            .map_err(|e| e.to_string())
+            .and_then(|s| serde_json::from_str::<Vec<PathBuf>>(&s).map_err(|e| e.to_string()));
+        match dirs {
+            Ok(dirs) => dirs
+                .into_iter()
+                .map(|dir| project_path.join(dir))
+                .filter(|dir| {
+                    let is_dir = dir.is_dir();
+                    if !is_dir {
+                        log::warn!(
+                            "Project::extra_asset_dirs: {} is not a directory",
+                            dir.display()
+                        );
+                    }
+                    is_dir
+                })
+                .collect(),
+            Err(e) => {
+                log::error!(
+                    "Project::extra_asset_dirs: failed to read {}, error: {e}",
+                    file.display()
+                );
+                Vec::new()
+            }
+        }
+    }
+
     pub fn set_running(&mut self, running: bool) {
         if let Some(compiled) = self.compiled_mut() {
             compiled.running = running;
@@ -917,6 +1160,99 @@ impl Project {
         self.compiled_ref().is_some_and(|compiled| compiled.running)
     }
 
+    /// Returns true if current scene has unsaved changes.
+    pub fn is_dirty(&self) -> bool {
+        self.state.as_ref().is_some_and(|state| state.dirty)
+    }
+
+    /// Mark current scene as saved or newly loaded.
+    fn clear_dirty(&mut self) {
+        if let Some(state) = self.state.as_mut() {
+            state.dirty = false;
+            state.last_world_structure = None;
+        }
+    }
+
+    /// Update the dirty flag of current scene, which is called every frame after editor ui.
+    /// Values edited in editor ui are detected by comparing world_data_before_ui and world_data,
+    /// and changes made by commands are detected by comparing the world structure with the last compared frame.
+    /// world_data_before_ui is None if the game is running, or if there is no user input in this frame,
+    /// in which case nothing is compared because the world is not edited.
+    /// Read-only values are not compared because they are usually changed by the game itself.
+    /// The window title is updated with a "*" suffix when the dirty flag changes.
+    pub fn update_dirty(
+        &mut self,
+        world_data_before_ui: Option<&WorldData>,
+        world_data: Option<&WorldData>,
+        window_title: &mut Option<String>,
+    ) {
+        let running = self.is_running();
+        let Some(state) = self.state.as_mut() else {
+            return;
+        };
+        if state.title_dirty != state.dirty {
+            state.title_dirty = state.dirty;
+            *window_title = Some(Self::window_title(&state.path, state.dirty));
+        }
+        let (Some(world_data), false) = (world_data, running) else {
+            // world changes while running are discarded after stopping
+            state.last_world_structure = None;
+            return;
+        };
+        let Some(world_data_before_ui) = world_data_before_ui else {
+            return;
+        };
+        let mut dirty = state.dirty;
+        let world_structure = WorldStructure::new(world_data_before_ui);
+        if state
+            .last_world_structure
+            .as_ref()
+            .is_some_and(|last_world_structure| *last_world_structure != world_structure)
+        {
+            dirty = true;
+        }
+        state.last_world_structure = Some(world_structure);
+        if !dirty {
+            let data_changed = |before: &Data, after: &Data| {
+                after.values.iter().any(|(name, value)| {
+                    !matches!(after.limits.get(name), Some(Limit::ReadOnly))
+                        && before.values.get(name) != Some(value)
+                })
+            };
+            dirty = world_data.entities.iter().any(|(eid, entity_data)| {
+                world_data_before_ui
+                    .entities
+                    .get(eid)
+                    .is_some_and(|entity_data_before_ui| {
+                        entity_data.components.iter().any(|(component_name, data)| {
+                            entity_data_before_ui
+                                .components
+                                .get(component_name)
+                                .is_some_and(|before| data_changed(before, data))
+                        })
+                    })
+            }) || world_data.uniques.iter().any(|(unique_name, data)| {
+                world_data_before_ui
+                    .uniques
+                    .get(unique_name)
+                    .is_some_and(|before| data_changed(before, data))
+            });
+        }
+        if dirty && !state.dirty {
+            log::debug!("Project::update_dirty: current scene has unsaved changes");
+            state.dirty = true;
+        }
+    }
+
+    /// The editor window title of the project in path, with a "*" suffix if current scene has unsaved changes.
+    fn window_title(path: &Path, dirty: bool) -> String {
+        format!(
+            "Steel Editor ({}){}",
+            path.display(),
+            if dirty { " *" } else { "" }
+        )
+    }
+
     pub fn save_to_memory(&mut self, prefab_data_override: Option<(EntityId, Arc<PrefabData>)>) {
         if let Some(compiled) = self.compiled_mut() {
             compiled.scene_data = compiled.save_scene(prefab_data_override);
@@ -939,6 +1275,34 @@ impl Project {
         self.state.as_ref().map(|state| &state.path)
     }
 
+    /// Get the editor preferences of the opened project, or None if no project is opened.
+    pub fn prefs(&self) -> Option<&ProjectPrefs> {
+        self.state.as_ref().map(|state| &state.prefs)
+    }
+
+    /// Get the mutable editor preferences of the opened project, call [Project::save_prefs] after changing them.
+    pub fn prefs_mut(&mut self) -> Option<&mut ProjectPrefs> {
+        self.state.as_mut().map(|state| &mut state.prefs)
+    }
+
+    /// Save the editor preferences of the opened project into the project directory.
+    pub fn save_prefs(&self) {
+        if let Some(state) = &self.state {
+            state.prefs.save(&state.path);
+        }
+    }
+
+    /// Return the path of the thumbnail of a scene, which is under the "thumbnail" directory of
+    /// the opened project with the same relative path as the scene in asset directory, or None if no project is opened.
+    /// Thumbnails are not put in asset directory so that they are not registered as assets.
+    pub fn scene_thumbnail_path(&self, scene: impl AsRef<Path>) -> Option<PathBuf> {
+        self.project_dir().map(|path| {
+            let mut thumbnail = path.join("thumbnail").join(scene).into_os_string();
+            thumbnail.push(".png");
+            PathBuf::from(thumbnail)
+        })
+    }
+
     /// Return the asset dir under the opened project directory, or None if no project is opened
     pub fn asset_dir(&self) -> Option<PathBuf> {
         self.project_dir().map(|path| path.join("asset"))
@@ -995,6 +1359,134 @@ impl Project {
             compiled.app.command_mut(CommandMut::ClearEntity);
             compiled.app.command_mut(CommandMut::SetCurrentScene(None));
             compiled.scene = None;
+            self.clear_dirty();
+        }
+    }
+
+    /// Copy a file dropped onto the editor window into the asset directory, so that it will be registered as an asset by
+    /// [Project::maintain_asset_dir]. A number is appended to the file name if a file with the same name already exists.
+    pub fn import_dropped_file(&self, file: impl AsRef<Path>) {
+        let file = file.as_ref();
+        let Some(asset_dir) = self.compiled_ref().and(self.asset_dir()) else {
+            log::warn!(
+                "Project::import_dropped_file: no compiled project to import {}",
+                file.display()
+            );
+            return;
+        };
+        if !file.is_file() {
+            log::warn!(
+                "Project::import_dropped_file: only files can be imported: {}",
+                file.display()
+            );
+            return;
+        }
+        if file.starts_with(&asset_dir) {
+            log::info!(
+                "Project::import_dropped_file: {} is already in asset directory",
+                file.display()
+            );
+            return;
+        }
+        let (Some(stem), extension) = (file.file_stem(), file.extension()) else {
+            return;
+        };
+        let mut target = asset_dir.join(file.file_name().unwrap());
+        let mut i = 1;
+        while target.exists() {
+            let mut file_name = stem.to_os_string();
+            file_name.push(format!(" ({i})"));
+            if let Some(extension) = extension {
+                file_name.push(".");
+                file_name.push(extension);
+            }
+            target = asset_dir.join(file_name);
+            i += 1;
+        }
+        match fs::copy(file, &target) {
+            Ok(_) => log::info!(
+                "Project::import_dropped_file: {} is copied to {}",
+                file.display(),
+                target.display()
+            ),
+            Err(e) => log::error!(
+                "Project::import_dropped_file: failed to copy {} to {}, error: {e}",
+                file.display(),
+                target.display()
+            ),
+        }
+    }
+
+    /// Load every scene and prefab file in asset directory and rewrite it in the current format if it is changed.
+    /// Changed and failed files are reported in log. It is safe to run this repeatedly.
+    pub fn migrate_assets(&self) {
+        log::info!("Project::migrate_assets start");
+        if let Some(asset_dir) = self.asset_dir() {
+            let (mut changed, mut unchanged, mut failed) = (0, 0, 0);
+            if let Err(e) = Self::_migrate_assets_recursive(
+                &asset_dir,
+                &mut changed,
+                &mut unchanged,
+                &mut failed,
+            ) {
+                log::error!("Project::migrate_assets error: {e}");
+            }
+            log::info!("Project::migrate_assets end: changed={changed}, unchanged={unchanged}, failed={failed}");
+        }
+    }
+
+    fn _migrate_assets_recursive(
+        dir: impl AsRef<Path>,
+        changed: &mut usize,
+        unchanged: &mut usize,
+        failed: &mut usize,
+    ) -> Result<(), Box<dyn Error>> {
+        for entry in fs::read_dir(dir)? {
+            let entry = entry?;
+            let path = entry.path();
+            if entry.file_type()?.is_dir() {
+                Self::_migrate_assets_recursive(path, changed, unchanged, failed)?;
+            } else if entry.file_type()?.is_file() {
+                let result = match path.extension().and_then(|extension| extension.to_str()) {
+                    Some("scene") => Self::_migrate_asset(&path, SceneData::cut),
+                    Some("prefab") => Self::_migrate_asset(&path, PrefabData::cut),
+                    _ => continue,
+                };
+                match result {
+                    Ok(true) => {
+                        log::info!("Project::migrate_assets: migrated {}", path.display());
+                        *changed += 1;
+                    }
+                    Ok(false) => *unchanged += 1,
+                    Err(e) => {
+                        log::error!(
+                            "Project::migrate_assets: failed to migrate {}, error: {e}",
+                            path.display()
+                        );
+                        *failed += 1;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Rewrite the file in path in the current format of T. The cut function of T is applied like saving T normally,
+    /// so that the stable order and the erased generations are the same as a normally saved file.
+    /// Returns true if the file is changed.
+    fn _migrate_asset<T: Serialize + for<'de> Deserialize<'de>>(
+        path: &Path,
+        cut: fn(&mut T),
+    ) -> Result<bool, Box<dyn Error>> {
+        let bytes = fs::read(path)?;
+        let mut object = serde_json::from_slice::<T>(&bytes)?;
+        cut(&mut object);
+        let new_bytes = serde_json::to_vec_pretty(&object)?;
+        if new_bytes == bytes {
+            Ok(false)
+        } else {
+            fs::write(path, new_bytes)?;
+            Ok(true)
         }
     }
 
@@ -1002,6 +1494,7 @@ impl Project {
     /// save file path, which is relative to the asset directory of opened project.
     pub fn save_scene(&mut self, scene: impl Into<PathBuf>) {
         let asset_dir = self.asset_dir();
+        let mut saved = false;
         if let Some(compiled) = self.compiled_mut() {
             compiled.scene_data = compiled.save_scene(None);
             let asset_dir =
@@ -1010,6 +1503,7 @@ impl Project {
             let scene_abs = asset_dir.join(&scene);
             match crate::utils::save_to_file(&compiled.scene_data, &scene_abs) {
                 Ok(_) => {
+                    saved = true;
                     match Self::get_asset_info_and_insert(asset_dir, scene, &compiled.app, false) {
                         Ok(asset_info) => {
                             compiled
@@ -1031,12 +1525,16 @@ impl Project {
                 ),
             }
         }
+        if saved {
+            self.clear_dirty();
+        }
     }
 
     /// Load [SceneData] from file and convert to [WorldData], scene is the load file path,
     /// which is relative to the asset directory of opened project.
     pub fn load_scene(&mut self, scene: impl Into<PathBuf>) {
         let asset_dir = self.asset_dir();
+        let mut loaded = false;
         if let Some(compiled) = self.compiled_mut() {
             let asset_dir =
                 asset_dir.expect("self.asset_dir() must be some if self.compiled_mut() is some");
@@ -1044,6 +1542,7 @@ impl Project {
             let scene_abs = asset_dir.join(&scene);
             match crate::utils::load_from_file::<SceneData>(&scene_abs) {
                 Ok(scene_data) => {
+                    loaded = true;
                     compiled.scene_data = scene_data;
                     compiled
                         .app
@@ -1069,6 +1568,9 @@ impl Project {
                 ),
             }
         }
+        if loaded {
+            self.clear_dirty();
+        }
     }
 
     /// Get the asset info of file in asset_path.
@@ -1096,7 +1598,7 @@ impl Project {
             }
             Ok(asset_info)
         } else {
-            let asset_info = Self::_create_asset_info(app);
+            let asset_info = Self::_create_asset_info(app.as_ref());
             fs::write(
                 abs_asset_info_path,
                 serde_json::to_string_pretty(&asset_info)?,
@@ -1123,7 +1625,7 @@ impl Project {
     }
 
     /// Create a new AssetInfo with a new random id.
-    fn _create_asset_info(app: &Box<dyn App>) -> AssetInfo {
+    fn _create_asset_info(app: &dyn App) -> AssetInfo {
         loop {
             let asset_id = AssetId::new(rand::random::<AssetIdType>());
             if asset_id == AssetId::INVALID {
@@ -1136,6 +1638,376 @@ impl Project {
             }
         }
     }
+
+    /// Create a new AssetInfo with an id derived from asset_path, which is used by [Project::import_assets] so that
+    /// the same asset files imported at once get the same ids in different clones of a project. If the id is already
+    /// used, a new id is derived with a different seed, so ids are only the same if the clones have the same assets.
+    fn _derive_asset_info(app: &dyn App, asset_path: &Path) -> AssetInfo {
+        let key = Self::_asset_manifest_key(asset_path);
+        for seed in 0.. {
+            let asset_id = AssetId::new(Self::_hash_asset_path(&key, seed));
+            if asset_id == AssetId::INVALID {
+                continue;
+            }
+            let mut exists = false;
+            app.command(Command::AssetIdExists(asset_id, &mut exists));
+            if !exists {
+                return AssetInfo::new(asset_id);
+            }
+        }
+        unreachable!("Project::_derive_asset_info: all asset ids are used")
+    }
+
+    /// FNV-1a hash of key and seed, which is stable across platforms and rust versions.
+    fn _hash_asset_path(key: &str, seed: u32) -> AssetIdType {
+        let mut hash: u32 = 0x811c9dc5;
+        for byte in key.bytes().chain(seed.to_le_bytes()) {
+            hash ^= byte as u32;
+            hash = hash.wrapping_mul(0x01000193);
+        }
+        hash
+    }
+
+    /// The key of an asset in asset id manifest, which is the asset path relative to asset directory with "/" separators.
+    fn _asset_manifest_key(asset_path: &Path) -> String {
+        asset_path
+            .components()
+            .map(|component| component.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/")
+    }
+
+    /// Create ".asset" files for all asset files in asset directory at once, which is useful after importing a large
+    /// asset pack. If manifest is some, it is a json file exported by [Project::export_asset_ids] from another clone
+    /// of the project, the ids of the listed asset files are set to the ids in the manifest, even if they already have
+    /// ".asset" files. Other asset files keep their ids, or get ids derived from their paths, see [Project::_derive_asset_info].
+    /// The numbers of created, changed, and failed ".asset" files are reported in log.
+    pub fn import_assets(&self, manifest: Option<&Path>) {
+        log::info!("Project::import_assets start, manifest={manifest:?}");
+        let (Some(compiled), Some(asset_dir)) = (self.compiled_ref(), self.asset_dir()) else {
+            return;
+        };
+        let manifest = match manifest.map(fs::read_to_string) {
+            Some(Ok(content)) => {
+                match serde_json::from_str::<BTreeMap<String, AssetId>>(&content) {
+                    Ok(manifest) => manifest,
+                    Err(e) => {
+                        log::error!("Project::import_assets: failed to parse manifest: {e}");
+                        return;
+                    }
+                }
+            }
+            Some(Err(e)) => {
+                log::error!("Project::import_assets: failed to read manifest: {e}");
+                return;
+            }
+            None => BTreeMap::new(),
+        };
+        // collect all id changes first and apply them later, so that ids can be swapped between assets
+        let (mut reassignments, mut failed) = (Vec::new(), 0);
+        if let Err(e) = Self::_import_assets_recursive(
+            &asset_dir,
+            &asset_dir,
+            &manifest,
+            &mut reassignments,
+            &mut failed,
+        ) {
+            log::error!("Project::import_assets error: {e}");
+        }
+        let (created, changed) = Self::_apply_asset_ids(
+            &asset_dir,
+            compiled.app.as_ref(),
+            reassignments,
+            &mut failed,
+        );
+        log::info!(
+            "Project::import_assets end: created={created}, changed={changed}, failed={failed}"
+        );
+    }
+
+    /// Collect (asset_path, old_id, new_id) of the asset files in dir whose ids need to be set. The new_id is None
+    /// if the id should be derived from asset_path, see [Project::_apply_asset_ids].
+    fn _import_assets_recursive(
+        asset_dir: &Path,
+        dir: &Path,
+        manifest: &BTreeMap<String, AssetId>,
+        reassignments: &mut Vec<(PathBuf, Option<AssetId>, Option<AssetId>)>,
+        failed: &mut usize,
+    ) -> Result<(), Box<dyn Error>> {
+        // sort entries so that derived ids do not depend on the order of files in file system
+        let mut entries = fs::read_dir(dir)?.collect::<Result<Vec<_>, _>>()?;
+        entries.sort_by_key(|entry| entry.file_name());
+        for entry in entries {
+            let path = entry.path();
+            let file_type = entry.file_type()?;
+            if file_type.is_dir() {
+                if let Err(e) = Self::_import_assets_recursive(
+                    asset_dir,
+                    &path,
+                    manifest,
+                    reassignments,
+                    failed,
+                ) {
+                    log::error!(
+                        "Project::import_assets: failed to import {}, error: {e}",
+                        path.display()
+                    );
+                    *failed += 1;
+                }
+            } else if file_type.is_file() && path.extension() != Some(OsStr::new("asset")) {
+                let result =
+                    path.strip_prefix(asset_dir)
+                        .map_err(|e| e.into())
+                        .and_then(|relative_path| {
+                            let asset_info_path = AssetInfo::asset_path_to_asset_info_path(&path);
+                            let asset_info = Self::_read_asset_info(&asset_info_path)?;
+                            let manifest_id = manifest
+                                .get(&Self::_asset_manifest_key(relative_path))
+                                .copied();
+                            match (asset_info, manifest_id) {
+                                (Some(asset_info), Some(id)) if asset_info.id != id => {
+                                    reassignments.push((
+                                        relative_path.to_path_buf(),
+                                        Some(asset_info.id),
+                                        Some(id),
+                                    ))
+                                }
+                                (Some(_), _) => (),
+                                (None, id) => {
+                                    reassignments.push((relative_path.to_path_buf(), None, id))
+                                }
+                            }
+                            Ok::<_, Box<dyn Error>>(())
+                        });
+                if let Err(e) = result {
+                    log::error!(
+                        "Project::import_assets: failed to import {}, error: {e}",
+                        path.display()
+                    );
+                    *failed += 1;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Apply the reassignments collected by [Project::_import_assets_recursive] in two phases: all old ids are
+    /// deleted first, then the new ids are inserted, so that swapping or rotating ids between assets works.
+    /// Assets with ids in manifest are inserted before assets with derived ids, so that derived ids never take
+    /// the ids in manifest. An asset keeps its old id if its new id is still used by another asset.
+    /// Returns the numbers of created and changed ".asset" files.
+    fn _apply_asset_ids(
+        asset_dir: &Path,
+        app: &dyn App,
+        mut reassignments: Vec<(PathBuf, Option<AssetId>, Option<AssetId>)>,
+        failed: &mut usize,
+    ) -> (usize, usize) {
+        for (_, old_id, _) in &reassignments {
+            if let Some(old_id) = old_id {
+                app.command(Command::DeleteAsset(*old_id));
+            }
+        }
+        reassignments.sort_by_key(|(_, _, id)| id.is_none());
+        let (mut created, mut changed) = (0, 0);
+        for (asset_path, old_id, id) in reassignments {
+            let id = id.unwrap_or_else(|| Self::_derive_asset_info(app, &asset_path).id);
+            match Self::_set_asset_id(asset_dir, &asset_path, id, app) {
+                Ok(_) if old_id.is_some() => changed += 1,
+                Ok(_) => created += 1,
+                Err(e) => {
+                    log::error!(
+                        "Project::import_assets: failed to import {}, error: {e}",
+                        asset_path.display()
+                    );
+                    *failed += 1;
+                    if let Some(old_id) = old_id {
+                        let mut exists = false;
+                        app.command(Command::AssetIdExists(old_id, &mut exists));
+                        if exists {
+                            log::error!(
+                                "Project::import_assets: old id {old_id:?} of {} is taken by another asset",
+                                asset_path.display()
+                            );
+                        } else {
+                            app.command(Command::InsertAsset(old_id, asset_path));
+                        }
+                    }
+                }
+            }
+        }
+        (created, changed)
+    }
+
+    /// Set the id of the asset in asset_path to id, and write it into the asset info file.
+    /// Fails if id is already used by another asset.
+    fn _set_asset_id(
+        asset_dir: &Path,
+        asset_path: &Path,
+        id: AssetId,
+        app: &dyn App,
+    ) -> Result<(), Box<dyn Error>> {
+        let mut exists = false;
+        app.command(Command::AssetIdExists(id, &mut exists));
+        if exists {
+            return crate::utils::err(format!("asset id {id:?} is already used"));
+        }
+        let abs_asset_info_path =
+            AssetInfo::asset_path_to_asset_info_path(asset_dir.join(asset_path));
+        fs::write(
+            abs_asset_info_path,
+            serde_json::to_string_pretty(&AssetInfo::new(id))?,
+        )?;
+        app.command(Command::InsertAsset(id, asset_path.to_path_buf()));
+        Ok(())
+    }
+
+    /// Write the ids of all asset files in asset directory into a json manifest file, which maps asset paths
+    /// to asset ids. The manifest can be imported by [Project::import_assets] in another clone of the project.
+    pub fn export_asset_ids(&self, manifest: &Path) {
+        let Some(asset_dir) = self.asset_dir() else {
+            return;
+        };
+        let mut asset_ids = BTreeMap::new();
+        let result = Self::_collect_asset_ids_recursive(&asset_dir, &asset_dir, &mut asset_ids)
+            .and_then(|_| {
+                Ok(fs::write(
+                    manifest,
+                    serde_json::to_string_pretty(&asset_ids)?,
+                )?)
+            });
+        match result {
+            Ok(_) => log::info!(
+                "Project::export_asset_ids: {} asset ids are exported to {}",
+                asset_ids.len(),
+                manifest.display()
+            ),
+            Err(e) => log::error!("Project::export_asset_ids error: {e}"),
+        }
+    }
+
+    /// Rename or move an asset file from one absolute path to another in asset directory. Its ".asset" file
+    /// is moved together and the asset path in AssetManager is updated, so that the asset keeps its id and
+    /// references to it are not broken. The scene thumbnail is moved too if the asset is a scene.
+    pub fn rename_asset(&mut self, from: &Path, to: &Path) {
+        log::info!(
+            "Project::rename_asset start, from={}, to={}",
+            from.display(),
+            to.display()
+        );
+        match self._rename_asset(from, to) {
+            Ok(_) => log::info!("Project::rename_asset end"),
+            Err(e) => log::error!("Project::rename_asset error: {e}"),
+        }
+    }
+
+    fn _rename_asset(&mut self, from: &Path, to: &Path) -> Result<(), Box<dyn Error>> {
+        let asset_dir = self
+            .asset_dir()
+            .ok_or(ProjectError::new("No project is opened"))?;
+        let from_relative = from.strip_prefix(&asset_dir).map_err(|_| {
+            ProjectError::new(format!("{} is not in asset directory", from.display()))
+        })?;
+        let to_relative = to.strip_prefix(&asset_dir).map_err(|_| {
+            ProjectError::new(format!("{} is not in asset directory", to.display()))
+        })?;
+        if !from.is_file() || from.extension().is_some_and(|ext| ext == "asset") {
+            return Err(
+                ProjectError::new(format!("{} is not an asset file", from.display())).boxed(),
+            );
+        }
+        if to.extension().is_some_and(|ext| ext == "asset") {
+            return Err(ProjectError::new(format!(
+                "{} must not have the \".asset\" extension",
+                to.display()
+            ))
+            .boxed());
+        }
+        let from_asset_info = AssetInfo::asset_path_to_asset_info_path(from);
+        let to_asset_info = AssetInfo::asset_path_to_asset_info_path(to);
+        if to.exists() || to_asset_info.exists() {
+            return Err(ProjectError::new(format!("{} already exists", to.display())).boxed());
+        }
+        let asset_info = Self::_read_asset_info(&from_asset_info)?.ok_or(ProjectError::new(
+            format!("{} does not exist", from_asset_info.display()),
+        ))?;
+
+        if let Some(parent) = to.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        // move the ".asset" file first, so that the watcher does not create a new id for the moved asset file
+        fs::rename(&from_asset_info, &to_asset_info)?;
+        if let Err(e) = fs::rename(from, to) {
+            if let Err(e) = fs::rename(&to_asset_info, &from_asset_info) {
+                log::error!(
+                    "Project::rename_asset: failed to move {} back, error: {e}",
+                    to_asset_info.display()
+                );
+            }
+            return Err(e.into());
+        }
+        if let Some(compiled) = self.compiled_mut() {
+            compiled.app.command(Command::UpdateAssetPath(
+                asset_info.id,
+                to_relative.to_path_buf(),
+            ));
+        }
+
+        if let (Some(from_thumbnail), Some(to_thumbnail)) = (
+            self.scene_thumbnail_path(from_relative),
+            self.scene_thumbnail_path(to_relative),
+        ) {
+            if from_thumbnail.exists() {
+                let result = to_thumbnail
+                    .parent()
+                    .map_or(Ok(()), fs::create_dir_all)
+                    .and_then(|_| fs::rename(&from_thumbnail, &to_thumbnail));
+                if let Err(e) = result {
+                    log::warn!(
+                        "Project::rename_asset: failed to move thumbnail {}, error: {e}",
+                        from_thumbnail.display()
+                    );
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn _collect_asset_ids_recursive(
+        asset_dir: &Path,
+        dir: &Path,
+        asset_ids: &mut BTreeMap<String, AssetId>,
+    ) -> Result<(), Box<dyn Error>> {
+        for entry in fs::read_dir(dir)? {
+            let entry = entry?;
+            let path = entry.path();
+            if entry.file_type()?.is_dir() {
+                Self::_collect_asset_ids_recursive(asset_dir, &path, asset_ids)?;
+            } else if path
+                .extension()
+                .is_some_and(|extension| extension == "asset")
+            {
+                let asset_path = AssetInfo::asset_info_path_to_asset_path(&path);
+                if let Some(asset_info) = Self::_read_asset_info(&path)? {
+                    let relative_path = asset_path.strip_prefix(asset_dir)?;
+                    asset_ids.insert(Self::_asset_manifest_key(relative_path), asset_info.id);
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The kind of a folder to open as project, see [Project::check_folder].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ProjectFolder {
+    /// The folder does not exist or is empty, a new steel project will be created in it.
+    New,
+    /// The folder is an existing steel project, whose Cargo.toml depends on steel-engine.
+    Steel,
+    /// The folder is not empty and is not a steel project, creating project files in it may mess it up.
+    Other,
+    /// The path exists but is not a folder.
+    NotFolder,
 }
 
 /// The error happened in project management.
@@ -1164,8 +2036,15 @@ impl std::fmt::Display for ProjectError {
 
 impl Error for ProjectError {}
 
+/// The file in project directory which contains extra asset directories, see [Project::extra_asset_dirs].
+const ASSET_DIRS_FILE: &str = "asset-dirs.json";
+
+/// The file in project directory which contains cargo features of the project crate, see [Project::cargo_features].
+const CARGO_FEATURES_FILE: &str = "cargo-features.json";
+
 const GITIGNORE: &'static str = "/target
 /build
+/editor-prefs.json
 ";
 
 const CARGO_TOML: &'static str = r#"[package]
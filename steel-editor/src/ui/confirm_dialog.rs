@@ -0,0 +1,69 @@
+use crate::{locale::Texts, utils::LocalData};
+
+/// ConfirmDialog asks user to confirm a destructive action before doing it, which can be suppressed
+/// by checking "Don't ask again" that is stored in [LocalData::skip_confirm_dialog].
+pub struct ConfirmDialog<A> {
+    /// The action waiting for confirmation, the dialog is shown if this is some.
+    action: Option<A>,
+    dont_ask_again: bool,
+}
+
+impl<A> ConfirmDialog<A> {
+    pub fn new() -> Self {
+        ConfirmDialog {
+            action: None,
+            dont_ask_again: false,
+        }
+    }
+
+    /// Request to do action. Returns the action if confirmation is suppressed so that it can be done immediately,
+    /// otherwise opens the dialog and returns None, the action will be returned by [ConfirmDialog::show] after confirmation.
+    pub fn request(&mut self, action: A, local_data: &LocalData) -> Option<A> {
+        if local_data.skip_confirm_dialog {
+            Some(action)
+        } else {
+            self.action = Some(action);
+            self.dont_ask_again = false;
+            None
+        }
+    }
+
+    /// Show the dialog if there is an action waiting for confirmation. Returns the action if it is confirmed.
+    pub fn show(
+        &mut self,
+        ctx: &egui::Context,
+        message: impl FnOnce(&A) -> String,
+        local_data: &mut LocalData,
+        texts: &Texts,
+    ) -> Option<A> {
+        let Some(action) = &self.action else {
+            return None;
+        };
+        let (mut confirmed, mut canceled) = (false, false);
+        egui::Window::new(texts.get("Confirm"))
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+            .show(ctx, |ui| {
+                ui.label(message(action));
+                ui.checkbox(&mut self.dont_ask_again, texts.get("Don't ask again"));
+                ui.horizontal(|ui| {
+                    confirmed = ui.button(texts.get("Ok")).clicked();
+                    canceled = ui.button(texts.get("Cancel")).clicked();
+                });
+            });
+        if confirmed {
+            if self.dont_ask_again {
+                log::info!("ConfirmDialog: don't ask again");
+                local_data.skip_confirm_dialog = true;
+                local_data.save();
+            }
+            self.action.take()
+        } else {
+            if canceled {
+                self.action = None;
+            }
+            None
+        }
+    }
+}
@@ -1,13 +1,14 @@
+use super::confirm_dialog::ConfirmDialog;
 use crate::{
     locale::Texts,
     project::Project,
-    utils::{err, EditorError},
+    utils::{err, EditorError, LocalData},
 };
 use glam::{Vec3, Vec4};
 use regex::Regex;
 use shipyard::EntityId;
 use std::{
-    collections::HashMap,
+    collections::{HashMap, HashSet},
     error::Error,
     ops::RangeInclusive,
     path::{Path, PathBuf},
@@ -16,21 +17,42 @@ use std::{
 use steel_common::{
     app::{App, Command, CommandMut},
     asset::{AssetId, AssetInfo},
-    data::{Data, EntitiesData, EntityData, Limit, PrefabData, Value, WorldData},
+    camera::SceneCamera,
+    data::{Data, EntitiesData, EntityData, EntityIdWithPath, Limit, PrefabData, Value, WorldData},
 };
 
 pub struct DataWindow {
     selected_entity: EntityId,
+    /// All selected entities including selected_entity. More entities can be selected by ctrl-clicking in Entities panel.
+    selected_entities: HashSet<EntityId>,
+    /// If true, editing a value in Entity panel also writes it to other selected entities which have the same component.
+    apply_to_all_selected: bool,
     selected_unique: String,
+    /// If some, the Entity panel is locked to inspect this entity regardless of selected entity.
+    locked_entity: Option<EntityId>,
+    /// If some, the Unique panel is locked to inspect this unique regardless of selected unique.
+    locked_unique: Option<String>,
     unnamed_regex: Regex,
+    delete_entity_dialog: ConfirmDialog<EntityId>,
+    /// The entity which is being dragged in Entities panel, which can be dropped onto entity values.
+    dragged_entity: EntityId,
+    /// The ids and names of named entities, which are listed in the dropdown of entity values.
+    entity_names: Vec<(EntityId, String)>,
 }
 
 impl DataWindow {
     pub fn new() -> Self {
         DataWindow {
             selected_entity: EntityId::dead(),
+            selected_entities: HashSet::new(),
+            apply_to_all_selected: true,
             selected_unique: String::new(),
+            locked_entity: None,
+            locked_unique: None,
             unnamed_regex: Regex::new(r"^unnamed-(\d+)$").unwrap(),
+            delete_entity_dialog: ConfirmDialog::new(),
+            dragged_entity: EntityId::dead(),
+            entity_names: Vec::new(),
         }
     }
 
@@ -41,6 +63,8 @@ impl DataWindow {
         project: &mut Project,
         asset_dir: impl AsRef<Path>,
         texts: &Texts,
+        scene_camera: &SceneCamera,
+        local_data: &LocalData,
     ) {
         let hierarchy = world_data
             .uniques
@@ -51,6 +75,23 @@ impl DataWindow {
             _ => panic!("Hierarchy does not have roots!"),
         };
 
+        if self.selected_entity != EntityId::dead() {
+            if local_data.key_bindings.move_entity_up.pressed(ui.ctx()) {
+                log::info!("entities_view->Move Entity Up");
+                project
+                    .app()
+                    .unwrap()
+                    .command_mut(CommandMut::MoveEntityUp(self.selected_entity));
+            }
+            if local_data.key_bindings.move_entity_down.pressed(ui.ctx()) {
+                log::info!("entities_view->Move Entity Down");
+                project
+                    .app()
+                    .unwrap()
+                    .command_mut(CommandMut::MoveEntityDown(self.selected_entity));
+            }
+        }
+
         if !root_entities.is_empty() {
             let (mut drag_entity, mut drop_parent, mut drop_before) =
                 (EntityId::dead(), None, EntityId::dead());
@@ -65,7 +106,9 @@ impl DataWindow {
                 &mut drop_parent,
                 &mut drop_before,
                 texts,
+                local_data,
             );
+            self.dragged_entity = drag_entity;
             if let Some(drop_parent) = drop_parent {
                 if drag_entity != EntityId::dead() && ui.input(|input| input.pointer.any_released())
                 {
@@ -81,7 +124,7 @@ impl DataWindow {
         ui.menu_button("+", |ui| {
             if ui.button(texts.get("New Entity")).clicked() {
                 log::info!("entities_view_create_menu->New Entity");
-                Self::create_new_entity(project.app().unwrap());
+                Self::create_new_entity(project.app().unwrap(), scene_camera);
                 ui.close_menu();
             }
             if ui.button(texts.get("From Prefab")).clicked() {
@@ -104,6 +147,7 @@ impl DataWindow {
         drop_parent: &mut Option<EntityId>,
         drop_before: &mut EntityId,
         texts: &Texts,
+        local_data: &LocalData,
     ) {
         for (i, &entity) in es.iter().enumerate() {
             let entity_data = entities
@@ -128,25 +172,64 @@ impl DataWindow {
                     |ui| {
                         Self::drag_source(ui, drag_id, |ui| {
                             let r = ui.selectable_label(
-                                self.selected_entity == entity,
+                                self.selected_entities.contains(&entity),
                                 Self::entity_label(&entity, entity_data),
                             );
                             if r.clicked() {
-                                self.selected_entity = entity;
+                                if ui.input(|input| input.modifiers.command) {
+                                    self.toggle_selected_entity(entity);
+                                } else {
+                                    self.set_selected_entity(entity);
+                                }
                             }
                             r.context_menu(|ui| {
                                 if ui.button(texts.get("Duplicate")).clicked() {
                                     log::info!("entity_context_menu->Duplicate");
-                                    Self::duplicate_entity(
-                                        entity,
-                                        entities,
-                                        project.app().unwrap(),
-                                    );
+                                    Self::duplicate_entity(entity, project.app().unwrap());
+                                    ui.close_menu();
+                                }
+                                if ui
+                                    .add_enabled(
+                                        i > 0,
+                                        egui::Button::new(texts.get("Move Up")).shortcut_text(
+                                            local_data.key_bindings.move_entity_up.format(ui.ctx()),
+                                        ),
+                                    )
+                                    .clicked()
+                                {
+                                    log::info!("entity_context_menu->Move Up");
+                                    project
+                                        .app()
+                                        .unwrap()
+                                        .command_mut(CommandMut::MoveEntityUp(entity));
+                                    ui.close_menu();
+                                }
+                                if ui
+                                    .add_enabled(
+                                        i + 1 < es.len(),
+                                        egui::Button::new(texts.get("Move Down")).shortcut_text(
+                                            local_data
+                                                .key_bindings
+                                                .move_entity_down
+                                                .format(ui.ctx()),
+                                        ),
+                                    )
+                                    .clicked()
+                                {
+                                    log::info!("entity_context_menu->Move Down");
+                                    project
+                                        .app()
+                                        .unwrap()
+                                        .command_mut(CommandMut::MoveEntityDown(entity));
                                     ui.close_menu();
                                 }
                                 if ui.button(texts.get("Delete")).clicked() {
                                     log::info!("entity_context_menu->Delete");
-                                    self.delete_entity(entity, project.app().unwrap());
+                                    self.request_delete_entity(
+                                        entity,
+                                        project.app().unwrap(),
+                                        local_data,
+                                    );
                                     ui.close_menu();
                                 }
                                 if !project.is_running() {
@@ -167,7 +250,6 @@ impl DataWindow {
                                         log::info!("entity_context_menu->Save As Prefab");
                                         Self::save_as_prefab(
                                             entity,
-                                            entities,
                                             project.app().unwrap(),
                                             &asset_dir,
                                         );
@@ -217,6 +299,7 @@ impl DataWindow {
                         drop_parent,
                         drop_before,
                         texts,
+                        local_data,
                     )
                 });
             } else {
@@ -228,71 +311,82 @@ impl DataWindow {
         }
     }
 
-    pub fn duplicate_entity(entity: EntityId, entities: &EntitiesData, app: &mut Box<dyn App>) {
-        let entities_data = Self::get_entities_data_of_entity(entity, entities);
-        let mut old_id_to_new_id = HashMap::new();
-        app.command_mut(CommandMut::AddEntities(
-            &entities_data,
-            &mut old_id_to_new_id,
-        ));
-        let new_id = *old_id_to_new_id.get(&entity).unwrap();
-
-        // attach duplicated entity next to the original entity
-        let entity_data = entities
-            .get(&entity)
-            .expect(format!("duplicate_entity: non-existent entity: {entity:?}").as_str());
-        app.command_mut(CommandMut::AttachAfter(
-            new_id,
-            entity_data.parent(),
-            entity,
-        ));
+    pub fn duplicate_entity(entity: EntityId, app: &mut Box<dyn App>) {
+        let mut new_id = EntityId::dead();
+        app.command_mut(CommandMut::DuplicateEntity(entity, &mut new_id));
     }
 
-    /// Get EntitiesData of an entity with its ancestors. This function will keep input entity as the first
+    /// Get EntitiesData of an entity with its descendants. The input entity is kept as the first
     /// entity in returned EntitiesData so that it will be the first entity in the PrefabData created later.
-    fn get_entities_data_of_entity(entity: EntityId, entities: &EntitiesData) -> EntitiesData {
+    fn get_entities_data_of_entity(entity: EntityId, app: &dyn App) -> EntitiesData {
         let mut entities_data = EntitiesData::default();
-        let mut entities_to_add = vec![entity];
-        while !entities_to_add.is_empty() {
-            let mut new_entities_to_add = Vec::new();
-            for entity in &entities_to_add {
-                let entity_data = entities.get(entity).expect(
-                    format!("entity_level::duplicate: non-existent entity: {entity:?}").as_str(),
-                );
-                entities_data.insert(*entity, entity_data.clone()); // TODO: avoid clone here
-                for e in entity_data.children().into_iter().flatten() {
-                    new_entities_to_add.push(*e);
-                }
-            }
-            entities_to_add = new_entities_to_add;
-        }
+        app.command(Command::GetEntitiesDataOfEntity(entity, &mut entities_data));
         entities_data
     }
 
-    pub fn delete_entity(&mut self, entity: EntityId, app: &mut Box<dyn App>) {
-        app.command_mut(CommandMut::DestroyEntity(entity));
-        self.selected_entity = EntityId::dead();
+    /// Delete entity after confirmation, see [ConfirmDialog].
+    pub fn request_delete_entity(
+        &mut self,
+        entity: EntityId,
+        app: &mut Box<dyn App>,
+        local_data: &LocalData,
+    ) {
+        if let Some(entity) = self.delete_entity_dialog.request(entity, local_data) {
+            self.delete_entity(entity, app);
+        }
     }
 
-    pub fn save_as_prefab(
-        entity: EntityId,
-        entities: &EntitiesData,
+    /// Show the confirm dialog of deleting entity requested by [DataWindow::request_delete_entity].
+    pub fn delete_entity_dialog(
+        &mut self,
+        ctx: &egui::Context,
         app: &mut Box<dyn App>,
-        asset_dir: impl AsRef<Path>,
+        local_data: &mut LocalData,
+        texts: &Texts,
     ) {
-        if let Err(e) = Self::save_as_prefab_inner(entity, entities, app, asset_dir) {
+        if let Some(entity) = self.delete_entity_dialog.show(
+            ctx,
+            |entity| {
+                texts
+                    .get("Delete entity {} and its children?")
+                    .replace("{}", &format!("{entity:?}"))
+            },
+            local_data,
+            texts,
+        ) {
+            self.delete_entity(entity, app);
+        }
+    }
+
+    pub fn delete_entity(&mut self, entity: EntityId, app: &mut Box<dyn App>) {
+        // warn about references that will become dangling after deletion
+        let mut references = Vec::new();
+        app.command(Command::FindReferencesTo(entity, &mut references));
+        for (e, component_name, value_name) in references {
+            log::warn!(
+                "DataWindow::delete_entity: {entity:?} is still referenced by {e:?}.{component_name}.{value_name}"
+            );
+        }
+        app.command_mut(CommandMut::DestroyEntity(entity));
+        self.set_selected_entity(EntityId::dead());
+        if self.locked_entity == Some(entity) {
+            self.locked_entity = None;
+        }
+    }
+
+    pub fn save_as_prefab(entity: EntityId, app: &mut Box<dyn App>, asset_dir: impl AsRef<Path>) {
+        if let Err(e) = Self::save_as_prefab_inner(entity, app, asset_dir) {
             log::error!("DataWindow::save_as_prefab error: {e:?}");
         }
     }
 
     fn save_as_prefab_inner(
         entity: EntityId,
-        entities: &EntitiesData,
         app: &mut Box<dyn App>,
         asset_dir: impl AsRef<Path>,
     ) -> Result<(), Box<dyn Error>> {
         // get all entities that we will save as prefab
-        let entities = Self::get_entities_data_of_entity(entity, entities);
+        let entities = Self::get_entities_data_of_entity(entity, app.as_ref());
 
         // convert entities to prefab data
         let get_prefab_data_fn = |prefab_asset: AssetId| {
@@ -358,10 +452,10 @@ impl DataWindow {
         let entity = prefab_root_entity;
 
         // get all entities in the prefab that we will save
-        let entities = Self::get_entities_data_of_entity(entity, entities);
+        let app = project.app().unwrap();
+        let entities = Self::get_entities_data_of_entity(entity, app.as_ref());
 
         // create updated prefab data
-        let app = project.app().unwrap();
         let get_prefab_data_fn = |prefab_asset: AssetId| {
             let mut prefab_data = None;
             app.command(Command::GetPrefabData(prefab_asset, &mut prefab_data));
@@ -402,8 +496,9 @@ impl DataWindow {
         Ok(())
     }
 
-    pub fn create_new_entity(app: &mut Box<dyn App>) {
-        app.command_mut(CommandMut::CreateEntity);
+    /// Create a new entity at the focus point of scene camera, so that it is immediately visible in scene window.
+    pub fn create_new_entity(app: &mut Box<dyn App>, scene_camera: &SceneCamera) {
+        app.command_mut(CommandMut::CreateEntity(scene_camera.focus_point()));
     }
 
     pub fn create_entities_from_prefab(app: &mut Box<dyn App>, asset_dir: impl AsRef<Path>) {
@@ -416,22 +511,13 @@ impl DataWindow {
         app: &mut Box<dyn App>,
         asset_dir: impl AsRef<Path>,
     ) -> Result<(), Box<dyn Error>> {
-        let file = rfd::FileDialog::new().set_directory(&asset_dir).pick_file();
-        if let Some(file) = file {
-            if !file.starts_with(&asset_dir) {
-                return err("you must load from file in asset directory!");
-            }
-            let asset_path = file
-                .strip_prefix(&asset_dir)
-                .expect("Already checked file.starts_with(&asset_dir) is true!");
-            let asset_info =
-                Project::get_asset_info_and_insert(&asset_dir, asset_path, app, false)?;
+        if let Some(prefab_asset) = Self::pick_prefab_inner(app, asset_dir)? {
             let get_prefab_data_fn = |prefab_asset: AssetId| {
                 let mut prefab_data = None;
                 app.command(Command::GetPrefabData(prefab_asset, &mut prefab_data));
                 prefab_data
             };
-            let prefab_data = get_prefab_data_fn(asset_info.id)
+            let prefab_data = get_prefab_data_fn(prefab_asset)
                 .ok_or(EditorError::new("failed to get prefab data!"))?;
             let (entities_data, entity_map) = prefab_data.to_entities_data(get_prefab_data_fn);
             let mut old_id_to_new_id = HashMap::new();
@@ -454,13 +540,50 @@ impl DataWindow {
                 .ok_or(EditorError::new("there should be a root entity in prefab!"))?;
             app.command(Command::LoadPrefab(
                 root_entity,
-                asset_info.id,
+                prefab_asset,
                 entity_id_to_prefab_entity_id_with_path,
             ));
         }
         Ok(())
     }
 
+    /// Pick a prefab file in asset directory by a file dialog. Returns the asset id of the prefab,
+    /// or None if no file is picked or the picked file is not a prefab.
+    pub fn pick_prefab(app: &mut Box<dyn App>, asset_dir: impl AsRef<Path>) -> Option<AssetId> {
+        match Self::pick_prefab_inner(app, asset_dir) {
+            Ok(Some(prefab_asset)) => {
+                let mut prefab_data = None;
+                app.command(Command::GetPrefabData(prefab_asset, &mut prefab_data));
+                if prefab_data.is_some() {
+                    return Some(prefab_asset);
+                }
+                log::error!("DataWindow::pick_prefab error: failed to get prefab data!");
+            }
+            Ok(None) => (),
+            Err(e) => log::error!("DataWindow::pick_prefab error: {e:?}"),
+        }
+        None
+    }
+
+    fn pick_prefab_inner(
+        app: &mut Box<dyn App>,
+        asset_dir: impl AsRef<Path>,
+    ) -> Result<Option<AssetId>, Box<dyn Error>> {
+        let file = rfd::FileDialog::new().set_directory(&asset_dir).pick_file();
+        if let Some(file) = file {
+            if !file.starts_with(&asset_dir) {
+                return err("you must load from file in asset directory!");
+            }
+            let asset_path = file
+                .strip_prefix(&asset_dir)
+                .expect("Already checked file.starts_with(&asset_dir) is true!");
+            let asset_info =
+                Project::get_asset_info_and_insert(&asset_dir, asset_path, app, false)?;
+            return Ok(Some(asset_info.id));
+        }
+        Ok(None)
+    }
+
     fn drag_source<R>(ui: &mut egui::Ui, id: egui::Id, body: impl FnOnce(&mut egui::Ui) -> R) {
         let is_being_dragged = ui.memory(|mem| mem.is_being_dragged(id));
 
@@ -562,18 +685,52 @@ impl DataWindow {
     pub fn entity_view(
         &mut self,
         ui: &mut egui::Ui,
-        entity_data: &mut EntityData,
+        entities: &mut EntitiesData,
         app: &mut Box<dyn App>,
         asset_dir: impl AsRef<Path>,
         texts: &Texts,
+        transform_2d: bool,
     ) {
-        Self::color_label(
-            ui,
-            egui::Color32::BLACK,
-            format!("{:?}", self.selected_entity),
-        );
+        self.update_entity_names(entities);
+        let entity = self.inspected_entity();
+        let others = self
+            .selected_entities
+            .iter()
+            .copied()
+            .filter(|e| *e != entity && entities.contains_key(e))
+            .collect::<Vec<_>>();
+        ui.horizontal(|ui| {
+            Self::color_label(ui, egui::Color32::BLACK, format!("{:?}", entity));
+            let mut locked = self.locked_entity.is_some();
+            if ui.toggle_value(&mut locked, texts.get("Lock")).changed() {
+                self.locked_entity = locked.then_some(entity);
+            }
+            if !others.is_empty() {
+                ui.toggle_value(
+                    &mut self.apply_to_all_selected,
+                    format!(
+                        "{} ({})",
+                        texts.get("Apply To All Selected"),
+                        others.len() + 1
+                    ),
+                );
+            }
+        });
         ui.separator();
+
+        let mixed = Self::mixed_values(entity, &others, entities);
+        let Some(entity_data) = entities.get_mut(&entity) else {
+            return;
+        };
+        let old_entity_data =
+            (self.apply_to_all_selected && !others.is_empty()).then(|| entity_data.clone());
+        let prefab_entity_data = Self::prefab_entity_data(entity_data, app.as_ref());
         for (component_name, component_data) in &mut entity_data.components {
+            let default_data = Self::default_component_data(
+                component_name,
+                prefab_entity_data.as_ref(),
+                app.as_ref(),
+            );
             ui.horizontal(|ui| {
                 ui.label(component_name);
                 if component_name != "Children"
@@ -582,16 +739,45 @@ impl DataWindow {
                 {
                     // TODO: use a more generic way to prevent some components from being destroyed by user
                     if ui.button("-").clicked() {
-                        app.command_mut(CommandMut::DestroyComponent(
-                            self.selected_entity,
-                            component_name,
-                        ));
+                        app.command_mut(CommandMut::DestroyComponent(entity, component_name));
+                    }
+                    if let Some(default_data) = &default_data {
+                        if ui
+                            .button("⟲")
+                            .on_hover_text(texts.get("Reset to Default"))
+                            .clicked()
+                        {
+                            log::info!("Reset component {component_name} of {entity:?}");
+                            Self::reset_data(component_data, default_data, None);
+                        }
                     }
                 }
             });
-            self.data_view(ui, component_name, component_data, app, &asset_dir, texts);
+            if transform_2d && component_name == "Transform" {
+                Self::transform_2d_view(
+                    ui,
+                    component_data,
+                    mixed.get(component_name),
+                    default_data.as_ref(),
+                    texts,
+                );
+            } else {
+                self.data_view(
+                    ui,
+                    component_name,
+                    component_data,
+                    mixed.get(component_name),
+                    default_data.as_ref(),
+                    app,
+                    &asset_dir,
+                    texts,
+                );
+            }
             ui.separator();
         }
+        if let Some(old_entity_data) = old_entity_data {
+            Self::apply_to_entities(entity, &old_entity_data, &others, entities);
+        }
 
         let mut components = Vec::new();
         app.command(Command::GetComponents(&mut components));
@@ -602,18 +788,146 @@ impl DataWindow {
             {
                 // TODO: use a more generic way to prevent some components from being created by user
                 if ui.button(component).clicked() {
-                    app.command_mut(CommandMut::CreateComponent(self.selected_entity, component));
+                    app.command_mut(CommandMut::CreateComponent(entity, component));
                     ui.close_menu();
                 }
             }
         });
     }
 
+    /// Get the entity data in prefab of a prefab instance, returns None if entity is not a prefab instance.
+    /// Values which contain entity ids are removed because entity ids in prefab are different from the ones in scene.
+    fn prefab_entity_data(entity_data: &EntityData, app: &dyn App) -> Option<EntityData> {
+        let (prefab_asset, prefab_entity_index, prefab_entity_path, _) =
+            entity_data.prefab_info()?;
+        let mut prefab_data = None;
+        app.command(Command::GetPrefabData(prefab_asset, &mut prefab_data));
+        let mut prefab_entity_data = prefab_data?
+            .entities
+            .get(&EntityIdWithPath(
+                EntityId::new_from_index_and_gen(prefab_entity_index, 0),
+                prefab_entity_path.clone(),
+            ))?
+            .0
+            .clone();
+        for component_data in prefab_entity_data.components.values_mut() {
+            component_data
+                .values
+                .retain(|_, value| !matches!(value, Value::Entity(_) | Value::VecEntity(_)));
+        }
+        Some(prefab_entity_data)
+    }
+
+    /// Get the data which a component is reset to, which is the component data in prefab for prefab instances,
+    /// or the data of the default value of component.
+    fn default_component_data(
+        component_name: &str,
+        prefab_entity_data: Option<&EntityData>,
+        app: &dyn App,
+    ) -> Option<Data> {
+        if let Some(component_data) =
+            prefab_entity_data.and_then(|entity_data| entity_data.components.get(component_name))
+        {
+            return Some(component_data.clone());
+        }
+        let mut default_data = None;
+        app.command(Command::GetDefaultComponentData(
+            component_name,
+            &mut default_data,
+        ));
+        default_data
+    }
+
+    /// Set values in data to the values in default_data, only the value with name is set if name is some.
+    /// Read-only values are not changed.
+    fn reset_data(data: &mut Data, default_data: &Data, name: Option<&str>) {
+        for (value_name, value) in data.values.iter_mut() {
+            if name.is_some_and(|name| name != value_name)
+                || matches!(data.limits.get(value_name), Some(Limit::ReadOnly))
+            {
+                continue;
+            }
+            if let Some(default_value) = default_data.values.get(value_name) {
+                *value = default_value.clone();
+            }
+        }
+    }
+
+    /// Find values of entity whose matching values in other entities are different.
+    /// Returns a map from component name to the names of mixed values.
+    fn mixed_values(
+        entity: EntityId,
+        others: &[EntityId],
+        entities: &EntitiesData,
+    ) -> HashMap<String, HashSet<String>> {
+        let mut mixed = HashMap::<String, HashSet<String>>::new();
+        let Some(entity_data) = entities.get(&entity) else {
+            return mixed;
+        };
+        for other in others {
+            let other_data = &entities[other];
+            for (component_name, component_data) in &entity_data.components {
+                let Some(other_component_data) = other_data.components.get(component_name) else {
+                    continue;
+                };
+                for (name, value) in &component_data.values {
+                    if other_component_data
+                        .values
+                        .get(name)
+                        .is_some_and(|other_value| other_value != value)
+                    {
+                        mixed
+                            .entry(component_name.clone())
+                            .or_default()
+                            .insert(name.clone());
+                    }
+                }
+            }
+        }
+        mixed
+    }
+
+    /// Write the values of entity which are changed from old_entity_data to other entities
+    /// which have the same component.
+    fn apply_to_entities(
+        entity: EntityId,
+        old_entity_data: &EntityData,
+        others: &[EntityId],
+        entities: &mut EntitiesData,
+    ) {
+        let mut changes = Vec::new();
+        for (component_name, component_data) in &entities[&entity].components {
+            let Some(old_component_data) = old_entity_data.components.get(component_name) else {
+                continue;
+            };
+            for (name, value) in &component_data.values {
+                if old_component_data.values.get(name) != Some(value) {
+                    changes.push((component_name.clone(), name.clone(), value.clone()));
+                }
+            }
+        }
+        for (component_name, name, value) in changes {
+            for other in others {
+                if let Some(other_value) = entities
+                    .get_mut(other)
+                    .and_then(|entity_data| entity_data.components.get_mut(&component_name))
+                    .and_then(|component_data| component_data.values.get_mut(&name))
+                {
+                    *other_value = value.clone();
+                }
+            }
+        }
+    }
+
+    /// Display data. Values whose names are in mixed are marked to show that they differ across selected entities.
+    /// If default_data is some, a reset button is displayed after each value which is different from default_data.
     pub fn data_view(
         &self,
         ui: &mut egui::Ui,
         data_name: &str,
         data: &mut Data,
+        mixed: Option<&HashSet<String>>,
+        default_data: Option<&Data>,
         app: &Box<dyn App>,
         asset_dir: impl AsRef<Path>,
         texts: &Texts,
@@ -624,11 +938,14 @@ impl DataWindow {
                 if !self.unnamed_regex.is_match(&name) {
                     ui.label(name);
                 }
+                if mixed.is_some_and(|mixed| mixed.contains(name)) {
+                    Self::color_label(ui, egui::Color32::DARK_RED, texts.get("Mixed"));
+                }
                 let limit = data.limits.get(name);
                 if let Some(Limit::ReadOnly) = limit {
-                    Self::immutable_value_view(ui, value, color, app);
+                    Self::immutable_value_view(ui, value, color, app, texts);
                 } else {
-                    Self::mutable_value_view(
+                    self.mutable_value_view(
                         ui,
                         value,
                         limit,
@@ -639,6 +956,59 @@ impl DataWindow {
                         asset_dir.as_ref(),
                         texts,
                     );
+                    if let Some(default_value) = default_data
+                        .and_then(|default_data| default_data.values.get(name))
+                        .filter(|default_value| *default_value != value)
+                    {
+                        if ui
+                            .small_button("⟲")
+                            .on_hover_text(texts.get("Reset to Default"))
+                            .clicked()
+                        {
+                            *value = default_value.clone();
+                        }
+                    }
+                }
+            });
+        }
+    }
+
+    /// Display Transform component data in 2D mode: position and scale as xy,
+    /// rotation as a single angle around z axis. The underlying 3D values are kept unchanged.
+    /// Mixed values and reset buttons are displayed like in data_view.
+    fn transform_2d_view(
+        ui: &mut egui::Ui,
+        data: &mut Data,
+        mixed: Option<&HashSet<String>>,
+        default_data: Option<&Data>,
+        texts: &Texts,
+    ) {
+        for name in ["position", "rotation", "scale"] {
+            let Some(Value::Vec3(v)) = data.values.get_mut(name) else {
+                continue;
+            };
+            ui.horizontal(|ui| {
+                ui.label(name);
+                if mixed.is_some_and(|mixed| mixed.contains(name)) {
+                    Self::color_label(ui, egui::Color32::DARK_RED, texts.get("Mixed"));
+                }
+                if name == "rotation" {
+                    ui.drag_angle(&mut v.z);
+                } else {
+                    Self::drag_float(ui, &mut v.x, None);
+                    Self::drag_float(ui, &mut v.y, None);
+                }
+                if let Some(Value::Vec3(default_v)) =
+                    default_data.and_then(|default_data| default_data.values.get(name))
+                {
+                    if default_v != v
+                        && ui
+                            .small_button("⟲")
+                            .on_hover_text(texts.get("Reset to Default"))
+                            .clicked()
+                    {
+                        *v = *default_v;
+                    }
                 }
             });
         }
@@ -649,6 +1019,7 @@ impl DataWindow {
         value: &Value,
         color: egui::Color32,
         app: &Box<dyn App>,
+        texts: &Texts,
     ) {
         match value {
             Value::Bool(b) => Self::color_label(ui, color, if *b { "☑" } else { "☐" }),
@@ -682,7 +1053,154 @@ impl DataWindow {
             Value::VecString(v) => Self::vec_value_view(ui, v, color),
             Value::VecEntity(v) => Self::vec_value_view(ui, v, color),
             Value::VecAsset(v) => Self::vec_value_view(ui, v, color),
+            Value::RangeFloat32(v) => {
+                Self::color_label(ui, color, format!("[{}, {}]", v.min, v.max))
+            }
+            Value::RangeInt32(v) => Self::color_label(ui, color, format!("[{}, {}]", v.min, v.max)),
         }
+
+        // ids and strings are often referenced in logs or code, so we make them easy to copy
+        let copy_text = match value {
+            Value::Int32(v) => Some(v.to_string()),
+            Value::Int64(v) => Some(v.to_string()),
+            Value::UInt32(v) => Some(v.to_string()),
+            Value::UInt64(v) => Some(v.to_string()),
+            Value::String(v) => Some(v.clone()),
+            Value::Entity(v) => Some(format!("{v:?}")),
+            Value::Asset(v) => Some(v.to_string()),
+            _ => None,
+        };
+        if let Some(copy_text) = copy_text {
+            if ui
+                .small_button("📋")
+                .on_hover_text(texts.get("Copy"))
+                .clicked()
+            {
+                ui.output_mut(|output| output.copied_text = copy_text);
+            }
+        }
+    }
+
+    /// Display an entity value with a dropdown of named entities and a button to clear it.
+    /// An entity can also be assigned by dragging it from Entities panel onto the value.
+    /// id must be unique among all values in the ui.
+    fn entity_value_view(&self, ui: &mut egui::Ui, v: &mut EntityId, id: &str, texts: &Texts) {
+        let selected_text = if *v == EntityId::dead() {
+            texts.get("None").to_string()
+        } else {
+            self.entity_names
+                .iter()
+                .find_map(|(eid, name)| (eid == v).then(|| name.clone()))
+                .unwrap_or_else(|| format!("{v:?}"))
+        };
+        let can_accept_what_is_being_dragged = self.dragged_entity != EntityId::dead();
+        let drop_result =
+            Self::drop_target(ui, can_accept_what_is_being_dragged, false, false, |ui| {
+                egui::ComboBox::from_id_source(id)
+                    .selected_text(selected_text)
+                    .show_ui(ui, |ui| {
+                        ui.selectable_value(v, EntityId::dead(), texts.get("None"));
+                        for (eid, name) in &self.entity_names {
+                            ui.selectable_value(v, *eid, name);
+                        }
+                    })
+                    .response
+                    .on_hover_text(texts.get("Drag an entity here from Entities panel"));
+            });
+        if drop_result.is_some() && ui.input(|input| input.pointer.any_released()) {
+            log::info!("Drop entity {:?} onto {id}", self.dragged_entity);
+            *v = self.dragged_entity;
+        }
+        if ui
+            .small_button("✖")
+            .on_hover_text(texts.get("Clear"))
+            .clicked()
+        {
+            *v = EntityId::dead();
+        }
+    }
+
+    /// Display an entity list value, each entity can be changed, moved up or down, or removed.
+    /// New entities are added by clicking "+" or dragging them from Entities panel onto "+".
+    fn vec_entity_value_view(
+        &self,
+        ui: &mut egui::Ui,
+        v: &mut Vec<EntityId>,
+        name: &String,
+        data_name: &str,
+        texts: &Texts,
+    ) {
+        ui.vertical(|ui| {
+            let (mut move_up, mut remove) = (None, None);
+            for i in 0..v.len() {
+                ui.horizontal(|ui| {
+                    // Use component_name/unique_name + value_name + index as id to make sure that every id is unique
+                    self.entity_value_view(
+                        ui,
+                        &mut v[i],
+                        &format!("{data_name} {name} {i}"),
+                        texts,
+                    );
+                    if ui
+                        .add_enabled(i > 0, egui::Button::new("⏶").small())
+                        .on_hover_text(texts.get("Move Up"))
+                        .clicked()
+                    {
+                        move_up = Some(i);
+                    }
+                    if ui
+                        .add_enabled(i + 1 < v.len(), egui::Button::new("⏷").small())
+                        .on_hover_text(texts.get("Move Down"))
+                        .clicked()
+                    {
+                        move_up = Some(i + 1);
+                    }
+                    if ui
+                        .small_button("-")
+                        .on_hover_text(texts.get("Remove"))
+                        .clicked()
+                    {
+                        remove = Some(i);
+                    }
+                });
+            }
+            if let Some(i) = move_up {
+                v.swap(i - 1, i);
+            }
+            if let Some(i) = remove {
+                v.remove(i);
+            }
+
+            let can_accept_what_is_being_dragged = self.dragged_entity != EntityId::dead();
+            let drop_result =
+                Self::drop_target(ui, can_accept_what_is_being_dragged, false, false, |ui| {
+                    if ui
+                        .button("+")
+                        .on_hover_text(texts.get("Drag an entity here from Entities panel"))
+                        .clicked()
+                    {
+                        v.push(EntityId::dead());
+                    }
+                });
+            if drop_result.is_some() && ui.input(|input| input.pointer.any_released()) {
+                log::info!("Add entity {:?} to {data_name}.{name}", self.dragged_entity);
+                v.push(self.dragged_entity);
+            }
+        });
+    }
+
+    /// Collect the ids and names of named entities for the dropdown of entity values, sorted by name.
+    fn update_entity_names(&mut self, entities: &EntitiesData) {
+        self.entity_names = entities
+            .iter()
+            .filter_map(|(eid, entity_data)| {
+                entity_data
+                    .name()
+                    .filter(|name| !name.is_empty())
+                    .map(|name| (*eid, name.clone()))
+            })
+            .collect();
+        self.entity_names.sort_by(|a, b| a.1.cmp(&b.1));
     }
 
     fn vec_value_view<T: std::fmt::Debug>(ui: &mut egui::Ui, v: &Vec<T>, color: egui::Color32) {
@@ -694,6 +1212,7 @@ impl DataWindow {
     }
 
     fn mutable_value_view(
+        &self,
         ui: &mut egui::Ui,
         value: &mut Value,
         limit: Option<&Limit>,
@@ -723,6 +1242,8 @@ impl DataWindow {
                     } else {
                         Self::color_label(ui, egui::Color32::RED, "zero length int_enum!");
                     }
+                } else if let Some(Limit::Int32RangeStep(range, step)) = limit {
+                    Self::drag_value_step(ui, v, range.clone(), *step);
                 } else {
                     let range = match limit {
                         Some(Limit::Int32Range(range)) => Some(range.clone()),
@@ -779,6 +1300,20 @@ impl DataWindow {
             Value::String(v) => {
                 if let Some(Limit::StringMultiline) = limit {
                     ui.text_edit_multiline(v);
+                } else if let Some(Limit::StringEnum(string_enum)) = limit {
+                    let selected_text = if string_enum.contains(v) {
+                        v.clone()
+                    } else {
+                        format!("{v} (missing)")
+                    };
+                    // Use component_name/unique_name + value_name as id to make sure that every id is unique
+                    egui::ComboBox::from_id_source(format!("{} {}", data_name, name))
+                        .selected_text(selected_text)
+                        .show_ui(ui, |ui| {
+                            for s in string_enum {
+                                ui.selectable_value(v, s.clone(), s);
+                            }
+                        });
                 } else {
                     ui.text_edit_singleline(v);
                 }
@@ -930,8 +1465,9 @@ impl DataWindow {
                     Self::drag_value(ui, &mut v.w, range.get(3).and_then(|r| r.clone()));
                 });
             }
+            // Use component_name/unique_name + value_name as id to make sure that every id is unique
             Value::Entity(v) => {
-                ui.label(format!("{v:?}")); // TODO: change entity in editor
+                self.entity_value_view(ui, v, &format!("{data_name} {name}"), texts)
             }
             Value::Asset(v) => {
                 ui.horizontal(|ui| {
@@ -947,17 +1483,34 @@ impl DataWindow {
                         } else {
                             asset_dir.as_ref().to_path_buf()
                         };
-                        let file = rfd::FileDialog::new()
-                            .set_directory(starting_dir)
-                            .pick_file();
+                        let mut file_dialog = rfd::FileDialog::new().set_directory(starting_dir);
+                        let extensions = match limit {
+                            Some(Limit::AssetExtensions(extensions)) => Some(extensions),
+                            _ => None,
+                        };
+                        if let Some(extensions) = extensions {
+                            file_dialog = file_dialog.add_filter(texts.get("Asset"), extensions);
+                        }
+                        let file = file_dialog.pick_file();
                         if let Some(mut file) = file {
-                            if file.starts_with(&asset_dir) {
-                                if file
-                                    .extension()
-                                    .is_some_and(|extension| extension == "asset")
-                                {
-                                    file = AssetInfo::asset_info_path_to_asset_path(file);
-                                }
+                            if file
+                                .extension()
+                                .is_some_and(|extension| extension == "asset")
+                            {
+                                file = AssetInfo::asset_info_path_to_asset_path(file);
+                            }
+                            let extension_allowed = extensions.is_none_or(|extensions| {
+                                file.extension().is_some_and(|extension| {
+                                    let extension = extension.to_string_lossy().to_lowercase();
+                                    extensions.contains(&extension)
+                                })
+                            });
+                            if !extension_allowed {
+                                log::error!(
+                                    "You must select a file with extension in {extensions:?}: {}",
+                                    file.display()
+                                );
+                            } else if file.starts_with(&asset_dir) {
                                 let asset_file = file.strip_prefix(&asset_dir).unwrap();
                                 match Project::get_asset_info_and_insert(
                                     &asset_dir, asset_file, app, false,
@@ -983,8 +1536,29 @@ impl DataWindow {
             Value::VecFloat32(v) => Self::vec_value_view(ui, v, color), // TODO: limit/add/remove/change
             Value::VecFloat64(v) => Self::vec_value_view(ui, v, color), // TODO: limit/add/remove/change
             Value::VecString(v) => Self::vec_value_view(ui, v, color),  // TODO: add/remove/change
-            Value::VecEntity(v) => Self::vec_value_view(ui, v, color),  // TODO: add/remove/change
-            Value::VecAsset(v) => Self::vec_value_view(ui, v, color),   // TODO: add/remove/change
+            Value::VecEntity(v) => self.vec_entity_value_view(ui, v, name, data_name, texts),
+            Value::VecAsset(v) => Self::vec_value_view(ui, v, color), // TODO: add/remove/change
+            Value::RangeFloat32(v) => {
+                let range = match limit {
+                    Some(Limit::Float32Range(range)) => range.clone(),
+                    _ => f32::MIN..=f32::MAX,
+                };
+                // min and max limit each other so that min <= max
+                ui.horizontal(|ui| {
+                    Self::drag_float(ui, &mut v.min, Some(*range.start()..=v.max));
+                    Self::drag_float(ui, &mut v.max, Some(v.min..=*range.end()));
+                });
+            }
+            Value::RangeInt32(v) => {
+                let range = match limit {
+                    Some(Limit::Int32Range(range)) => range.clone(),
+                    _ => i32::MIN..=i32::MAX,
+                };
+                ui.horizontal(|ui| {
+                    Self::drag_value(ui, &mut v.min, Some(*range.start()..=v.max));
+                    Self::drag_value(ui, &mut v.max, Some(v.min..=*range.end()));
+                });
+            }
         }
     }
 
@@ -1021,6 +1595,16 @@ impl DataWindow {
         ui.add(drag_value);
     }
 
+    /// Like [Self::drag_value], but the value snaps to step inside range, see [Limit::snap_to_step].
+    fn drag_value_step(ui: &mut egui::Ui, v: &mut i32, range: RangeInclusive<i32>, step: i32) {
+        ui.add(
+            egui::DragValue::new(v)
+                .speed(step.max(1))
+                .clamp_range(range.clone()),
+        );
+        *v = Limit::snap_to_step(*v, &range, step);
+    }
+
     fn show_asset(
         ui: &mut egui::Ui,
         color: egui::Color32,
@@ -1055,12 +1639,87 @@ impl DataWindow {
         self.selected_entity
     }
 
+    /// Select only this entity, clearing the multi-selection.
     pub fn set_selected_entity(&mut self, selected_entity: EntityId) {
         self.selected_entity = selected_entity;
+        self.selected_entities.clear();
+        if selected_entity != EntityId::dead() {
+            self.selected_entities.insert(selected_entity);
+        }
+    }
+
+    /// Add entity to the multi-selection or remove it if it is already selected.
+    fn toggle_selected_entity(&mut self, entity: EntityId) {
+        if self.selected_entities.remove(&entity) {
+            if self.selected_entity == entity {
+                self.selected_entity = self
+                    .selected_entities
+                    .iter()
+                    .next()
+                    .copied()
+                    .unwrap_or_else(EntityId::dead);
+            }
+        } else {
+            self.selected_entities.insert(entity);
+            self.selected_entity = entity;
+        }
+    }
+
+    /// The entity shown in Entity panel, which is the locked entity or the selected entity.
+    pub fn inspected_entity(&self) -> EntityId {
+        self.locked_entity.unwrap_or(self.selected_entity)
+    }
+
+    /// The unique shown in Unique panel, which is the locked unique or the selected unique.
+    pub fn inspected_unique(&self) -> &String {
+        self.locked_unique.as_ref().unwrap_or(&self.selected_unique)
     }
 
-    pub fn selected_unique(&self) -> &String {
-        &self.selected_unique
+    /// Display the data of inspected unique with a lock button.
+    pub fn unique_view(
+        &mut self,
+        ui: &mut egui::Ui,
+        unique_data: &mut Data,
+        entities: &EntitiesData,
+        app: &mut Box<dyn App>,
+        asset_dir: impl AsRef<Path>,
+        texts: &Texts,
+    ) {
+        self.update_entity_names(entities);
+        let unique_name = self.inspected_unique().clone();
+        let mut default_data = None;
+        app.command(Command::GetDefaultUniqueData(
+            &unique_name,
+            &mut default_data,
+        ));
+        ui.horizontal(|ui| {
+            Self::color_label(ui, egui::Color32::BLACK, &unique_name);
+            let mut locked = self.locked_unique.is_some();
+            if ui.toggle_value(&mut locked, texts.get("Lock")).changed() {
+                self.locked_unique = locked.then(|| unique_name.clone());
+            }
+            if let Some(default_data) = &default_data {
+                if ui
+                    .button("⟲")
+                    .on_hover_text(texts.get("Reset to Default"))
+                    .clicked()
+                {
+                    log::info!("Reset unique {unique_name}");
+                    Self::reset_data(unique_data, default_data, None);
+                }
+            }
+        });
+        ui.separator();
+        self.data_view(
+            ui,
+            &unique_name,
+            unique_data,
+            None,
+            default_data.as_ref(),
+            app,
+            asset_dir,
+            texts,
+        );
     }
 }
 
@@ -1,16 +1,17 @@
 use egui_winit_vulkano::Gui;
 use glam::{UVec2, Vec2};
-use std::sync::Arc;
+use std::{error::Error, path::Path, sync::Arc};
 use steel_common::ext::VulkanoWindowRendererExt;
 use vulkano::{
+    buffer::{Buffer, BufferCreateInfo, BufferUsage},
     command_buffer::{
         allocator::StandardCommandBufferAllocator, AutoCommandBufferBuilder, CommandBufferUsage,
-        CopyImageInfo, PrimaryCommandBufferAbstract,
+        CopyImageInfo, CopyImageToBufferInfo, PrimaryCommandBufferAbstract,
     },
     device::Queue,
     format::Format,
     image::{view::ImageView, Image, ImageCreateInfo, ImageUsage},
-    memory::allocator::AllocationCreateInfo,
+    memory::allocator::{AllocationCreateInfo, MemoryTypeFilter},
     sync::GpuFuture,
 };
 use vulkano_util::{context::VulkanoContext, renderer::VulkanoWindowRenderer};
@@ -180,4 +181,70 @@ impl ImageWindow {
             .unwrap()
             .boxed()
     }
+
+    /// Save the image of current frame as a png file whose width and height are at most max_size.
+    /// The image was drawn in a previous frame which uses the same image index, which is good enough for thumbnail.
+    pub fn save_thumbnail(
+        &self,
+        context: &VulkanoContext,
+        max_size: u32,
+        path: impl AsRef<Path>,
+    ) -> Result<(), Box<dyn Error>> {
+        let unorm_image = self.image().ok_or("image is not created yet")?.image();
+        let [width, height, _] = unorm_image.extent();
+        let buffer = Buffer::from_iter(
+            context.memory_allocator().clone(),
+            BufferCreateInfo {
+                usage: BufferUsage::TRANSFER_DST,
+                ..Default::default()
+            },
+            AllocationCreateInfo {
+                memory_type_filter: MemoryTypeFilter::PREFER_HOST
+                    | MemoryTypeFilter::HOST_RANDOM_ACCESS,
+                ..Default::default()
+            },
+            (0..width * height * 4).map(|_| 0u8),
+        )?;
+        let allocator =
+            StandardCommandBufferAllocator::new(context.device().clone(), Default::default());
+        let mut builder = AutoCommandBufferBuilder::primary(
+            &allocator,
+            context.graphics_queue().queue_family_index(),
+            CommandBufferUsage::OneTimeSubmit,
+        )?;
+        builder.copy_image_to_buffer(CopyImageToBufferInfo::image_buffer(
+            unorm_image.clone(),
+            buffer.clone(),
+        ))?;
+        builder
+            .build()?
+            // no need to execute after previous drawing future because they are excuting on the same vk queue
+            .execute(context.graphics_queue().clone())?
+            // the copy must be finished before the buffer is read
+            .then_signal_fence_and_flush()?
+            .wait(None)?;
+
+        let mut pixels = buffer.read()?.to_vec();
+        if matches!(
+            unorm_image.format(),
+            Format::B8G8R8A8_UNORM | Format::B8G8R8A8_SRGB
+        ) {
+            pixels
+                .chunks_exact_mut(4)
+                .for_each(|pixel| pixel.swap(0, 2));
+        }
+        let image =
+            image::RgbaImage::from_raw(width, height, pixels).ok_or("image buffer is too small")?;
+        let scale = (max_size as f32 / width.max(height) as f32).min(1.0);
+        let thumbnail = image::imageops::thumbnail(
+            &image,
+            ((width as f32 * scale) as u32).max(1),
+            ((height as f32 * scale) as u32).max(1),
+        );
+        if let Some(parent) = path.as_ref().parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        thumbnail.save(path)?;
+        Ok(())
+    }
 }
@@ -1,22 +1,39 @@
-use super::{image_window::ImageWindow, EditorState};
+use super::{confirm_dialog::ConfirmDialog, image_window::ImageWindow, EditorState};
 use crate::{
     locale::{Language, Texts},
-    project::Project,
+    project::{Project, ProjectFolder},
     ui::data_window::DataWindow,
-    utils::LocalData,
+    utils::{self, KeyBinding, KeyBindings, LocalData, Theme},
 };
 use egui_dock::DockState;
 use egui_winit_vulkano::Gui;
 use shipyard::EntityId;
-use std::time::Instant;
-use steel_common::{app::Command, camera::SceneCamera, data::WorldData};
+use std::{
+    path::{Path, PathBuf},
+    time::Instant,
+};
+use steel_common::{
+    app::{Command, SceneStats},
+    camera::SceneCamera,
+    data::{Limit, Value, WorldData},
+};
 use vulkano_util::context::VulkanoContext;
 
 pub struct MenuBar {
     show_open_project_dialog: bool,
     show_asset_system_introduction_dialog: bool,
     show_scene_camera_edit_window: bool,
-    switch_to_game_window_on_start: bool,
+    show_scene_stats_window: bool,
+    show_key_bindings_window: bool,
+    /// True if user is opening a non-empty folder which is not a steel project, waiting for confirmation.
+    open_other_folder: bool,
+    /// The index of the key binding in [KeyBindings::iter_mut] which is waiting for a key press.
+    changing_key_binding: Option<usize>,
+    confirm_dialog: ConfirmDialog<MenuAction>,
+    /// The menu action waiting for user to decide whether to save current scene, see [MenuBar::save_prompt_dialog].
+    save_prompt: Option<MenuAction>,
+    /// The menu action to do at the end of [MenuBar::ui].
+    menu_action: Option<MenuAction>,
     fps_counter: FpsCounter,
 }
 
@@ -26,7 +43,13 @@ impl MenuBar {
             show_open_project_dialog: false,
             show_asset_system_introduction_dialog: false,
             show_scene_camera_edit_window: false,
-            switch_to_game_window_on_start: false,
+            show_scene_stats_window: false,
+            show_key_bindings_window: false,
+            open_other_folder: false,
+            changing_key_binding: None,
+            confirm_dialog: ConfirmDialog::new(),
+            save_prompt: None,
+            menu_action: None,
             fps_counter: FpsCounter::new(),
         }
     }
@@ -67,6 +90,10 @@ impl MenuBar {
 
         self.scene_camera_edit_window(data_window, ctx, project, texts, scene_camera);
 
+        self.scene_stats_window(ctx, project, texts);
+
+        self.key_bindings_window(ctx, local_data, texts);
+
         egui::TopBottomPanel::top("my_top_panel").show(&ctx, |ui| {
             egui::menu::bar(ui, |ui| {
                 ui.menu_button(texts.get("Project"), |ui| {
@@ -78,9 +105,7 @@ impl MenuBar {
                     if project.is_open() {
                         if ui.button(texts.get("Close")).clicked() {
                             log::info!("Menu->Project->Close");
-                            scene_window.close(Some(gui));
-                            game_window.close(Some(gui));
-                            project.close(local_data, window_title, gui_game);
+                            self.request_menu_action(MenuAction::CloseProject, project, local_data);
                             ui.close_menu();
                         }
                         if ui.button(texts.get("Compile")).clicked() {
@@ -90,6 +115,16 @@ impl MenuBar {
                             project.compile(local_data, gui_game, context);
                             ui.close_menu();
                         }
+                        if ui
+                            .checkbox(&mut local_data.release_build, texts.get("Release Build"))
+                            .changed()
+                        {
+                            log::info!(
+                                "Menu->Project->Release Build: {}",
+                                local_data.release_build
+                            );
+                            local_data.save();
+                        }
                     }
                     if project.is_compiled() && !project.is_running() {
                         ui.menu_button(texts.get("Export"), |ui| {
@@ -120,16 +155,16 @@ impl MenuBar {
                                     log::info!("Menu->Edit->Duplicate");
                                     DataWindow::duplicate_entity(
                                         data_window.selected_entity(),
-                                        &world_data.entities,
                                         project.app().unwrap(),
                                     );
                                     ui.close_menu();
                                 }
                                 if ui.button(texts.get("Delete")).clicked() {
                                     log::info!("Menu->Edit->Delete");
-                                    data_window.delete_entity(
+                                    data_window.request_delete_entity(
                                         data_window.selected_entity(),
                                         project.app().unwrap(),
+                                        local_data,
                                     );
                                     ui.close_menu();
                                 }
@@ -157,7 +192,6 @@ impl MenuBar {
                                         let asset_dir = project.asset_dir().unwrap();
                                         DataWindow::save_as_prefab(
                                             data_window.selected_entity(),
-                                            &world_data.entities,
                                             project.app().unwrap(),
                                             asset_dir,
                                         );
@@ -166,10 +200,25 @@ impl MenuBar {
                                 }
                             },
                         );
+                        if let Some(prefs) = project.prefs_mut() {
+                            if ui
+                                .checkbox(
+                                    &mut prefs.transform_2d,
+                                    texts.get("2D Transform Editing"),
+                                )
+                                .changed()
+                            {
+                                log::info!(
+                                    "Menu->Edit->2D Transform Editing: {}",
+                                    prefs.transform_2d
+                                );
+                                project.save_prefs();
+                            }
+                        }
                         ui.menu_button(texts.get("Create"), |ui| {
                             if ui.button(texts.get("New Entity")).clicked() {
                                 log::info!("Menu->Edit->Create->New Entity");
-                                DataWindow::create_new_entity(project.app().unwrap());
+                                DataWindow::create_new_entity(project.app().unwrap(), scene_camera);
                                 ui.close_menu();
                             }
                             if ui.button(texts.get("From Prefab")).clicked() {
@@ -182,6 +231,11 @@ impl MenuBar {
                                 ui.close_menu();
                             }
                         });
+                        if ui.button(texts.get("Scene Statistics")).clicked() {
+                            log::info!("Menu->Edit->Scene Statistics");
+                            self.show_scene_stats_window = true;
+                            ui.close_menu();
+                        }
                     });
 
                     ui.add_enabled_ui(!project.is_running(), |ui| {
@@ -196,7 +250,13 @@ impl MenuBar {
                                     .clicked()
                                 {
                                     log::info!("Menu->Scene->Save");
-                                    project.save_scene(scene_path);
+                                    project.save_scene(scene_path.as_path());
+                                    Self::save_scene_thumbnail(
+                                        project,
+                                        scene_window,
+                                        context,
+                                        scene_path,
+                                    );
                                     ui.close_menu();
                                 }
                             }
@@ -224,6 +284,12 @@ impl MenuBar {
                                     );
                                     if let Some(file) = file {
                                         project.save_scene(file);
+                                        Self::save_scene_thumbnail(
+                                            project,
+                                            scene_window,
+                                            context,
+                                            file,
+                                        );
                                     }
                                 }
                                 ui.close_menu();
@@ -240,18 +306,18 @@ impl MenuBar {
                                         "After convert_to_scene_relative_path, file={file:?}"
                                     );
                                     if let Some(file) = file {
-                                        project.load_scene(file);
-                                        // We set world_data to None to prevent app from loading outdated world_data later this frame.
-                                        // But this will cause a splash screen problem due to the disappearance of the windows showing world_data for one frame.
-                                        // TODO: find a way to avoid this splash screen problem.
-                                        *world_data = None;
+                                        self.request_menu_action(
+                                            MenuAction::LoadScene(file.to_path_buf()),
+                                            project,
+                                            local_data,
+                                        );
                                     }
                                 }
                                 ui.close_menu();
                             }
                             if ui.button(texts.get("New")).clicked() {
                                 log::info!("Menu->Scene->New");
-                                project.new_scene();
+                                self.request_menu_action(MenuAction::NewScene, project, local_data);
                                 ui.close_menu();
                             }
                         });
@@ -259,12 +325,13 @@ impl MenuBar {
 
                     // start running function
                     let start_running_fn =
-                        |project: &mut Project,
-                         switch_to_game_window_on_start: bool,
-                         dock_state: &mut DockState<String>| {
+                        |project: &mut Project, dock_state: &mut DockState<String>| {
                             project.save_to_memory(None);
                             project.app().unwrap().command(Command::ResetTime);
-                            if switch_to_game_window_on_start {
+                            if project
+                                .prefs()
+                                .is_some_and(|prefs| prefs.switch_to_game_window_on_start)
+                            {
                                 if let Some(tab) = dock_state.find_tab(&"Game".to_string()) {
                                     dock_state.set_active_tab(tab);
                                 }
@@ -272,9 +339,19 @@ impl MenuBar {
                             project.set_running(true);
                         };
 
-                    if !project.is_running() && ui.input(|i| i.key_pressed(egui::Key::F5)) {
-                        log::info!("Start running by pressing F5");
-                        start_running_fn(project, self.switch_to_game_window_on_start, dock_state);
+                    if !project.is_running()
+                        && self.changing_key_binding.is_none()
+                        && local_data.key_bindings.start_running.pressed(ctx)
+                    {
+                        log::info!("Start running by pressing shortcut");
+                        start_running_fn(project, dock_state);
+                    }
+
+                    if self.changing_key_binding.is_none()
+                        && local_data.key_bindings.toggle_ray_tracing.pressed(ctx)
+                    {
+                        log::info!("Toggle ray tracing by pressing shortcut");
+                        Self::toggle_ray_tracing(world_data);
                     }
 
                     ui.menu_button(texts.get("Run"), |ui| {
@@ -283,24 +360,58 @@ impl MenuBar {
                                 log::info!("Menu->Run->Stop");
                                 project.load_from_memory();
                                 project.set_running(false);
+                                editor_state.spawn_prefab = None;
+                                ui.close_menu();
+                            }
+                            if editor_state.spawn_prefab.is_some() {
+                                if ui.button(texts.get("Stop Spawning Prefab")).clicked() {
+                                    log::info!("Menu->Run->Stop Spawning Prefab");
+                                    editor_state.spawn_prefab = None;
+                                    ui.close_menu();
+                                }
+                            } else if ui
+                                .button(texts.get("Spawn Prefab by Clicking"))
+                                .on_hover_text(texts.get(
+                                    "Choose a prefab, then click in Scene window to spawn it, spawned entities are discarded when running stops",
+                                ))
+                                .clicked()
+                            {
+                                log::info!("Menu->Run->Spawn Prefab by Clicking");
+                                let asset_dir = project.asset_dir().unwrap();
+                                editor_state.spawn_prefab =
+                                    DataWindow::pick_prefab(project.app().unwrap(), asset_dir);
                                 ui.close_menu();
                             }
                         } else {
-                            if ui.button(format!("{} (F5)", texts.get("Start"))).clicked() {
+                            if ui
+                                .button(format!(
+                                    "{} ({})",
+                                    texts.get("Start"),
+                                    local_data.key_bindings.start_running.format(ctx)
+                                ))
+                                .clicked()
+                            {
                                 log::info!("Menu->Run->Start");
-                                start_running_fn(
-                                    project,
-                                    self.switch_to_game_window_on_start,
-                                    dock_state,
-                                );
+                                start_running_fn(project, dock_state);
                                 ui.close_menu();
                             }
                         }
 
-                        ui.checkbox(
-                            &mut self.switch_to_game_window_on_start,
-                            texts.get("Switch to Game Window on Start"),
-                        );
+                        if let Some(prefs) = project.prefs_mut() {
+                            if ui
+                                .checkbox(
+                                    &mut prefs.switch_to_game_window_on_start,
+                                    texts.get("Switch to Game Window on Start"),
+                                )
+                                .changed()
+                            {
+                                log::info!(
+                                    "Menu->Run->Switch to Game Window on Start: {}",
+                                    prefs.switch_to_game_window_on_start
+                                );
+                                project.save_prefs();
+                            }
+                        }
                     });
 
                     ui.menu_button(texts.get("Camera"), |ui| {
@@ -310,13 +421,48 @@ impl MenuBar {
                             ui.close_menu();
                         }
                         if ui
-                            .button(format!("{} (Home)", texts.get("Reset")))
+                            .button(format!(
+                                "{} ({})",
+                                texts.get("Reset"),
+                                local_data.key_bindings.reset_scene_camera.format(ctx)
+                            ))
                             .clicked()
                         {
                             log::info!("Menu->Camera->Reset");
                             scene_camera.reset();
                             ui.close_menu();
                         }
+                        if ui.button(texts.get("Frame All")).clicked() {
+                            log::info!("Menu->Camera->Frame All");
+                            let mut bounds = None;
+                            project
+                                .app()
+                                .unwrap()
+                                .command(Command::GetWorldBounds(&mut bounds));
+                            if let Some((min, max)) = bounds {
+                                scene_camera.frame(min, max);
+                            }
+                            ui.close_menu();
+                        }
+                        if ui
+                            .checkbox(&mut editor_state.solo_render, texts.get("Solo Render"))
+                            .on_hover_text(texts.get("Only draw the selected entity and its descendants"))
+                            .changed()
+                        {
+                            log::info!("Menu->Camera->Solo Render: {}", editor_state.solo_render);
+                        }
+                        if ui
+                            .button(format!(
+                                "{} ({})",
+                                texts.get("Toggle Ray Tracing"),
+                                local_data.key_bindings.toggle_ray_tracing.format(ctx)
+                            ))
+                            .clicked()
+                        {
+                            log::info!("Menu->Camera->Toggle Ray Tracing");
+                            Self::toggle_ray_tracing(world_data);
+                            ui.close_menu();
+                        }
                     });
                 });
 
@@ -329,6 +475,66 @@ impl MenuBar {
                             }
                             ui.close_menu();
                         }
+                        ui.add_enabled_ui(project.is_compiled(), |ui| {
+                            if ui.button(texts.get("Import All Assets")).clicked() {
+                                log::info!("Menu->Asset->Import All Assets");
+                                project.import_assets(None);
+                                ui.close_menu();
+                            }
+                            if ui.button(texts.get("Import Asset Ids")).clicked() {
+                                log::info!("Menu->Asset->Import Asset Ids");
+                                let file = rfd::FileDialog::new()
+                                    .add_filter("json", &["json"])
+                                    .pick_file();
+                                log::info!("Close FileDialog, file={file:?}");
+                                if let Some(file) = file {
+                                    project.import_assets(Some(&file));
+                                }
+                                ui.close_menu();
+                            }
+                        });
+                        if ui.button(texts.get("Export Asset Ids")).clicked() {
+                            log::info!("Menu->Asset->Export Asset Ids");
+                            let file = rfd::FileDialog::new()
+                                .add_filter("json", &["json"])
+                                .set_file_name("asset-ids.json")
+                                .save_file();
+                            log::info!("Close FileDialog, file={file:?}");
+                            if let Some(file) = file {
+                                project.export_asset_ids(&file);
+                            }
+                            ui.close_menu();
+                        }
+                        ui.add_enabled_ui(project.is_compiled(), |ui| {
+                            if ui.button(texts.get("Rename/Move Asset")).clicked() {
+                                log::info!("Menu->Asset->Rename/Move Asset");
+                                if let Some(asset_dir) = project.asset_dir() {
+                                    let from = rfd::FileDialog::new()
+                                        .set_directory(&asset_dir)
+                                        .pick_file();
+                                    log::info!("Close FileDialog, from={from:?}");
+                                    if let Some(from) = from {
+                                        let mut dialog = rfd::FileDialog::new()
+                                            .set_directory(from.parent().unwrap_or(&asset_dir));
+                                        if let Some(file_name) = from.file_name() {
+                                            dialog =
+                                                dialog.set_file_name(file_name.to_string_lossy());
+                                        }
+                                        let to = dialog.save_file();
+                                        log::info!("Close FileDialog, to={to:?}");
+                                        if let Some(to) = to {
+                                            project.rename_asset(&from, &to);
+                                        }
+                                    }
+                                }
+                                ui.close_menu();
+                            }
+                        });
+                        if ui.button(texts.get("Migrate Scenes And Prefabs")).clicked() {
+                            log::info!("Menu->Asset->Migrate Scenes And Prefabs");
+                            project.migrate_assets();
+                            ui.close_menu();
+                        }
                         if ui.button(texts.get("Introduction")).clicked() {
                             log::info!("Menu->Asset->Introduction");
                             self.show_asset_system_introduction_dialog = true;
@@ -364,12 +570,242 @@ impl MenuBar {
                             ui.close_menu();
                         }
                     });
+                    let mut confirm_destructive_actions = !local_data.skip_confirm_dialog;
+                    if ui
+                        .checkbox(
+                            &mut confirm_destructive_actions,
+                            texts.get("Confirm Destructive Actions"),
+                        )
+                        .changed()
+                    {
+                        log::info!(
+                            "Menu->Ui->Confirm Destructive Actions: {confirm_destructive_actions}"
+                        );
+                        local_data.skip_confirm_dialog = !confirm_destructive_actions;
+                        local_data.save();
+                    }
+                    if ui.button(texts.get("Key Bindings")).clicked() {
+                        log::info!("Menu->Ui->Key Bindings");
+                        self.show_key_bindings_window = true;
+                        ui.close_menu();
+                    }
+                    ui.menu_button(texts.get("Anti-aliasing"), |ui| {
+                        for (msaa_samples, text) in [
+                            (1, texts.get("Off")),
+                            (2, "2x MSAA"),
+                            (4, "4x MSAA"),
+                            (8, "8x MSAA"),
+                        ] {
+                            if ui
+                                .radio_value(&mut local_data.msaa_samples, msaa_samples, text)
+                                .clicked()
+                            {
+                                log::info!("Menu->Ui->Anti-aliasing: {msaa_samples}");
+                                local_data.save();
+                                ui.close_menu();
+                            }
+                        }
+                    });
+                    ui.menu_button(texts.get("Theme"), |ui| {
+                        for (theme, text) in [
+                            (Theme::Dark, texts.get("Dark")),
+                            (Theme::Light, texts.get("Light")),
+                        ] {
+                            if ui.radio_value(&mut local_data.theme, theme, text).clicked() {
+                                log::info!("Menu->Ui->Theme: {theme:?}");
+                                ctx.set_visuals(theme.visuals());
+                                local_data.save();
+                                ui.close_menu();
+                            }
+                        }
+                    });
+                    ui.menu_button(texts.get("Font"), |ui| {
+                        if ui.button(texts.get("Choose Font File")).clicked() {
+                            log::info!("Menu->Ui->Font->Choose Font File");
+                            let file = rfd::FileDialog::new()
+                                .add_filter("font", &["ttf", "ttc", "otf"])
+                                .pick_file();
+                            log::info!("Close FileDialog, file={file:?}");
+                            if let Some(file) = file {
+                                ctx.set_fonts(utils::editor_fonts(Some(&file)));
+                                local_data.font_path = Some(file);
+                                local_data.save();
+                            }
+                            ui.close_menu();
+                        }
+                        if ui.button(texts.get("Default Font")).clicked() {
+                            log::info!("Menu->Ui->Font->Default Font");
+                            ctx.set_fonts(utils::editor_fonts(None));
+                            local_data.font_path = None;
+                            local_data.save();
+                            ui.close_menu();
+                        }
+                    });
                 });
 
+                // zoom factor can be changed by zoom menu buttons or keyboard shortcuts
+                if ctx.zoom_factor() != local_data.zoom_factor {
+                    log::info!("Zoom factor: {}", ctx.zoom_factor());
+                    local_data.zoom_factor = ctx.zoom_factor();
+                    local_data.save();
+                }
+
                 self.fps_counter.update();
                 ui.label(format!("{}{:.2}", texts.get("fps: "), self.fps_counter.fps));
             });
         });
+
+        if let Some(action) = self.confirm_dialog.show(
+            ctx,
+            |action| {
+                match action {
+                    MenuAction::CloseProject => {
+                        texts.get("Close the project? Unsaved changes will be lost.")
+                    }
+                    MenuAction::NewScene => {
+                        texts.get("Create a new scene? Unsaved changes will be lost.")
+                    }
+                    MenuAction::LoadScene(_) => {
+                        texts.get("Load the scene? Unsaved changes will be lost.")
+                    }
+                }
+                .to_string()
+            },
+            local_data,
+            texts,
+        ) {
+            self.menu_action = Some(action);
+        }
+
+        self.save_prompt_dialog(ctx, project, scene_window, context, texts);
+
+        if let Some(action) = self.menu_action.take() {
+            match action {
+                MenuAction::CloseProject => {
+                    scene_window.close(Some(gui));
+                    game_window.close(Some(gui));
+                    project.close(local_data, window_title, gui_game);
+                }
+                MenuAction::NewScene => project.new_scene(),
+                MenuAction::LoadScene(file) => {
+                    project.load_scene(file);
+                    // We set world_data to None to prevent app from loading outdated world_data later this frame.
+                    // But this will cause a splash screen problem due to the disappearance of the windows showing world_data for one frame.
+                    // TODO: find a way to avoid this splash screen problem.
+                    *world_data = None;
+                }
+            }
+        }
+    }
+
+    /// Request to do a menu action which may discard unsaved changes. If current scene has unsaved changes,
+    /// user is prompted to save it first, otherwise close project and new scene need confirmation by [ConfirmDialog].
+    /// The action is done at the end of [MenuBar::ui] after it is confirmed.
+    fn request_menu_action(
+        &mut self,
+        action: MenuAction,
+        project: &Project,
+        local_data: &LocalData,
+    ) {
+        if project.is_dirty() {
+            self.save_prompt = Some(action);
+        } else if let MenuAction::LoadScene(_) = action {
+            self.menu_action = Some(action);
+        } else {
+            self.menu_action = self.confirm_dialog.request(action, local_data);
+        }
+    }
+
+    /// Ask user whether to save current scene before doing the menu action in self.save_prompt.
+    fn save_prompt_dialog(
+        &mut self,
+        ctx: &egui::Context,
+        project: &mut Project,
+        scene_window: &ImageWindow,
+        context: &VulkanoContext,
+        texts: &Texts,
+    ) {
+        if self.save_prompt.is_none() {
+            return;
+        }
+        let (mut save, mut dont_save, mut cancel) = (false, false, false);
+        egui::Window::new(texts.get("Unsaved Changes"))
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+            .show(ctx, |ui| {
+                ui.label(texts.get("Current scene has unsaved changes. Save it?"));
+                ui.horizontal(|ui| {
+                    save = ui.button(texts.get("Save")).clicked();
+                    dont_save = ui.button(texts.get("Don't Save")).clicked();
+                    cancel = ui.button(texts.get("Cancel")).clicked();
+                });
+            });
+        if save {
+            log::info!("MenuBar::save_prompt_dialog: Save");
+            let scene_path = project.scene_relative_path().or_else(|| {
+                let file = rfd::FileDialog::new()
+                    .set_directory(project.asset_dir()?)
+                    .save_file()?;
+                project
+                    .convert_to_scene_relative_path(&file.with_extension("scene"))
+                    .map(Path::to_path_buf)
+            });
+            if let Some(scene_path) = scene_path {
+                project.save_scene(&scene_path);
+                Self::save_scene_thumbnail(project, scene_window, context, scene_path);
+            }
+            // only continue the menu action if current scene is saved successfully
+            let action = self.save_prompt.take();
+            if !project.is_dirty() {
+                self.menu_action = action;
+            }
+        } else if dont_save {
+            log::info!("MenuBar::save_prompt_dialog: Don't Save");
+            self.menu_action = self.save_prompt.take();
+        } else if cancel {
+            log::info!("MenuBar::save_prompt_dialog: Cancel");
+            self.save_prompt = None;
+        }
+    }
+
+    /// Flip the "ray_tracing" value of RenderManager unique, which is loaded into app in this frame.
+    /// This does nothing if ray tracing is not supported, in which case the value is read-only.
+    fn toggle_ray_tracing(world_data: &mut Option<WorldData>) {
+        let Some(render_manager) = world_data
+            .as_mut()
+            .and_then(|world_data| world_data.uniques.0.get_mut("RenderManager"))
+        else {
+            return;
+        };
+        if matches!(
+            render_manager.limits.get("ray_tracing"),
+            Some(Limit::ReadOnly)
+        ) {
+            log::warn!("MenuBar::toggle_ray_tracing: ray tracing is not supported");
+            return;
+        }
+        if let Some(Value::Bool(ray_tracing)) = render_manager.values.get_mut("ray_tracing") {
+            *ray_tracing = !*ray_tracing;
+        }
+    }
+
+    /// Capture scene window as the thumbnail of the saved scene so that scenes can be previewed.
+    fn save_scene_thumbnail(
+        project: &Project,
+        scene_window: &ImageWindow,
+        context: &VulkanoContext,
+        scene: impl AsRef<Path>,
+    ) {
+        if let Some(thumbnail_path) = project.scene_thumbnail_path(scene) {
+            match scene_window.save_thumbnail(context, 256, &thumbnail_path) {
+                Ok(_) => log::info!("Saved scene thumbnail: {}", thumbnail_path.display()),
+                Err(e) => log::warn!(
+                    "MenuBar::save_scene_thumbnail: failed to save {}, error={e}",
+                    thumbnail_path.display()
+                ),
+            }
+        }
     }
 
     fn open_project_dialog(
@@ -406,23 +842,52 @@ impl MenuBar {
                         }
                     }
                 });
-                if ui.button(texts.get("Open")).clicked() {
+                let folder = Project::check_folder(&editor_state.project_path);
+                if folder != ProjectFolder::Other {
+                    self.open_other_folder = false;
+                }
+                let mut open = false;
+                if self.open_other_folder {
+                    ui.colored_label(
+                        egui::Color32::RED,
+                        texts.get("The folder is not empty and is not a steel project. Create a steel project in it anyway?"),
+                    );
+                    ui.horizontal(|ui| {
+                        open = ui.button(texts.get("Create Anyway")).clicked();
+                        if ui.button(texts.get("Cancel")).clicked() {
+                            self.open_other_folder = false;
+                        }
+                    });
+                } else if folder == ProjectFolder::NotFolder {
+                    ui.colored_label(egui::Color32::RED, texts.get("The path is not a folder."));
+                } else if ui.button(texts.get("Open")).clicked() {
                     if editor_state.project_path.display().to_string().is_empty() {
                         log::info!("Open project failed, path is empty");
-                    } else {
-                        log::info!("Open project, path={}", editor_state.project_path.display());
-                        scene_window.close(Some(gui));
-                        game_window.close(Some(gui));
-                        project.open(
-                            editor_state.project_path.clone(),
-                            local_data,
-                            window_title,
-                            gui_game,
+                    } else if folder == ProjectFolder::Other {
+                        log::info!(
+                            "Open project in non-steel folder, waiting for confirmation, path={}",
+                            editor_state.project_path.display()
                         );
-                        project.compile(local_data, gui_game, context);
-                        self.show_open_project_dialog = false;
+                        self.open_other_folder = true;
+                    } else {
+                        open = true;
                     }
                 }
+                if open {
+                    log::info!("Open project, path={}", editor_state.project_path.display());
+                    scene_window.close(Some(gui));
+                    game_window.close(Some(gui));
+                    project.open(
+                        editor_state.project_path.clone(),
+                        self.open_other_folder,
+                        local_data,
+                        window_title,
+                        gui_game,
+                    );
+                    project.compile(local_data, gui_game, context);
+                    self.show_open_project_dialog = false;
+                    self.open_other_folder = false;
+                }
             });
         self.show_open_project_dialog &= show;
     }
@@ -455,6 +920,8 @@ impl MenuBar {
                     ui,
                     "SceneCamera",
                     &mut data,
+                    None,
+                    None,
                     project.app().unwrap(),
                     asset_dit,
                     texts,
@@ -462,6 +929,135 @@ impl MenuBar {
                 scene_camera.set_data(&data);
             });
     }
+
+    fn scene_stats_window(&mut self, ctx: &egui::Context, project: &mut Project, texts: &Texts) {
+        if !project.is_compiled() {
+            self.show_scene_stats_window = false;
+        }
+        egui::Window::new(texts.get("Scene Statistics"))
+            .open(&mut self.show_scene_stats_window)
+            .show(ctx, |ui| {
+                let mut stats = SceneStats::default();
+                project
+                    .app()
+                    .unwrap()
+                    .command(Command::GetSceneStats(&mut stats));
+                let draw_count: usize = stats.draw_counts.iter().map(|(_, count)| count).sum();
+
+                egui::Grid::new("scene_stats").show(ui, |ui| {
+                    ui.label(texts.get("Entities"));
+                    ui.label(stats.entity_count.to_string());
+                    ui.end_row();
+                    ui.label(texts.get("Colliders"));
+                    ui.label(stats.collider_count.to_string());
+                    ui.end_row();
+                    ui.label(texts.get("Draws"));
+                    ui.label(draw_count.to_string());
+                    ui.end_row();
+                });
+
+                egui::CollapsingHeader::new(texts.get("Components"))
+                    .default_open(true)
+                    .show(ui, |ui| {
+                        egui::Grid::new("scene_stats_components").show(ui, |ui| {
+                            for (name, count) in &stats.component_counts {
+                                ui.label(name);
+                                ui.label(count.to_string());
+                                ui.end_row();
+                            }
+                        });
+                    });
+
+                egui::CollapsingHeader::new(texts.get("Draws"))
+                    .default_open(true)
+                    .show(ui, |ui| {
+                        egui::Grid::new("scene_stats_draws").show(ui, |ui| {
+                            for (name, count) in &stats.draw_counts {
+                                ui.label(name);
+                                ui.label(count.to_string());
+                                ui.end_row();
+                            }
+                        });
+                    });
+            });
+    }
+
+    fn key_bindings_window(
+        &mut self,
+        ctx: &egui::Context,
+        local_data: &mut LocalData,
+        texts: &Texts,
+    ) {
+        if !self.show_key_bindings_window {
+            self.changing_key_binding = None;
+        }
+        if let Some(index) = self.changing_key_binding {
+            // the next pressed key with modifiers becomes the new key binding, escape cancels changing
+            let pressed = ctx.input(|input| {
+                input.events.iter().find_map(|event| match event {
+                    egui::Event::Key {
+                        key,
+                        pressed: true,
+                        modifiers,
+                        ..
+                    } => Some((*key, *modifiers)),
+                    _ => None,
+                })
+            });
+            if let Some((key, modifiers)) = pressed {
+                ctx.input_mut(|input| input.consume_key(modifiers, key));
+                self.changing_key_binding = None;
+                if key != egui::Key::Escape {
+                    let (name, key_binding) = local_data
+                        .key_bindings
+                        .iter_mut()
+                        .into_iter()
+                        .nth(index)
+                        .unwrap();
+                    *key_binding = KeyBinding::new(modifiers, key);
+                    log::info!(
+                        "KeyBindings: {name} is changed to {}",
+                        key_binding.format(ctx)
+                    );
+                    local_data.save();
+                }
+            }
+        }
+        egui::Window::new(texts.get("Key Bindings"))
+            .open(&mut self.show_key_bindings_window)
+            .show(ctx, |ui| {
+                egui::Grid::new("key_bindings").show(ui, |ui| {
+                    for (i, (name, key_binding)) in
+                        local_data.key_bindings.iter_mut().into_iter().enumerate()
+                    {
+                        ui.label(texts.get(name));
+                        let text = if self.changing_key_binding == Some(i) {
+                            texts.get("Press a key...").to_string()
+                        } else {
+                            key_binding.format(ctx)
+                        };
+                        if ui.button(text).clicked() {
+                            self.changing_key_binding = Some(i);
+                        }
+                        ui.end_row();
+                    }
+                });
+                if ui.button(texts.get("Restore Defaults")).clicked() {
+                    log::info!("KeyBindings: restore defaults");
+                    local_data.key_bindings = KeyBindings::default();
+                    self.changing_key_binding = None;
+                    local_data.save();
+                }
+            });
+    }
+}
+
+/// The menu actions which may discard unsaved changes, see [MenuBar::request_menu_action].
+enum MenuAction {
+    CloseProject,
+    NewScene,
+    /// Load the scene in this path, which is relative to the asset directory.
+    LoadScene(PathBuf),
 }
 
 struct FpsCounter {
@@ -1,3 +1,4 @@
+mod confirm_dialog;
 mod data_window;
 mod image_window;
 mod menu_bar;
@@ -12,7 +13,8 @@ use menu_bar::MenuBar;
 use shipyard::EntityId;
 use std::path::PathBuf;
 use steel_common::{
-    app::{App, Command, WindowIndex},
+    app::{App, Command, CommandMut, WindowIndex},
+    asset::AssetId,
     camera::{CameraSettings, OrthographicCameraSize, SceneCamera},
     data::{Value, WorldData},
 };
@@ -98,8 +100,20 @@ impl Editor {
                 scene_camera,
             );
 
+            if let Some(app) = project.app() {
+                self.data_window
+                    .delete_entity_dialog(&ctx, app, local_data, &self.texts);
+            }
+
             if project.is_compiled() {
-                self.update_editor_window(&ctx, project, world_data, input, scene_camera);
+                self.update_editor_window(
+                    &ctx,
+                    project,
+                    world_data,
+                    local_data,
+                    input,
+                    scene_camera,
+                );
             } else if project.is_open() {
                 Self::compile_error_dialog(&ctx, &self.texts);
             }
@@ -131,23 +145,28 @@ impl Editor {
                                             project,
                                             asset_dir.as_ref().expect("project.asset_dir() must be some when project.app() is some"),
                                             &self.texts,
+                                            scene_camera,
+                                            local_data,
                                         );
                                     }
                                 }
                             }
                             "Entity" => {
                                 if let Some(world_data) = world_data {
+                                    let transform_2d =
+                                        project.prefs().is_some_and(|prefs| prefs.transform_2d);
                                     if let Some(app) = project.app() {
-                                        if let Some(entity_data) = world_data
+                                        if world_data
                                             .entities
-                                            .get_mut(&self.data_window.selected_entity())
+                                            .contains_key(&self.data_window.inspected_entity())
                                         {
                                             self.data_window.entity_view(
                                                 ui,
-                                                entity_data,
+                                                &mut world_data.entities,
                                                 app,
                                                 asset_dir.as_ref().expect("project.asset_dir() must be some when project.app() is some"),
                                                 &self.texts,
+                                                transform_2d,
                                             );
                                         }
                                     }
@@ -163,12 +182,12 @@ impl Editor {
                                     if let Some(app) = project.app() {
                                         if let Some(unique_data) = world_data
                                             .uniques
-                                            .get_mut(self.data_window.selected_unique())
+                                            .get_mut(self.data_window.inspected_unique())
                                         {
-                                            self.data_window.data_view(
+                                            self.data_window.unique_view(
                                                 ui,
-                                                self.data_window.selected_unique(),
                                                 unique_data,
+                                                &world_data.entities,
                                                 app,
                                                 asset_dir.as_ref().expect("project.asset_dir() must be some when project.app() is some"),
                                                 &self.texts,
@@ -200,15 +219,24 @@ impl Editor {
         ctx: &egui::Context,
         project: &mut Project,
         world_data: &mut Option<WorldData>,
+        local_data: &LocalData,
         input: &WinitInputHelper,
         scene_camera: &mut SceneCamera,
     ) {
         if self.scene_focus() {
-            self.update_scene_camera(ctx, input, scene_camera);
+            self.update_scene_camera(ctx, local_data, input, scene_camera);
+            let spawn_prefab = self
+                .editor_state
+                .spawn_prefab
+                .filter(|_| project.is_running());
             if let Some(app) = project.app() {
-                self.click_entity(ctx, app, input);
-                if let Some(world_data) = world_data {
-                    self.drag_entity(world_data, input, scene_camera);
+                if let Some(prefab) = spawn_prefab {
+                    self.spawn_prefab(ctx, app, input, scene_camera, prefab);
+                } else {
+                    self.click_entity(ctx, app, input);
+                    if let Some(world_data) = world_data {
+                        self.drag_entity(world_data, input, scene_camera);
+                    }
                 }
             }
         }
@@ -217,10 +245,11 @@ impl Editor {
     fn update_scene_camera(
         &self,
         ctx: &egui::Context,
+        local_data: &LocalData,
         input: &WinitInputHelper,
         scene_camera: &mut SceneCamera,
     ) {
-        if input.key_pressed(VirtualKeyCode::Home) {
+        if local_data.key_bindings.reset_scene_camera.pressed(ctx) {
             scene_camera.reset();
         }
 
@@ -254,24 +283,7 @@ impl Editor {
                 }
 
                 if input.mouse_held(1) {
-                    let screen_to_world = match size {
-                        OrthographicCameraSize::FixedWidth => {
-                            *width / self.scene_window.pixel().x as f32
-                        }
-                        OrthographicCameraSize::FixedHeight => {
-                            *height / self.scene_window.pixel().y as f32
-                        }
-                        OrthographicCameraSize::MinWidthHeight => {
-                            if *width / *height
-                                > self.scene_window.pixel().x as f32
-                                    / self.scene_window.pixel().y as f32
-                            {
-                                *width / self.scene_window.pixel().x as f32
-                            } else {
-                                *height / self.scene_window.pixel().y as f32
-                            }
-                        }
-                    };
+                    let screen_to_world = self.screen_to_world(*width, *height, *size);
                     let mouse_diff = input.mouse_diff();
                     scene_camera.position.x -= mouse_diff.0 * screen_to_world;
                     scene_camera.position.y += mouse_diff.1 * screen_to_world;
@@ -292,12 +304,7 @@ impl Editor {
                     ctx.set_cursor_icon(egui::CursorIcon::Default);
                 }
 
-                let rotation = scene_camera.rotation.to_scaled_axis(); // x: pitch, y: yaw, z: roll
-                let direction = Vec3::new(
-                    rotation.y.sin() * rotation.x.cos(),
-                    rotation.x.sin(),
-                    -rotation.y.cos() * rotation.x.cos(),
-                );
+                let direction = scene_camera.direction();
                 let right = direction.cross(Vec3::Y).normalize();
                 let up = right.cross(direction).normalize();
                 if input.key_held(VirtualKeyCode::A) || input.key_held(VirtualKeyCode::Left) {
@@ -369,24 +376,7 @@ impl Editor {
                 {
                     if let Some(data) = entity_data.components.get_mut("Transform") {
                         if let Some(Value::Vec3(position)) = data.values.get_mut("position") {
-                            let screen_to_world = match size {
-                                OrthographicCameraSize::FixedWidth => {
-                                    width / self.scene_window.pixel().x as f32
-                                }
-                                OrthographicCameraSize::FixedHeight => {
-                                    height / self.scene_window.pixel().y as f32
-                                }
-                                OrthographicCameraSize::MinWidthHeight => {
-                                    if width / height
-                                        > self.scene_window.pixel().x as f32
-                                            / self.scene_window.pixel().y as f32
-                                    {
-                                        width / self.scene_window.pixel().x as f32
-                                    } else {
-                                        height / self.scene_window.pixel().y as f32
-                                    }
-                                }
-                            };
+                            let screen_to_world = self.screen_to_world(width, height, size);
                             let mouse_diff = input.mouse_diff();
                             position.x += mouse_diff.0 * screen_to_world;
                             position.y -= mouse_diff.1 * screen_to_world;
@@ -397,6 +387,80 @@ impl Editor {
         }
     }
 
+    /// Create an instance of prefab at the clicked position in scene window, which is used to quickly test
+    /// gameplay while running. The instances are discarded when running stops, since the world is restored.
+    fn spawn_prefab(
+        &mut self,
+        ctx: &egui::Context,
+        app: &mut Box<dyn App>,
+        input: &WinitInputHelper,
+        scene_camera: &SceneCamera,
+        prefab: AssetId,
+    ) {
+        if input.mouse_pressed(0) {
+            if let Some((x, y)) = input.mouse() {
+                let x = x - self.scene_window.position().x * ctx.pixels_per_point();
+                let y = y - self.scene_window.position().y * ctx.pixels_per_point();
+                let screen_position = UVec2::new(x as u32, y as u32);
+                let mut eid = EntityId::dead();
+                let mut position = None;
+                app.command(Command::GetEntityAndPositionAtScreen(
+                    WindowIndex::SCENE,
+                    screen_position,
+                    &mut eid,
+                    &mut position,
+                ));
+                // if nothing is clicked, spawn at z = 0 in 2D or in front of scene camera in 3D
+                let position = position.unwrap_or_else(|| match scene_camera.settings {
+                    CameraSettings::Orthographic {
+                        width,
+                        height,
+                        size,
+                        ..
+                    } => {
+                        let screen_to_world = self.screen_to_world(width, height, size);
+                        let offset =
+                            screen_position.as_vec2() - self.scene_window.pixel().as_vec2() / 2.0;
+                        Vec3::new(
+                            scene_camera.position.x + offset.x * screen_to_world,
+                            scene_camera.position.y - offset.y * screen_to_world,
+                            0.0,
+                        )
+                    }
+                    CameraSettings::Perspective { .. } => scene_camera.focus_point(),
+                });
+                let mut root = EntityId::dead();
+                app.command_mut(CommandMut::InstantiatePrefab(
+                    prefab,
+                    position,
+                    Quat::IDENTITY,
+                    Vec3::ONE,
+                    &mut root,
+                ));
+                if root != EntityId::dead() {
+                    log::info!("Spawn prefab {prefab:?} at {position}");
+                    self.data_window.set_selected_entity(root);
+                }
+            }
+        }
+    }
+
+    /// Get the world length of one pixel in scene window for an orthographic scene camera.
+    fn screen_to_world(&self, width: f32, height: f32, size: OrthographicCameraSize) -> f32 {
+        let pixel = self.scene_window.pixel().as_vec2();
+        match size {
+            OrthographicCameraSize::FixedWidth => width / pixel.x,
+            OrthographicCameraSize::FixedHeight => height / pixel.y,
+            OrthographicCameraSize::MinWidthHeight => {
+                if width / height > pixel.x / pixel.y {
+                    width / pixel.x
+                } else {
+                    height / pixel.y
+                }
+            }
+        }
+    }
+
     fn scene_focus(&self) -> bool {
         self.editor_state
             .focused_tab
@@ -424,12 +488,23 @@ impl Editor {
     pub fn game_window(&self) -> &ImageWindow {
         &self.game_window
     }
+
+    /// Get the entity to draw alone in scene window, None if solo render is off or no entity is selected.
+    pub fn solo_entity(&self) -> Option<EntityId> {
+        let selected_entity = self.data_window.selected_entity();
+        (self.editor_state.solo_render && selected_entity != EntityId::dead())
+            .then_some(selected_entity)
+    }
 }
 
 struct EditorState {
     focused_tab: Option<String>,
     project_path: PathBuf,
     pressed_entity: EntityId,
+    /// The prefab to spawn by clicking in scene window while running, see [Editor::spawn_prefab].
+    spawn_prefab: Option<AssetId>,
+    /// If true, only the selected entity and its descendants are drawn in scene window, see [Editor::solo_entity].
+    solo_render: bool,
 }
 
 impl EditorState {
@@ -438,6 +513,8 @@ impl EditorState {
             focused_tab: None,
             project_path: local_data.last_open_project_path.clone(),
             pressed_entity: EntityId::dead(),
+            spawn_prefab: None,
+            solo_render: false,
         }
     }
 }
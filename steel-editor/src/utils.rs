@@ -45,6 +45,31 @@ pub struct LocalData {
     pub last_open_project_path: PathBuf,
     pub open_last_project_on_start: bool,
     pub scene_asset_and_data: Option<(Option<AssetId>, SceneData)>,
+    /// True if the scene in scene_asset_and_data has unsaved changes.
+    #[serde(default)]
+    pub scene_dirty: bool,
+    /// The number of samples per pixel for multisample anti-aliasing of scene window and game window.
+    #[serde(default = "LocalData::default_msaa_samples")]
+    pub msaa_samples: u32,
+    /// Compile the project dynlib in release mode for running optimized code in editor.
+    #[serde(default)]
+    pub release_build: bool,
+    /// The keyboard shortcuts of editor actions.
+    #[serde(default)]
+    pub key_bindings: KeyBindings,
+    /// Do destructive actions like deleting entity immediately without asking for confirmation.
+    #[serde(default)]
+    pub skip_confirm_dialog: bool,
+    /// The zoom factor of editor ui, which can be changed in Menu->Ui.
+    #[serde(default = "LocalData::default_zoom_factor")]
+    pub zoom_factor: f32,
+    /// The visuals theme of editor ui.
+    #[serde(default)]
+    pub theme: Theme,
+    /// The font file used by editor ui before the default egui fonts, for example a font which supports CJK characters.
+    /// None means using the bundled CJK font if the "cjk-font" feature is enabled, see [editor_fonts].
+    #[serde(default)]
+    pub font_path: Option<PathBuf>,
 }
 
 impl LocalData {
@@ -65,11 +90,27 @@ impl LocalData {
                     last_open_project_path,
                     open_last_project_on_start: false,
                     scene_asset_and_data: None,
+                    scene_dirty: false,
+                    msaa_samples: Self::default_msaa_samples(),
+                    release_build: false,
+                    key_bindings: KeyBindings::default(),
+                    skip_confirm_dialog: false,
+                    zoom_factor: Self::default_zoom_factor(),
+                    theme: Theme::default(),
+                    font_path: None,
                 }
             }
         }
     }
 
+    fn default_msaa_samples() -> u32 {
+        4
+    }
+
+    fn default_zoom_factor() -> f32 {
+        1.0
+    }
+
     pub fn save(&self) {
         if let Some(error) = save_to_file(self, Self::PATH).err() {
             log::warn!("Failed to save LocalData, error={error}");
@@ -77,6 +118,168 @@ impl LocalData {
     }
 }
 
+/// The editor preferences of a project, which are stored in the project directory
+/// so that a project opens with its last tool configuration.
+#[derive(Serialize, Deserialize, Default)]
+pub struct ProjectPrefs {
+    /// Edit Transform component in 2D mode: position and scale as xy, rotation as a single angle.
+    #[serde(default)]
+    pub transform_2d: bool,
+    /// Switch to the Game window when starting running.
+    #[serde(default)]
+    pub switch_to_game_window_on_start: bool,
+}
+
+impl ProjectPrefs {
+    const FILE: &'static str = "editor-prefs.json";
+
+    /// Load the preferences of the project in project_path, or default preferences if there is no preferences file.
+    pub fn load(project_path: impl AsRef<Path>) -> Self {
+        let path = project_path.as_ref().join(Self::FILE);
+        if !path.exists() {
+            return ProjectPrefs::default();
+        }
+        load_from_file(&path).unwrap_or_else(|error| {
+            log::warn!(
+                "Failed to load ProjectPrefs from {}, error={error}",
+                path.display()
+            );
+            ProjectPrefs::default()
+        })
+    }
+
+    /// Save the preferences into the project in project_path.
+    pub fn save(&self, project_path: impl AsRef<Path>) {
+        let path = project_path.as_ref().join(Self::FILE);
+        if let Some(error) = save_to_file(self, &path).err() {
+            log::warn!(
+                "Failed to save ProjectPrefs to {}, error={error}",
+                path.display()
+            );
+        }
+    }
+}
+
+/// The visuals theme of editor ui.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Debug, Default)]
+pub enum Theme {
+    #[default]
+    Dark,
+    Light,
+}
+
+impl Theme {
+    /// Get the egui visuals of this theme.
+    pub fn visuals(&self) -> egui::Visuals {
+        match self {
+            Theme::Dark => egui::Visuals::dark(),
+            Theme::Light => egui::Visuals::light(),
+        }
+    }
+}
+
+/// Create the font definitions of editor ui. The font at font_path is added before the default egui fonts,
+/// if font_path is None or fails to load, the bundled CJK font is added instead if the "cjk-font" feature is enabled.
+pub fn editor_fonts(font_path: Option<&Path>) -> egui::FontDefinitions {
+    const EDITOR_FONT: &str = "editor-font";
+    let mut fonts = egui::FontDefinitions::default();
+    let font_data = font_path
+        .and_then(|font_path| match std::fs::read(font_path) {
+            Ok(bytes) => Some(egui::FontData::from_owned(bytes)),
+            Err(e) => {
+                log::warn!("Failed to load font {}, error={e}", font_path.display());
+                None
+            }
+        })
+        .or_else(bundled_font);
+    if let Some(font_data) = font_data {
+        fonts.font_data.insert(EDITOR_FONT.to_owned(), font_data);
+        fonts
+            .families
+            .get_mut(&egui::FontFamily::Proportional)
+            .unwrap()
+            .insert(0, EDITOR_FONT.to_owned());
+        fonts
+            .families
+            .get_mut(&egui::FontFamily::Monospace)
+            .unwrap()
+            .push(EDITOR_FONT.to_owned());
+    }
+    fonts
+}
+
+/// The bundled font to support displaying chinese in egui, which is large so that it is only included with "cjk-font" feature.
+#[cfg(feature = "cjk-font")]
+fn bundled_font() -> Option<egui::FontData> {
+    Some(egui::FontData::from_static(include_bytes!(
+        "../fonts/msyh.ttc"
+    )))
+}
+
+#[cfg(not(feature = "cjk-font"))]
+fn bundled_font() -> Option<egui::FontData> {
+    None
+}
+
+/// A keyboard shortcut which is a key with modifiers.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub struct KeyBinding {
+    pub modifiers: egui::Modifiers,
+    pub key: egui::Key,
+}
+
+impl KeyBinding {
+    pub const fn new(modifiers: egui::Modifiers, key: egui::Key) -> Self {
+        KeyBinding { modifiers, key }
+    }
+
+    /// Returns true and consumes the key press if this key binding is pressed in this frame.
+    pub fn pressed(&self, ctx: &egui::Context) -> bool {
+        ctx.input_mut(|input| input.consume_key(self.modifiers, self.key))
+    }
+
+    /// Format this key binding for displaying in ui, for example "Ctrl+Z".
+    pub fn format(&self, ctx: &egui::Context) -> String {
+        ctx.format_shortcut(&egui::KeyboardShortcut::new(self.modifiers, self.key))
+    }
+}
+
+/// The keyboard shortcuts of editor actions, which can be changed in Menu->Ui->Key Bindings.
+#[derive(Serialize, Deserialize, Clone, PartialEq)]
+#[serde(default)]
+pub struct KeyBindings {
+    pub start_running: KeyBinding,
+    pub reset_scene_camera: KeyBinding,
+    pub toggle_ray_tracing: KeyBinding,
+    pub move_entity_up: KeyBinding,
+    pub move_entity_down: KeyBinding,
+}
+
+impl Default for KeyBindings {
+    fn default() -> Self {
+        KeyBindings {
+            start_running: KeyBinding::new(egui::Modifiers::NONE, egui::Key::F5),
+            reset_scene_camera: KeyBinding::new(egui::Modifiers::NONE, egui::Key::Home),
+            toggle_ray_tracing: KeyBinding::new(egui::Modifiers::NONE, egui::Key::F7),
+            move_entity_up: KeyBinding::new(egui::Modifiers::ALT, egui::Key::ArrowUp),
+            move_entity_down: KeyBinding::new(egui::Modifiers::ALT, egui::Key::ArrowDown),
+        }
+    }
+}
+
+impl KeyBindings {
+    /// Get all key bindings with their action names, which are also the keys of [crate::locale::Texts].
+    pub fn iter_mut(&mut self) -> [(&'static str, &mut KeyBinding); 5] {
+        [
+            ("Start Running", &mut self.start_running),
+            ("Reset Scene Camera", &mut self.reset_scene_camera),
+            ("Toggle Ray Tracing", &mut self.toggle_ray_tracing),
+            ("Move Entity Up", &mut self.move_entity_up),
+            ("Move Entity Down", &mut self.move_entity_down),
+        ]
+    }
+}
+
 /// The general error happened in steel editor.
 #[derive(Debug)]
 pub struct EditorError {
@@ -0,0 +1,287 @@
+use crate::{edit::Edit, time::Time, transform::Transform};
+use glam::{Quat, Vec3};
+use shipyard::{Component, Get, IntoIter, IntoWithId, UniqueView, ViewMut};
+use steel_common::data::{Data, Limit, Value};
+
+/// The interpolation mode between two keyframes of [Animator].
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum Interpolation {
+    /// Keep the values of previous keyframe until next keyframe is reached.
+    Step,
+    /// Interpolate values linearly.
+    #[default]
+    Linear,
+    /// Interpolate values with smoothstep, which eases in and out at each keyframe.
+    Smooth,
+}
+
+impl Interpolation {
+    /// Helper function for [Limit::Int32Enum].
+    pub fn to_i32(&self) -> i32 {
+        match self {
+            Interpolation::Step => 0,
+            Interpolation::Linear => 1,
+            Interpolation::Smooth => 2,
+        }
+    }
+
+    /// Helper function for [Limit::Int32Enum].
+    pub fn from_i32(i: i32) -> Self {
+        match i {
+            0 => Interpolation::Step,
+            1 => Interpolation::Linear,
+            2 => Interpolation::Smooth,
+            _ => Self::default(),
+        }
+    }
+
+    /// Helper function for [Limit::Int32Enum].
+    pub fn enum_vector() -> Vec<(i32, String)> {
+        vec![
+            (0, "Step".into()),
+            (1, "Linear".into()),
+            (2, "Smooth".into()),
+        ]
+    }
+}
+
+/// What [Animator] does after playing to the last keyframe.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum LoopMode {
+    /// Stop at the last keyframe.
+    Once,
+    /// Jump back to the first keyframe.
+    #[default]
+    Loop,
+    /// Play backward to the first keyframe, then forward again.
+    PingPong,
+}
+
+impl LoopMode {
+    /// Helper function for [Limit::Int32Enum].
+    pub fn to_i32(&self) -> i32 {
+        match self {
+            LoopMode::Once => 0,
+            LoopMode::Loop => 1,
+            LoopMode::PingPong => 2,
+        }
+    }
+
+    /// Helper function for [Limit::Int32Enum].
+    pub fn from_i32(i: i32) -> Self {
+        match i {
+            0 => LoopMode::Once,
+            1 => LoopMode::Loop,
+            2 => LoopMode::PingPong,
+            _ => Self::default(),
+        }
+    }
+
+    /// Helper function for [Limit::Int32Enum].
+    pub fn enum_vector() -> Vec<(i32, String)> {
+        vec![
+            (0, "Once".into()),
+            (1, "Loop".into()),
+            (2, "PingPong".into()),
+        ]
+    }
+}
+
+/// A keyframe of [Animator], which is the local transform values of the entity at time.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Keyframe {
+    pub time: f32,
+    pub position: Vec3,
+    pub rotation: Quat,
+    pub scale: Vec3,
+}
+
+impl Default for Keyframe {
+    fn default() -> Self {
+        Keyframe {
+            time: 0.0,
+            position: Vec3::ZERO,
+            rotation: Quat::IDENTITY,
+            scale: Vec3::ONE,
+        }
+    }
+}
+
+/// The Animator component plays keyframe animation on the [Transform] of this entity.
+/// In steel-editor, the number of keyframes is changed by editing the "times" list,
+/// then the transform values of each keyframe can be edited.
+#[derive(Component, Debug)]
+pub struct Animator {
+    /// The keyframes, which should be sorted by time. They are sorted when set by [Edit::set_data].
+    pub keyframes: Vec<Keyframe>,
+    pub interpolation: Interpolation,
+    pub loop_mode: LoopMode,
+    /// Whether the animation time is advancing.
+    pub playing: bool,
+    /// The playback speed multiplier.
+    pub speed: f32,
+    /// The current animation time in seconds.
+    pub time: f32,
+}
+
+impl Default for Animator {
+    fn default() -> Self {
+        Animator {
+            keyframes: Vec::new(),
+            interpolation: Interpolation::default(),
+            loop_mode: LoopMode::default(),
+            playing: true,
+            speed: 1.0,
+            time: 0.0,
+        }
+    }
+}
+
+impl Animator {
+    /// Advance the animation time by delta seconds according to [Animator::speed] and [Animator::loop_mode].
+    pub fn advance(&mut self, delta: f32) {
+        if !self.playing || self.keyframes.is_empty() {
+            return;
+        }
+        let start = self.keyframes[0].time;
+        let duration = self.keyframes[self.keyframes.len() - 1].time - start;
+        self.time += delta * self.speed;
+        if duration <= 0.0 {
+            self.time = start;
+            return;
+        }
+        self.time = match self.loop_mode {
+            LoopMode::Once => self.time.clamp(start, start + duration),
+            LoopMode::Loop => start + (self.time - start).rem_euclid(duration),
+            // keep time in [start, start + 2 * duration), the second half is played backward in Animator::sample
+            LoopMode::PingPong => start + (self.time - start).rem_euclid(2.0 * duration),
+        };
+    }
+
+    /// Sample the transform values at current animation time.
+    /// Returns None if there is no keyframe.
+    pub fn sample(&self) -> Option<Keyframe> {
+        let first = self.keyframes.first()?;
+        let last = self.keyframes.last()?;
+        let mut time = self.time;
+        if self.loop_mode == LoopMode::PingPong && time > last.time {
+            time = 2.0 * last.time - time;
+        }
+        if time <= first.time {
+            return Some(*first);
+        }
+        if time >= last.time {
+            return Some(*last);
+        }
+        let next = self.keyframes.partition_point(|k| k.time <= time);
+        let (a, b) = (&self.keyframes[next - 1], &self.keyframes[next]);
+        let mut s = if b.time > a.time {
+            (time - a.time) / (b.time - a.time)
+        } else {
+            0.0
+        };
+        s = match self.interpolation {
+            Interpolation::Step => 0.0,
+            Interpolation::Linear => s,
+            Interpolation::Smooth => s * s * (3.0 - 2.0 * s),
+        };
+        Some(Keyframe {
+            time,
+            position: a.position.lerp(b.position, s),
+            rotation: a.rotation.slerp(b.rotation, s),
+            scale: a.scale.lerp(b.scale, s),
+        })
+    }
+}
+
+impl Edit for Animator {
+    fn name() -> &'static str {
+        "Animator"
+    }
+
+    fn get_data(&self) -> Data {
+        let mut data = Data::new()
+            .insert_with_limit(
+                "interpolation",
+                Value::Int32(self.interpolation.to_i32()),
+                Limit::Int32Enum(Interpolation::enum_vector()),
+            )
+            .insert_with_limit(
+                "loop_mode",
+                Value::Int32(self.loop_mode.to_i32()),
+                Limit::Int32Enum(LoopMode::enum_vector()),
+            )
+            .insert("playing", Value::Bool(self.playing))
+            .insert("speed", Value::Float32(self.speed))
+            .insert("time", Value::Float32(self.time))
+            .insert(
+                "times",
+                Value::VecFloat32(self.keyframes.iter().map(|k| k.time).collect()),
+            );
+        for (i, keyframe) in self.keyframes.iter().enumerate() {
+            data.add_value(format!("position_{i}"), Value::Vec3(keyframe.position));
+            data.add_value_with_limit(
+                format!("rotation_{i}"),
+                Value::Vec3(keyframe.rotation.to_scaled_axis()),
+                Limit::Float32Rotation,
+            );
+            data.add_value(format!("scale_{i}"), Value::Vec3(keyframe.scale));
+        }
+        data
+    }
+
+    fn set_data(&mut self, data: &Data) {
+        if let Some(Value::Int32(v)) = data.get("interpolation") {
+            self.interpolation = Interpolation::from_i32(*v);
+        }
+        if let Some(Value::Int32(v)) = data.get("loop_mode") {
+            self.loop_mode = LoopMode::from_i32(*v);
+        }
+        if let Some(Value::Bool(v)) = data.get("playing") {
+            self.playing = *v;
+        }
+        if let Some(Value::Float32(v)) = data.get("speed") {
+            self.speed = *v;
+        }
+        if let Some(Value::Float32(v)) = data.get("time") {
+            self.time = *v;
+        }
+        if let Some(Value::VecFloat32(times)) = data.get("times") {
+            // new keyframes copy the transform values of the last keyframe
+            let last = self.keyframes.last().copied().unwrap_or_default();
+            self.keyframes.resize(times.len(), last);
+            for (keyframe, time) in self.keyframes.iter_mut().zip(times) {
+                keyframe.time = *time;
+            }
+        }
+        for (i, keyframe) in self.keyframes.iter_mut().enumerate() {
+            if let Some(Value::Vec3(v)) = data.get(format!("position_{i}")) {
+                keyframe.position = *v;
+            }
+            if let Some(Value::Vec3(v)) = data.get(format!("rotation_{i}")) {
+                keyframe.rotation = Quat::from_scaled_axis(*v);
+            }
+            if let Some(Value::Vec3(v)) = data.get(format!("scale_{i}")) {
+                keyframe.scale = *v;
+            }
+        }
+        // sort after all values are set because values are matched to keyframes by index in data
+        self.keyframes.sort_by(|a, b| a.time.total_cmp(&b.time));
+    }
+}
+
+/// Advance all [Animator] components and write the sampled values into their [Transform] components.
+pub fn animator_update_system(
+    mut animators: ViewMut<Animator>,
+    mut transforms: ViewMut<Transform>,
+    time: UniqueView<Time>,
+) {
+    for (e, animator) in (&mut animators).iter().with_id() {
+        animator.advance(time.delta());
+        if let (Some(keyframe), Ok(mut transform)) = (animator.sample(), (&mut transforms).get(e)) {
+            transform.position = keyframe.position;
+            transform.rotation = keyframe.rotation;
+            transform.scale = keyframe.scale;
+        }
+    }
+}
@@ -1,36 +1,48 @@
 pub use steel_common::app::*;
 
 use crate::{
+    animation::Animator,
     asset::AssetManager,
-    camera::{Camera, CameraInfo},
+    behavior::{Behavior, BehaviorFn, Behaviors},
+    camera::{Camera, CameraConfiner, CameraFollow, CameraInfo},
     data::{
-        ComponentRegistry, ComponentRegistryExt, CreatePrefabParam, EntitiesDataExt,
-        LoadPrefabParam, Prefab, PrefabAssets, UniqueRegistry,
+        ComponentRegistry, ComponentRegistryExt, CreatePrefabParam, Data, EntitiesDataExt,
+        LoadPrefabParam, Prefab, PrefabAssets, UniqueFn, UniqueRegistry,
     },
     edit::Edit,
     hierarchy::{Children, Hierarchy, Parent},
     input::Input,
+    interpolation::{InterpolateTransform, TransformInterpolation},
     name::Name,
+    physics2d::Physics2DManager,
     render::{
+        blend::BlendMode,
         canvas::{Canvas, GetEntityAtScreenParam},
         image::ImageAssets,
+        layer::{HideInGame, RenderLayers, SortingLayer, SortingLayers},
         model::ModelAssets,
         pipeline::raytracing::material::Material,
         renderer::Renderer,
         renderer2d::Renderer2D,
-        texture::TextureAssets,
+        shape3d::Shape3D,
+        texture::{TextureAssets, TextureTiling},
+        world_ui::WorldUi,
         FrameRenderInfo, RenderManager,
     },
     scene::SceneManager,
+    tag::{TagIndex, Tags},
     time::Time,
     transform::Transform,
     ui::EguiContext,
+    window::Window,
 };
+use indexmap::IndexMap;
 use shipyard::{
-    EntitiesView, IntoWorkloadSystem, Unique, UniqueView, UniqueViewMut, ViewMut, Workload, World,
+    EntitiesView, Get, IntoWorkloadSystem, Unique, UniqueView, UniqueViewMut, View, ViewMut,
+    Workload, World,
 };
-use steel_common::platform::Platform;
-use vulkano::{command_buffer::PrimaryCommandBufferAbstract, sync::GpuFuture};
+use steel_common::{data::WorldData, platform::Platform};
+use vulkano::sync::GpuFuture;
 
 /// SteelApp contains data and logic of a steel application.
 /// # Examples
@@ -113,6 +125,17 @@ pub struct SteelApp {
     post_update_workload_editor: Option<Workload>,
 
     draw_editor_workload: Option<Workload>,
+
+    scene_loaded_workload: Option<Workload>,
+    scene_unloaded_workload: Option<Workload>,
+
+    custom_schedules: IndexMap<&'static str, CustomSchedule>,
+
+    /// Whether the warning of drawing in fixed update steps is logged, see [SteelApp::run_update_steps].
+    fixed_update_drawing_warned: bool,
+
+    /// The logger settings used by steel-client, see [SteelApp::set_log_config].
+    log_config: LogConfig,
 }
 
 impl SteelApp {
@@ -131,18 +154,38 @@ impl SteelApp {
             pre_update_workload_editor: Some(Workload::new("pre_update_editor")),
             post_update_workload_editor: Some(Workload::new("post_update_editor")),
             draw_editor_workload: Some(Workload::new("draw_editor")),
+            scene_loaded_workload: Some(Workload::new("scene_loaded")),
+            scene_unloaded_workload: Some(Workload::new("scene_unloaded")),
+            custom_schedules: IndexMap::new(),
+            fixed_update_drawing_warned: false,
+            log_config: LogConfig::default(),
         }
         .register_component::<Name>()
+        .register_component::<Tags>()
         .register_component::<Prefab>()
         .register_component::<Parent>()
         .register_component::<Children>()
         .register_component::<Transform>()
         .register_component::<Camera>()
+        .register_component::<CameraFollow>()
+        .register_component::<CameraConfiner>()
+        .register_component::<Animator>()
+        .register_component::<Behavior>()
         .register_component::<Renderer>()
         .register_component::<Renderer2D>()
+        .register_component::<Shape3D>()
         .register_component::<Material>()
+        .register_component::<TextureTiling>()
+        .register_component::<BlendMode>()
+        .register_component::<RenderLayers>()
+        .register_component::<SortingLayer>()
+        .register_component::<HideInGame>()
+        .register_component::<InterpolateTransform>()
         .register_unique::<RenderManager>()
         .add_and_register_unique(Hierarchy::default())
+        .add_and_register_unique(SortingLayers::default())
+        .add_and_register_unique(TransformInterpolation::default())
+        // runtime-only uniques are not registered, so they are never saved into or loaded from scene files
         .add_unique(AssetManager::default())
         .add_unique(PrefabAssets::default())
         .add_unique(ImageAssets::default())
@@ -150,17 +193,30 @@ impl SteelApp {
         .add_unique(ModelAssets::default())
         .add_unique(CameraInfo::new())
         .add_unique(Canvas::default())
+        .add_unique(WorldUi::default())
         .add_unique(Input::new())
         .add_unique(Time::new())
+        .add_unique(Window::default())
+        .add_unique(TagIndex::default())
+        .add_unique(Behaviors::default())
         .add_system(
             Schedule::PreUpdate,
             crate::hierarchy::hierarchy_maintain_system,
         )
         .add_system(Schedule::PreUpdate, crate::time::time_maintain_system)
+        .add_system(Schedule::PreUpdate, crate::tag::tag_index_update_system)
+        .add_system(
+            Schedule::PreUpdate,
+            crate::render::layer::sorting_layers_maintain_system,
+        )
         .add_system(
             Schedule::PreUpdate,
             crate::render::canvas::canvas_clear_system,
         )
+        .add_system(Schedule::Update, crate::camera::camera_follow_system)
+        .add_system(Schedule::Update, crate::animation::animator_update_system)
+        .add_system(Schedule::Update, crate::behavior::behavior_update_system)
+        .add_system(Schedule::PostUpdate, crate::camera::camera_confiner_system)
         .add_system(Schedule::PostUpdate, crate::camera::camera_maintain_system)
         .add_system(
             Schedule::PostUpdate,
@@ -170,6 +226,14 @@ impl SteelApp {
             Schedule::PostUpdate,
             crate::render::renderer::renderer_to_canvas_system,
         )
+        .add_system(
+            Schedule::PostUpdate,
+            crate::render::shape3d::shape3d_to_canvas_system,
+        )
+        .add_system(
+            Schedule::PostUpdate,
+            crate::render::world_ui::world_ui_to_canvas_system,
+        )
     }
 
     /// Box self.
@@ -177,6 +241,12 @@ impl SteelApp {
         Box::new(self)
     }
 
+    /// Set the log level and log file of steel-client, see [LogConfig].
+    pub fn set_log_config(mut self, log_config: LogConfig) -> Self {
+        self.log_config = log_config;
+        self
+    }
+
     /// Register a component type so that this component can be edited in steel-editor.
     /// Trait bounds <C: ComponentRegistryExt> equals to <C: Component + Edit + Default + Send + Sync>.
     pub fn register_component<C: ComponentRegistryExt>(mut self) -> Self {
@@ -184,6 +254,13 @@ impl SteelApp {
         self
     }
 
+    /// Register a dynamic component named name which is described by schema, so that this component can be
+    /// created and edited in steel-editor and saved in scenes without a rust type. See [crate::dynamic].
+    pub fn register_dynamic_component(mut self, name: impl Into<String>, schema: Data) -> Self {
+        self.component_registry.register_dynamic(name, schema);
+        self
+    }
+
     /// Register a unique type so that this unique can be edited in steel-editor.
     pub fn register_unique<U: Unique + Edit + Send + Sync>(mut self) -> Self {
         self.unique_registry.register::<U>();
@@ -198,8 +275,36 @@ impl SteelApp {
 
     /// Add a unique into ecs world, also register this unique type so that this unique can be edited in steel-editor.
     pub fn add_and_register_unique<U: Unique + Edit + Send + Sync>(mut self, unique: U) -> Self {
+        let default_data = unique.get_data();
         self.world.add_unique(unique);
         self.unique_registry.register::<U>();
+        self.unique_registry
+            .get_mut(U::name())
+            .unwrap()
+            .default_data = Some(default_data);
+        self
+    }
+
+    /// Mark a registered unique as transient, so that it is still editable in steel-editor,
+    /// but is not saved into scene files and is not loaded from scene files.
+    /// This is useful for uniques which only contain runtime state.
+    /// A transient unique is reset to its [Default] value when the scene is cleared by [SceneManager::clear_scene].
+    ///
+    /// All registered builtin uniques are persistent, they are saved into and loaded from scene files. When the scene is
+    /// cleared, [Hierarchy], [SortingLayers], [TransformInterpolation] and [crate::physics2d::Physics2DManager], which are
+    /// added by [SteelApp::add_and_register_unique], are reset to the data they are added with, while [RenderManager],
+    /// which is registered by [SteelApp::register_unique], keeps its current settings. Builtin uniques which only
+    /// contain runtime state, like [Canvas], [CameraInfo] and [WorldUi], are not registered, so they are never saved,
+    /// are not reset when the scene is cleared, and do not need to be marked.
+    pub fn mark_unique_transient<U: Unique + Edit + Default + Send + Sync>(mut self) -> Self {
+        let unique_fn = self.unique_registry.get_mut(U::name()).unwrap_or_else(|| {
+            panic!(
+                "SteelApp::mark_unique_transient: unique {} is not registered",
+                U::name()
+            )
+        });
+        unique_fn.persistent = false;
+        unique_fn.reset = Some(UniqueRegistry::reset_fn::<U>);
         self
     }
 
@@ -257,10 +362,164 @@ impl SteelApp {
                         .with_system(system),
                 );
             }
+            Schedule::SceneLoaded => {
+                self.scene_loaded_workload = Some(
+                    self.scene_loaded_workload
+                        .take()
+                        .unwrap()
+                        .with_system(system),
+                );
+            }
+            Schedule::SceneUnloaded => {
+                self.scene_unloaded_workload = Some(
+                    self.scene_unloaded_workload
+                        .take()
+                        .unwrap()
+                        .with_system(system),
+                );
+            }
+            Schedule::Custom(name) => {
+                let custom_schedule = self.custom_schedules.get_mut(name).unwrap_or_else(|| {
+                    panic!("SteelApp::add_system: custom schedule {name} is not added, call SteelApp::add_schedule first")
+                });
+                custom_schedule.workload =
+                    Some(custom_schedule.workload.take().unwrap().with_system(system));
+                custom_schedule.workload_editor = Some(
+                    custom_schedule
+                        .workload_editor
+                        .take()
+                        .unwrap()
+                        .with_system(system),
+                );
+            }
         }
         self
     }
 
+    /// Add a custom schedule which runs before or after another schedule, then you can use
+    /// [Schedule::Custom] with the same name to add systems into it. For example, an AI plugin
+    /// can add a schedule after [Schedule::Update] to run all AI systems after physics update.
+    /// A custom schedule is skipped in steel-editor when the schedule it is positioned to is skipped.
+    /// Custom schedules positioned to the same schedule run in the order they are added.
+    pub fn add_schedule(mut self, name: &'static str, position: SchedulePosition) -> Self {
+        let (SchedulePosition::Before(schedule) | SchedulePosition::After(schedule)) = position;
+        if let Schedule::Custom(other) = schedule {
+            if !self.custom_schedules.contains_key(other) {
+                panic!(
+                    "SteelApp::add_schedule: custom schedule {other} is not added before {name}"
+                );
+            }
+        }
+        if self.custom_schedules.contains_key(name) {
+            panic!("SteelApp::add_schedule: custom schedule {name} is already added");
+        }
+        self.custom_schedules.insert(
+            name,
+            CustomSchedule {
+                position,
+                workload: Some(Workload::new(name)),
+                workload_editor: Some(Workload::new(name)),
+            },
+        );
+        self
+    }
+
+    /// Take the workload of schedule with all custom schedules positioned to it.
+    /// If editor is true, take the workload which runs in steel-editor when the game is not running.
+    fn take_workload(&mut self, schedule: Schedule, editor: bool) -> Workload {
+        let mut workload = Workload::new(format!("{schedule:?}"));
+        if editor && schedule == Schedule::Update {
+            return workload;
+        }
+        for name in self.custom_schedule_names(SchedulePosition::Before(schedule)) {
+            workload = workload.append(&mut self.take_workload(Schedule::Custom(name), editor));
+        }
+        let mut schedule_workload = match (schedule, editor) {
+            (Schedule::PreInit, _) => self.pre_init_workload.take(),
+            (Schedule::Init, _) => self.init_workload.take(),
+            (Schedule::PostInit, _) => self.post_init_workload.take(),
+            (Schedule::PreUpdate, false) => self.pre_update_workload.take(),
+            (Schedule::PreUpdate, true) => self.pre_update_workload_editor.take(),
+            (Schedule::Update, _) => self.update_workload.take(),
+            (Schedule::PostUpdate, false) => self.post_update_workload.take(),
+            (Schedule::PostUpdate, true) => self.post_update_workload_editor.take(),
+            (Schedule::DrawEditor, _) => self.draw_editor_workload.take(),
+            (Schedule::SceneLoaded, _) => self.scene_loaded_workload.take(),
+            (Schedule::SceneUnloaded, _) => self.scene_unloaded_workload.take(),
+            (Schedule::Custom(name), false) => self.custom_schedules[name].workload.take(),
+            (Schedule::Custom(name), true) => self.custom_schedules[name].workload_editor.take(),
+        }
+        .unwrap();
+        workload = workload.append(&mut schedule_workload);
+        for name in self.custom_schedule_names(SchedulePosition::After(schedule)) {
+            workload = workload.append(&mut self.take_workload(Schedule::Custom(name), editor));
+        }
+        workload
+    }
+
+    /// Run [Schedule::Update] as many times as [Time] decides for this frame. If [Time::update_rate] is set,
+    /// drawing data added to [Canvas] and [WorldUi] during Update is dropped with a warning, because Update
+    /// runs zero or more times per frame at a fixed cadence, which makes it flicker or be drawn repeatedly.
+    fn run_update_steps(&mut self) {
+        let fixed = self
+            .world
+            .run(|time: UniqueView<Time>| time.update_rate > 0.0);
+        let (canvas, world_ui) = if fixed {
+            self.world.run(
+                |mut canvas: UniqueViewMut<Canvas>, mut world_ui: UniqueViewMut<WorldUi>| {
+                    (std::mem::take(&mut *canvas), std::mem::take(&mut *world_ui))
+                },
+            )
+        } else {
+            Default::default()
+        };
+        while self
+            .world
+            .run(|mut time: UniqueViewMut<Time>| time.next_update_step())
+        {
+            self.world.run_workload("update").unwrap();
+        }
+        if fixed {
+            let drawn = self.world.run(
+                |mut update_canvas: UniqueViewMut<Canvas>,
+                 mut update_world_ui: UniqueViewMut<WorldUi>| {
+                    let drawn = !update_canvas.is_empty() || !update_world_ui.is_empty();
+                    *update_canvas = canvas;
+                    *update_world_ui = world_ui;
+                    drawn
+                },
+            );
+            if drawn && !self.fixed_update_drawing_warned {
+                self.fixed_update_drawing_warned = true;
+                log::warn!("SteelApp::update: drawing to Canvas or WorldUi in Schedule::Update is dropped because \
+                    Time::update_rate is set, draw in Schedule::PostUpdate instead");
+            }
+        }
+    }
+
+    fn custom_schedule_names(&self, position: SchedulePosition) -> Vec<&'static str> {
+        self.custom_schedules
+            .iter()
+            .filter_map(|(name, custom_schedule)| {
+                (custom_schedule.position == position).then_some(*name)
+            })
+            .collect()
+    }
+
+    /// Register a behavior callback with name, so that entities with a [Behavior] component
+    /// which picks this name call callback every update. See [Behavior] for an example.
+    pub fn add_behavior(self, name: impl Into<String>, callback: BehaviorFn) -> Self {
+        self.world
+            .run(|mut behaviors: UniqueViewMut<Behaviors>| behaviors.add(name, callback));
+        self
+    }
+
+    /// Register a reload-sensitive static, see [crate::reload::register_reload_static] for more information.
+    pub fn add_reload_static(self, name: &'static str, reseed: fn()) -> Self {
+        crate::reload::register_reload_static(name, reseed);
+        self
+    }
+
     /// Add a plugin, see [Plugin] for more information.
     pub fn add_plugin(self, plugin: impl Plugin) -> Self {
         plugin.apply(self)
@@ -274,24 +533,43 @@ impl App for SteelApp {
             .add_unique(RenderManager::new(info.context, info.ray_tracing_supported));
         self.world.add_unique(SceneManager::new(info.scene));
         Workload::new("init")
-            .append(&mut self.pre_init_workload.take().unwrap())
-            .append(&mut self.init_workload.take().unwrap())
-            .append(&mut self.post_init_workload.take().unwrap())
+            .append(&mut self.take_workload(Schedule::PreInit, false))
+            .append(&mut self.take_workload(Schedule::Init, false))
+            .append(&mut self.take_workload(Schedule::PostInit, false))
+            .add_to_world(&self.world)
+            .unwrap();
+        // Update runs in its own workload so that it can run at Time::update_rate instead of every frame
+        Workload::new("pre_update")
+            .append(&mut self.take_workload(Schedule::PreUpdate, false))
             .add_to_world(&self.world)
             .unwrap();
-        Workload::new("update_all")
-            .append(&mut self.pre_update_workload.take().unwrap())
-            .append(&mut self.update_workload.take().unwrap())
-            .append(&mut self.post_update_workload.take().unwrap())
+        Workload::new("update")
+            .append(&mut self.take_workload(Schedule::Update, false))
+            .with_system(crate::interpolation::transform_interpolation_record_system)
+            .add_to_world(&self.world)
+            .unwrap();
+        Workload::new("post_update")
+            .with_system(crate::interpolation::transform_interpolation_blend_system)
+            .append(&mut self.take_workload(Schedule::PostUpdate, false))
+            .with_system(crate::interpolation::transform_interpolation_restore_system)
             .add_to_world(&self.world)
             .unwrap();
         Workload::new("update_editor")
-            .append(&mut self.pre_update_workload_editor.take().unwrap())
-            .append(&mut self.post_update_workload_editor.take().unwrap())
+            .append(&mut self.take_workload(Schedule::PreUpdate, true))
+            .append(&mut self.take_workload(Schedule::Update, true))
+            .append(&mut self.take_workload(Schedule::PostUpdate, true))
             .add_to_world(&self.world)
             .unwrap();
         Workload::new("draw_editor")
-            .append(&mut self.draw_editor_workload.take().unwrap())
+            .append(&mut self.take_workload(Schedule::DrawEditor, false))
+            .add_to_world(&self.world)
+            .unwrap();
+        Workload::new("scene_loaded")
+            .append(&mut self.take_workload(Schedule::SceneLoaded, false))
+            .add_to_world(&self.world)
+            .unwrap();
+        Workload::new("scene_unloaded")
+            .append(&mut self.take_workload(Schedule::SceneUnloaded, false))
             .add_to_world(&self.world)
             .unwrap();
 
@@ -301,39 +579,40 @@ impl App for SteelApp {
     fn update(&mut self, info: UpdateInfo) {
         self.world.add_unique(EguiContext::new(info.ctx.clone()));
 
+        PrefabAssets::maintain_system(&mut self.world, &self.component_registry);
         SceneManager::maintain_system(
             &mut self.world,
             &self.component_registry,
             &self.unique_registry,
         );
 
-        let workload = if info.update {
-            "update_all"
+        if info.update {
+            self.world.run_workload("pre_update").unwrap();
+            self.run_update_steps();
+            self.world.run_workload("post_update").unwrap();
         } else {
-            "update_editor"
-        };
-        self.world.run_workload(workload).unwrap();
+            self.world.run_workload("update_editor").unwrap();
+        }
 
         self.world.remove_unique::<EguiContext>().unwrap();
     }
 
-    fn draw(&mut self, mut info: DrawInfo) -> Box<dyn GpuFuture> {
+    fn draw(&mut self, info: DrawInfo) -> Box<dyn GpuFuture> {
         if let Some(editor) = &info.editor_info {
             self.world
                 .run(|mut camera: UniqueViewMut<CameraInfo>| camera.set(editor.camera));
             self.world.run_workload("draw_editor").unwrap();
+        } else {
+            self.world
+                .run(|mut window: UniqueViewMut<Window>| window.update_size(info.window_size));
         }
-        self.world.add_unique(FrameRenderInfo::from(&mut info));
-        let (gpu_future, command_buffer) =
-            self.world.run(crate::render::canvas::canvas_render_system);
+        self.world.add_unique(FrameRenderInfo::from(&info));
+        let after_future = self.world.run_with_data(
+            crate::render::canvas::canvas_render_system,
+            info.before_future,
+        );
         self.world.remove_unique::<FrameRenderInfo>().unwrap();
-        command_buffer
-            .execute_after(
-                info.before_future.join(gpu_future),
-                info.context.graphics_queue().clone(),
-            )
-            .unwrap()
-            .boxed()
+        after_future
     }
 
     fn command(&self, cmd: Command) {
@@ -341,17 +620,81 @@ impl App for SteelApp {
             Command::Save(world_data) => {
                 world_data.clear();
                 for component_fn in self.component_registry.values() {
-                    (component_fn.save_to_data)(world_data, &self.world);
+                    component_fn.save_to_data(world_data, &self.world);
                 }
                 for unique_fn in self.unique_registry.values() {
                     (unique_fn.save_to_data)(world_data, &self.world);
                 }
             }
+            Command::SaveScene(world_data) => {
+                world_data.clear();
+                for component_fn in self.component_registry.values() {
+                    component_fn.save_to_data(world_data, &self.world);
+                }
+                for unique_fn in self.unique_registry.values() {
+                    if unique_fn.persistent {
+                        (unique_fn.save_to_data)(world_data, &self.world);
+                    }
+                }
+            }
+            Command::SaveSceneToFile(path, saved) => {
+                match SceneManager::save_file(
+                    &self.world,
+                    &self.component_registry,
+                    &self.unique_registry,
+                    path,
+                ) {
+                    Ok(_) => *saved = true,
+                    Err(e) => {
+                        *saved = false;
+                        log::error!(
+                            "SteelApp::command: failed to save scene to {}, error={e}",
+                            path.display()
+                        );
+                    }
+                }
+            }
             Command::GetEntityCount(entity_count) => {
                 *entity_count = self
                     .world
                     .run(|entities: EntitiesView| entities.iter().count());
             }
+            Command::GetSceneStats(stats) => {
+                stats.entity_count = self
+                    .world
+                    .run(|entities: EntitiesView| entities.iter().count());
+                stats.component_counts = self
+                    .component_registry
+                    .iter()
+                    .map(|(name, component_fn)| (name.to_string(), component_fn.count(&self.world)))
+                    .collect();
+                stats
+                    .component_counts
+                    .sort_by(|(_, count1), (_, count2)| count2.cmp(count1));
+                stats.collider_count = self
+                    .world
+                    .borrow::<UniqueView<Physics2DManager>>()
+                    .map_or(0, |physics2d_manager| physics2d_manager.collider_set.len());
+                stats.draw_counts = self
+                    .world
+                    .run(|canvas: UniqueView<Canvas>| canvas.draw_counts());
+            }
+            Command::GetWorldBounds(out_bounds) => {
+                let mut bounds = self.world.run(|canvas: UniqueView<Canvas>| canvas.bounds());
+                if let Some((min, max)) = self
+                    .world
+                    .borrow::<UniqueView<Physics2DManager>>()
+                    .ok()
+                    .and_then(|physics2d_manager| physics2d_manager.collider_bounds())
+                {
+                    let (min, max) = (min.extend(0.0), max.extend(0.0));
+                    bounds = Some(match bounds {
+                        Some((min0, max0)) => (min0.min(min), max0.max(max)),
+                        None => (min, max),
+                    });
+                }
+                *out_bounds = bounds;
+            }
             Command::GetEntityAtScreen(window_index, screen_position, out_eid) => {
                 self.world.add_unique(GetEntityAtScreenParam {
                     window_index,
@@ -364,10 +707,59 @@ impl App for SteelApp {
                     .remove_unique::<GetEntityAtScreenParam>()
                     .unwrap();
             }
+            Command::GetEntityAndPositionAtScreen(
+                window_index,
+                screen_position,
+                out_eid,
+                out_position,
+            ) => {
+                self.world.add_unique(GetEntityAtScreenParam {
+                    window_index,
+                    screen_position,
+                });
+                *out_eid = self
+                    .world
+                    .run(crate::render::canvas::get_entity_at_screen_system);
+                *out_position = self
+                    .world
+                    .run(crate::render::canvas::get_position_at_screen_system);
+                self.world
+                    .remove_unique::<GetEntityAtScreenParam>()
+                    .unwrap();
+            }
+            Command::FindReferencesTo(eid, references) => {
+                let mut world_data = WorldData::default();
+                for component_fn in self.component_registry.values() {
+                    component_fn.save_to_data(&mut world_data, &self.world);
+                }
+                *references = world_data.find_references_to(eid);
+            }
+            Command::GetEntitiesDataOfEntity(eid, entities_data) => {
+                *entities_data = crate::data::get_entities_data_of_entity(
+                    &self.world,
+                    &self.component_registry,
+                    eid,
+                );
+            }
             Command::GetComponents(components) => {
                 *components = self.component_registry.keys().map(|s| *s).collect();
                 // TODO: cache components
             }
+            Command::GetUniques(uniques) => {
+                *uniques = self.unique_registry.keys().map(|s| s.to_string()).collect();
+            }
+            Command::GetDefaultComponentData(component_name, data) => {
+                *data = self
+                    .component_registry
+                    .get(component_name)
+                    .map(|component_fn| component_fn.default_data());
+            }
+            Command::GetDefaultUniqueData(unique_name, data) => {
+                *data = self
+                    .unique_registry
+                    .get(unique_name)
+                    .and_then(|unique_fn| unique_fn.default_data.clone());
+            }
             Command::UpdateInput(events) => {
                 self.world
                     .run(|mut input: UniqueViewMut<Input>| input.step_with_window_events(events));
@@ -375,6 +767,12 @@ impl App for SteelApp {
             Command::ResetTime => {
                 self.world.run(|mut time: UniqueViewMut<Time>| time.reset());
             }
+            Command::TakeWindowRequest(request) => {
+                *request = self
+                    .world
+                    .run(|mut window: UniqueViewMut<Window>| window.take_request());
+            }
+            Command::GetLogConfig(log_config) => *log_config = self.log_config.clone(),
             Command::GetAssetPath(asset_id, path) => {
                 *path = self
                     .world
@@ -383,6 +781,16 @@ impl App for SteelApp {
                     .get_asset_path(asset_id)
                     .map(|path| path.clone());
             }
+            Command::GetAssetsOfType(asset_type, assets) => {
+                *assets = self
+                    .world
+                    .borrow::<UniqueView<AssetManager>>()
+                    .unwrap()
+                    .get_assets_of_type(asset_type)
+                    .into_iter()
+                    .map(|(asset_id, path)| (asset_id, path.clone()))
+                    .collect();
+            }
             Command::GetAssetContent(asset_id, content) => {
                 *content = self.world.run(
                     |mut asset_manager: UniqueViewMut<AssetManager>,
@@ -424,6 +832,16 @@ impl App for SteelApp {
                     .unwrap()
                     .update_asset_path(asset_id, path);
             }
+            Command::GetPrefabInfo(eid, prefab_info) => {
+                *prefab_info = self.world.run(|prefabs: View<Prefab>| {
+                    (&prefabs).get(eid).ok().map(|prefab| PrefabInfo {
+                        asset: prefab.asset(),
+                        entity_index: prefab.entity_index(),
+                        entity_path: prefab.entity_path().clone(),
+                        root_entity: prefab.root_entity(),
+                    })
+                });
+            }
             Command::GetPrefabData(asset_id, data) => {
                 *data = self.world.run(
                     |mut prefab_assets: UniqueViewMut<PrefabAssets>,
@@ -470,12 +888,19 @@ impl App for SteelApp {
         match cmd {
             CommandMut::Load(world_data) => {
                 for component_fn in self.component_registry.values() {
-                    (component_fn.load_from_data)(&mut self.world, world_data);
+                    component_fn.load_from_data(&mut self.world, world_data);
                 }
                 for unique_fn in self.unique_registry.values() {
                     (unique_fn.load_from_data)(&mut self.world, world_data);
                 }
             }
+            CommandMut::LoadEntities(entities_data, old_id_to_new_id) => {
+                *old_id_to_new_id = crate::data::load_entities_data(
+                    &mut self.world,
+                    &self.component_registry,
+                    entities_data,
+                );
+            }
             CommandMut::Reload(scene_data) => {
                 SceneManager::load(
                     &mut self.world,
@@ -483,14 +908,60 @@ impl App for SteelApp {
                     &self.component_registry,
                     &self.unique_registry,
                 );
+                self.world.run_workload("scene_loaded").unwrap();
+            }
+            CommandMut::LoadSceneFromFile(path, loaded) => {
+                match SceneManager::load_file(
+                    &mut self.world,
+                    &self.component_registry,
+                    &self.unique_registry,
+                    path,
+                ) {
+                    Ok(_) => *loaded = true,
+                    Err(e) => {
+                        *loaded = false;
+                        log::error!(
+                            "SteelApp::command_mut: failed to load scene from {}, error={e}",
+                            path.display()
+                        );
+                    }
+                }
+            }
+            CommandMut::ClearScene => {
+                SceneManager::clear(&mut self.world, &self.unique_registry);
             }
             CommandMut::SetCurrentScene(scene) => {
                 SceneManager::set_current_scene(&mut self.world, scene);
             }
-            CommandMut::CreateEntity => {
+            CommandMut::ResetUnique(unique_name) => match self.unique_registry.get(unique_name) {
+                Some(UniqueFn {
+                    load_from_data,
+                    default_data: Some(default_data),
+                    ..
+                }) => {
+                    let mut world_data = WorldData::default();
+                    world_data
+                        .uniques
+                        .insert(unique_name.to_string(), default_data.clone());
+                    load_from_data(&mut self.world, &world_data);
+                }
+                Some(_) => log::warn!(
+                    "SteelApp::command_mut: ResetUnique: unique {unique_name} has no default value"
+                ),
+                None => log::warn!(
+                    "SteelApp::command_mut: ResetUnique: unique {unique_name} is not registered"
+                ),
+            },
+            CommandMut::CreateEntity(position) => {
+                let name = self
+                    .world
+                    .run(|names: View<Name>| Name::unique_name("Entity", &names));
                 self.world.add_entity((
-                    Name::new("New Entity"),
-                    Transform::default(),
+                    Name::new(name),
+                    Transform {
+                        position,
+                        ..Default::default()
+                    },
                     Renderer2D::default(),
                 ));
             }
@@ -498,6 +969,22 @@ impl App for SteelApp {
                 *old_id_to_new_id =
                     entities_data.add_to_world(&mut self.world, &self.component_registry);
             }
+            CommandMut::DuplicateEntity(id, new_id) => {
+                *new_id =
+                    crate::data::duplicate_entity(&mut self.world, &self.component_registry, id);
+            }
+            CommandMut::InstantiatePrefab(prefab_asset, position, rotation, scale, root) => {
+                *root = crate::data::instantiate_prefab(
+                    &mut self.world,
+                    &self.component_registry,
+                    prefab_asset,
+                    Transform {
+                        position,
+                        rotation,
+                        scale,
+                    },
+                );
+            }
             CommandMut::DestroyEntity(id) => {
                 self.world.delete_entity(id);
             }
@@ -506,12 +993,19 @@ impl App for SteelApp {
             }
             CommandMut::CreateComponent(id, component_name) => {
                 if let Some(component_fn) = self.component_registry.get(component_name) {
-                    (component_fn.create)(&mut self.world, id);
+                    component_fn.create(&mut self.world, id);
+                }
+            }
+            CommandMut::SetComponentData(id, component_name, data) => {
+                if let Some(component_fn) = self.component_registry.get(component_name) {
+                    component_fn.load_entity_from_data(&mut self.world, id, data);
+                } else {
+                    log::warn!("SteelApp::command_mut: SetComponentData: component {component_name} is not registered");
                 }
             }
             CommandMut::DestroyComponent(id, component_name) => {
                 if let Some(component_fn) = self.component_registry.get(component_name.as_str()) {
-                    (component_fn.destroy)(&mut self.world, id);
+                    component_fn.destroy(&mut self.world, id);
                 }
             }
             CommandMut::AttachBefore(eid, parent, before) => {
@@ -550,11 +1044,46 @@ impl App for SteelApp {
                     },
                 );
             }
+            CommandMut::MoveEntityUp(eid) => {
+                self.world.run(
+                    |mut hierarchy: UniqueViewMut<Hierarchy>,
+                     mut childrens: ViewMut<Children>,
+                     parents: View<Parent>| {
+                        crate::hierarchy::move_sibling(
+                            &mut hierarchy,
+                            &mut childrens,
+                            &parents,
+                            eid,
+                            true,
+                        );
+                    },
+                );
+            }
+            CommandMut::MoveEntityDown(eid) => {
+                self.world.run(
+                    |mut hierarchy: UniqueViewMut<Hierarchy>,
+                     mut childrens: ViewMut<Children>,
+                     parents: View<Parent>| {
+                        crate::hierarchy::move_sibling(
+                            &mut hierarchy,
+                            &mut childrens,
+                            &parents,
+                            eid,
+                            false,
+                        );
+                    },
+                );
+            }
         }
     }
+
+    fn with_world(&mut self, f: &mut dyn FnMut(&mut World)) {
+        f(&mut self.world);
+    }
 }
 
 /// System running schedule.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Schedule {
     /// The schedule that runs once when the application starts before [Schedule::Init].
     PreInit,
@@ -564,7 +1093,10 @@ pub enum Schedule {
     PostInit,
     /// The schedule that runs every frame before [Schedule::Update].
     PreUpdate,
-    /// The schedule that runs every frame.
+    /// The schedule that runs every frame, or at a fixed cadence if [Time::update_rate] is set.
+    /// At a fixed cadence, this runs zero or more times per frame, so input edge states like key pressed
+    /// are not reliable in this schedule, drawing to [Canvas] and [WorldUi] here is dropped, and ui shown with
+    /// [EguiContext] here flickers or is shown repeatedly. Draw in [Schedule::PostUpdate] which runs every frame.
     /// This schedule is skipped in steel-editor when the game is not running.
     /// For example, physics2d_update_system should run in this scheduler so that
     /// physics objects do not fall due to gravity when the game is not running in the Editor.
@@ -575,6 +1107,29 @@ pub enum Schedule {
     /// You can put systems here to display something only in scene window.
     /// For example, physics2d_debug_render_system shows colliders' bounds only in scene window.
     DrawEditor,
+    /// The schedule that runs once right after a scene is loaded or cleared, before the next [Schedule::PreUpdate].
+    /// You can put systems here to do one-time setup for a scene, like spawning derived entities or building caches.
+    /// Note that [SceneManager::current_scene] is not updated yet when steel-editor reloads the scene.
+    SceneLoaded,
+    /// The schedule that runs once right before a scene is unloaded or cleared, when all entities still exist.
+    SceneUnloaded,
+    /// The custom schedule added by [SteelApp::add_schedule].
+    Custom(&'static str),
+}
+
+/// The position of a custom schedule relative to another schedule, see [SteelApp::add_schedule].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SchedulePosition {
+    /// Run before the schedule.
+    Before(Schedule),
+    /// Run after the schedule.
+    After(Schedule),
+}
+
+struct CustomSchedule {
+    position: SchedulePosition,
+    workload: Option<Workload>,
+    workload_editor: Option<Workload>,
 }
 
 /// Plugin is a collection of components, uniques, and systems. You can use [SteelApp::add_plugin] to add them to SteelApp.
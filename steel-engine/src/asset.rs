@@ -6,7 +6,7 @@ use std::{
     path::{Path, PathBuf},
     sync::Arc,
 };
-use steel_common::platform::Platform;
+use steel_common::{data::Limit, platform::Platform};
 
 /// Asset info.
 pub struct Asset {
@@ -80,9 +80,41 @@ impl AssetManager {
         })
     }
 
+    /// Get all assets of asset_type with their paths sorted by path, asset_type can be "texture", "model",
+    /// "scene", "prefab" or a file extension, see [Limit::asset_type]. For example, you can list all scenes
+    /// with asset_type "scene" to build a level select menu, then switch to one with SceneManager::switch_scene.
+    pub fn get_assets_of_type(&self, asset_type: &str) -> Vec<(AssetId, &PathBuf)> {
+        let Limit::AssetExtensions(extensions) = Limit::asset_type(asset_type) else {
+            unreachable!()
+        };
+        let mut assets = self
+            .assets
+            .iter()
+            .filter(|(_, asset)| {
+                asset
+                    .path
+                    .extension()
+                    .and_then(|extension| extension.to_str())
+                    .is_some_and(|extension| extensions.contains(&extension.to_ascii_lowercase()))
+            })
+            .map(|(id, asset)| (*id, &asset.path))
+            .collect::<Vec<_>>();
+        assets.sort_by_key(|(_, path)| *path);
+        assets
+    }
+
     /// Insert an asset with asset_id and path. If asset_id exists,
     /// this is equivalent to clear an asset cache by setting asset's content to None.
     pub(crate) fn insert_asset(&mut self, asset_id: AssetId, path: PathBuf) {
+        if let Some(asset) = self.assets.get(&asset_id) {
+            if asset.path != path {
+                log::warn!(
+                    "AssetManager::insert_asset: duplicate asset id {asset_id:?}, {} is replaced by {}",
+                    asset.path.display(),
+                    path.display()
+                );
+            }
+        }
         self.assets.insert(asset_id, Asset::new(path));
     }
 
@@ -0,0 +1,116 @@
+use crate::{edit::Edit, pool::Pooled};
+use indexmap::IndexMap;
+use shipyard::{
+    AllStorages, AllStoragesViewMut, Component, EntityId, IntoIter, IntoWithId, Unique, UniqueView,
+    View,
+};
+use std::sync::RwLock;
+use steel_common::data::{Data, Limit, Value};
+
+/// The callback type of a behavior, which is called every update with the entity id
+/// of the [Behavior] component and mutable access to all storages of the ecs world.
+pub type BehaviorFn = fn(EntityId, &mut AllStorages);
+
+/// A copy of the names in [Behaviors], which is used by [Behavior] to list the registered callbacks
+/// in the combo box of steel-editor. Behaviors are saved by name, so loading does not need this list.
+static BEHAVIOR_NAMES: RwLock<Vec<String>> = RwLock::new(Vec::new());
+
+/// The Behaviors unique contains all behavior callbacks registered by [crate::app::SteelApp::add_behavior].
+#[derive(Unique, Default)]
+pub struct Behaviors {
+    callbacks: IndexMap<String, BehaviorFn>,
+}
+
+impl Behaviors {
+    /// Register callback with name, an existing callback with the same name is replaced.
+    pub fn add(&mut self, name: impl Into<String>, callback: BehaviorFn) {
+        self.callbacks.insert(name.into(), callback);
+        if let Ok(mut names) = BEHAVIOR_NAMES.write() {
+            *names = self.callbacks.keys().cloned().collect();
+        }
+    }
+
+    /// Get the callback named name.
+    pub fn get(&self, name: impl AsRef<str>) -> Option<BehaviorFn> {
+        self.callbacks.get(name.as_ref()).copied()
+    }
+
+    /// Get the names of all registered callbacks.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.callbacks.keys().map(|name| name.as_str())
+    }
+}
+
+/// The Behavior component attaches a behavior callback registered by [crate::app::SteelApp::add_behavior]
+/// to an entity, the callback is picked by name and is called every update by [behavior_update_system].
+/// # Example
+/// ```rust
+/// use steel::{app::{App, SteelApp}, transform::Transform};
+/// use shipyard::{AllStorages, EntityId, Get, ViewMut};
+///
+/// #[no_mangle]
+/// pub fn create() -> Box<dyn App> {
+///     SteelApp::new().add_behavior("Rise", rise).boxed()
+/// }
+///
+/// fn rise(eid: EntityId, all_storages: &mut AllStorages) {
+///     let mut transforms = all_storages.borrow::<ViewMut<Transform>>().unwrap();
+///     if let Ok(mut transform) = (&mut transforms).get(eid) {
+///         transform.position.y += 0.01;
+///     }
+/// }
+/// ```
+#[derive(Component, Debug, Default, Clone, PartialEq)]
+pub struct Behavior {
+    /// The name of the behavior callback.
+    pub name: String,
+}
+
+impl Behavior {
+    /// Create a Behavior component.
+    pub fn new(name: impl Into<String>) -> Self {
+        Behavior { name: name.into() }
+    }
+}
+
+impl Edit for Behavior {
+    fn name() -> &'static str {
+        "Behavior"
+    }
+
+    fn get_data(&self) -> Data {
+        let names = BEHAVIOR_NAMES
+            .read()
+            .map(|names| names.clone())
+            .unwrap_or_default();
+        Data::new().insert_with_limit(
+            "name",
+            Value::String(self.name.clone()),
+            Limit::StringEnum(names),
+        )
+    }
+
+    fn set_data(&mut self, data: &Data) {
+        if let Some(Value::String(v)) = data.get("name") {
+            self.name.clone_from(v);
+        }
+    }
+}
+
+/// Call the behavior callback of every [Behavior] component. Callbacks which are not registered are skipped,
+/// and entities which are waiting in [crate::pool::EntityPool] are skipped.
+pub fn behavior_update_system(mut all_storages: AllStoragesViewMut) {
+    let calls = all_storages.run(
+        |behaviors: View<Behavior>, pooled: View<Pooled>, registry: UniqueView<Behaviors>| {
+            (&behaviors, !&pooled)
+                .iter()
+                .with_id()
+                .map(|(eid, (behavior, _))| (eid, behavior))
+                .filter_map(|(eid, behavior)| registry.get(&behavior.name).map(|f| (eid, f)))
+                .collect::<Vec<_>>()
+        },
+    );
+    for (eid, callback) in calls {
+        callback(eid, &mut all_storages);
+    }
+}
@@ -1,20 +1,31 @@
 pub use steel_common::camera::*;
 
-use crate::{edit::Edit, transform::Transform};
-use glam::{Mat4, Quat, UVec2, Vec3};
+use crate::{
+    edit::Edit, hierarchy::Parent, render::layer::RenderLayers, time::Time, transform::Transform,
+    window::Window,
+};
+use glam::{Mat4, Quat, UVec2, Vec2, Vec3, Vec3Swizzles, Vec4, Vec4Swizzles};
 use shipyard::{
-    AddComponent, Component, Get, IntoIter, IntoWithId, Unique, UniqueViewMut, View, ViewMut,
+    AddComponent, Component, EntityId, Get, IntoIter, IntoWithId, Unique, UniqueView,
+    UniqueViewMut, View, ViewMut,
 };
-use steel_common::data::Data;
+use steel_common::data::{Data, Limit, Value};
 
 /// The camera info to use for current frame.
 /// CameraInfo is overriden by [Camera] component every frame if it exists,
 /// and is overriden by [SceneCamera] every frame if we are in steel-editor.
-#[derive(Unique)]
+#[derive(Unique, Clone)]
 pub struct CameraInfo {
     pub position: Vec3,
     pub rotation: Quat,
     pub settings: CameraSettings,
+    /// The layer mask of camera, only entities in these layers are rendered, see [RenderLayers].
+    pub render_layers: RenderLayers,
+    /// The normalized rect (x, y, width, height) of the window that this camera renders into, see [Camera::viewport].
+    pub viewport: Vec4,
+    /// The other cameras which are rendered after this camera into their own viewports, used for split-screen
+    /// and picture-in-picture. Each of them has no other cameras. This is empty in the scene window of steel-editor.
+    pub others: Vec<CameraInfo>,
 }
 
 impl CameraInfo {
@@ -24,17 +35,33 @@ impl CameraInfo {
             position: Vec3::ZERO,
             rotation: Quat::IDENTITY,
             settings: CameraSettings::new_orthographic(),
+            render_layers: RenderLayers::ALL,
+            viewport: Camera::FULL_VIEWPORT,
+            others: Vec::new(),
         }
     }
 
-    /// The camera forward direction vector.
+    /// Get the pixel offset and pixel size of [CameraInfo::viewport] in a window of window_size.
+    /// The viewport is clamped into the window and is at least one pixel large.
+    pub fn viewport_rect(&self, window_size: UVec2) -> (UVec2, UVec2) {
+        let window_size_f = window_size.as_vec2();
+        let min = (self.viewport.xy() * window_size_f)
+            .round()
+            .clamp(Vec2::ZERO, window_size_f);
+        let max = ((self.viewport.xy() + self.viewport.zw()) * window_size_f)
+            .round()
+            .clamp(Vec2::ZERO, window_size_f);
+        let offset = min.as_uvec2().min(window_size.saturating_sub(UVec2::ONE));
+        let extent = (max - min)
+            .as_uvec2()
+            .max(UVec2::ONE)
+            .min(window_size - offset);
+        (offset, extent)
+    }
+
+    /// The camera forward direction vector, see [camera_direction].
     pub fn direction(&self) -> Vec3 {
-        let rotation = self.rotation.to_scaled_axis(); // x: pitch, y: yaw, z: roll
-        Vec3::new(
-            rotation.y.sin() * rotation.x.cos(),
-            rotation.x.sin(),
-            -rotation.y.cos() * rotation.x.cos(),
-        )
+        camera_direction(self.rotation)
     }
 
     /// Caculate the (projection * view) matrix of camera.
@@ -48,29 +75,13 @@ impl CameraInfo {
         let up = right.cross(direction).normalize();
         let view = Mat4::look_at_rh(self.position, self.position + direction, up);
         let mut projection = match self.settings {
-            CameraSettings::Orthographic {
-                width,
-                height,
-                size,
-                near,
-                far,
-            } => {
-                let (half_width, half_height) = match size {
-                    OrthographicCameraSize::FixedWidth => Self::fixed_width(width, window_size),
-                    OrthographicCameraSize::FixedHeight => Self::fixed_height(height, window_size),
-                    OrthographicCameraSize::MinWidthHeight => {
-                        if width / height > window_size.x as f32 / window_size.y as f32 {
-                            Self::fixed_width(width, window_size)
-                        } else {
-                            Self::fixed_height(height, window_size)
-                        }
-                    }
-                };
+            CameraSettings::Orthographic { near, far, .. } => {
+                let half_size = self.orthographic_half_size(window_size).unwrap();
                 Mat4::orthographic_rh(
-                    -half_width,
-                    half_width,
-                    -half_height,
-                    half_height,
+                    -half_size.x,
+                    half_size.x,
+                    -half_size.y,
+                    half_size.y,
                     near,
                     far,
                 )
@@ -83,6 +94,46 @@ impl CameraInfo {
         projection * view
     }
 
+    /// Same as [CameraInfo::projection_view], but the depth is reversed so that near plane is at depth 1
+    /// and far plane is at depth 0. Together with a float depth buffer and a greater depth compare operation,
+    /// reverse-Z gives much better depth precision for distant geometry, which reduces z-fighting.
+    pub fn projection_view_reverse_z(&self, window_size: &UVec2) -> Mat4 {
+        // depth = 1 - depth, which is z = w - z in clip space
+        let reverse_z = Mat4::from_cols(
+            Vec4::X,
+            Vec4::Y,
+            Vec4::new(0.0, 0.0, -1.0, 0.0),
+            Vec4::new(0.0, 0.0, 1.0, 1.0),
+        );
+        reverse_z * self.projection_view(window_size)
+    }
+
+    /// Get the half width and half height in world units of the area seen by an orthographic camera
+    /// which renders into window_size, None if this is a perspective camera.
+    pub fn orthographic_half_size(&self, window_size: &UVec2) -> Option<Vec2> {
+        let CameraSettings::Orthographic {
+            width,
+            height,
+            size,
+            ..
+        } = self.settings
+        else {
+            return None;
+        };
+        let (half_width, half_height) = match size {
+            OrthographicCameraSize::FixedWidth => Self::fixed_width(width, window_size),
+            OrthographicCameraSize::FixedHeight => Self::fixed_height(height, window_size),
+            OrthographicCameraSize::MinWidthHeight => {
+                if width / height > window_size.x as f32 / window_size.y as f32 {
+                    Self::fixed_width(width, window_size)
+                } else {
+                    Self::fixed_height(height, window_size)
+                }
+            }
+        };
+        Some(Vec2::new(half_width, half_height))
+    }
+
     fn fixed_width(width: f32, window_size: &UVec2) -> (f32, f32) {
         let half_width = width / 2.0;
         let half_height = half_width * window_size.y as f32 / window_size.x as f32;
@@ -99,30 +150,65 @@ impl CameraInfo {
         self.position = scene_camera.position;
         self.rotation = scene_camera.rotation;
         self.settings = scene_camera.settings;
+        self.render_layers = RenderLayers::ALL;
+        self.viewport = Camera::FULL_VIEWPORT;
+        self.others.clear();
     }
 }
 
 /// The Camera component can be attached to an entity and move camera according to the [Transform] component.
+/// There can be multiple cameras with different [Camera::viewport] for split-screen or picture-in-picture.
+/// All cameras draw entity ids into the same eid image, so picking an entity at a screen position where
+/// viewports overlap returns the entity drawn by the last camera in [Camera::order].
 #[derive(Component, Debug)]
-pub struct Camera(pub CameraSettings);
+pub struct Camera {
+    pub settings: CameraSettings,
+    /// The normalized rect (x, y, width, height) of the window that this camera renders into, where (0, 0)
+    /// is the top left corner and (1, 1) is the bottom right corner.
+    pub viewport: Vec4,
+    /// Cameras are rendered from low order to high order, cameras with the same order are rendered in the order
+    /// of entities. The first camera clears the whole window, and the other cameras only clear their viewports,
+    /// so a picture-in-picture camera needs a higher order than the camera of the whole window.
+    pub order: i32,
+}
+
+impl Camera {
+    /// The viewport which covers the whole window.
+    pub const FULL_VIEWPORT: Vec4 = Vec4::new(0.0, 0.0, 1.0, 1.0);
+
+    /// Create a Camera component which renders into the whole window.
+    pub fn new(settings: CameraSettings) -> Self {
+        Camera {
+            settings,
+            viewport: Self::FULL_VIEWPORT,
+            order: 0,
+        }
+    }
+}
+
+impl From<CameraSettings> for Camera {
+    fn from(settings: CameraSettings) -> Self {
+        Camera::new(settings)
+    }
+}
 
 impl std::ops::Deref for Camera {
     type Target = CameraSettings;
 
     fn deref(&self) -> &Self::Target {
-        &self.0
+        &self.settings
     }
 }
 
 impl std::ops::DerefMut for Camera {
     fn deref_mut(&mut self) -> &mut Self::Target {
-        &mut self.0
+        &mut self.settings
     }
 }
 
 impl Default for Camera {
     fn default() -> Self {
-        Camera(CameraSettings::new_orthographic())
+        Camera::new(CameraSettings::new_orthographic())
     }
 }
 
@@ -133,28 +219,223 @@ impl Edit for Camera {
 
     fn get_data(&self) -> Data {
         let mut data = Data::new();
-        self.0.get_data(&mut data);
+        self.settings.get_data(&mut data);
+        data.add_value_with_limit(
+            "viewport",
+            Value::Vec4(self.viewport),
+            Limit::Float32Range(0.0..=1.0),
+        );
+        data.add_value("order", Value::Int32(self.order));
         data
     }
 
     fn set_data(&mut self, data: &Data) {
-        self.0.set_data(data);
+        self.settings.set_data(data);
+        if let Some(Value::Vec4(v)) = data.get("viewport") {
+            self.viewport = *v;
+        }
+        if let Some(Value::Int32(v)) = data.get("order") {
+            self.order = *v;
+        }
     }
 }
 
-/// Modify [CameraInfo] unique according to the [Camera] component.
+/// Modify [CameraInfo] unique according to the [Camera] components. The cameras are sorted by [Camera::order],
+/// the first camera is stored in [CameraInfo] itself, and the other cameras are stored in [CameraInfo::others].
 pub fn camera_maintain_system(
     mut transform: ViewMut<Transform>,
     camera: View<Camera>,
+    render_layers: View<RenderLayers>,
     mut info: UniqueViewMut<CameraInfo>,
 ) {
-    if let Some((e, camera)) = camera.iter().with_id().next() {
+    let mut cameras = camera.iter().with_id().collect::<Vec<_>>();
+    // stable sort keeps the order of entities for cameras with the same order
+    cameras.sort_by_key(|(_, camera)| camera.order);
+    let mut cameras = cameras.into_iter().map(|(e, camera)| {
         if !transform.contains(e) {
             transform.add_component_unchecked(e, Transform::default());
         }
         let transform = transform.get(e).unwrap();
-        info.position = transform.position;
-        info.rotation = transform.rotation;
-        info.settings = **camera;
+        CameraInfo {
+            position: transform.position,
+            rotation: transform.rotation,
+            settings: camera.settings,
+            render_layers: render_layers.get(e).copied().unwrap_or(RenderLayers::ALL),
+            viewport: camera.viewport,
+            others: Vec::new(),
+        }
+    });
+    if let Some(mut first) = cameras.next() {
+        first.others = cameras.collect();
+        *info = first;
     } // TODO: handle situation without Camera
 }
+
+/// The CameraFollow component makes the [Transform] of this entity smoothly follow the target entity.
+/// Usually used together with the [Camera] component.
+#[derive(Component, Edit, Default, Debug)]
+pub struct CameraFollow {
+    /// The entity to follow.
+    pub target: EntityId,
+    /// The position offset to the target.
+    pub offset: Vec3,
+    /// The time in seconds to catch up most of the distance to the target, 0 means no smoothing.
+    #[edit(limit = "Limit::Float32Range(0.0..=f32::MAX)")]
+    pub smoothing: f32,
+    /// The half size of the area on xy plane that the target can move in without moving this entity.
+    #[edit(limit = "Limit::Float32Range(0.0..=f32::MAX)")]
+    pub dead_zone: Vec2,
+    /// Whether to clamp the position on xy plane in [CameraFollow::bounds_min, CameraFollow::bounds_max].
+    /// Note that this only clamps the center of camera, use [CameraConfiner] to keep the whole view in bounds.
+    pub use_bounds: bool,
+    pub bounds_min: Vec2,
+    pub bounds_max: Vec2,
+}
+
+impl CameraFollow {
+    /// Caculate the next world position from the current world position and the target world position.
+    pub fn follow(&self, position: Vec3, target_position: Vec3, delta: f32) -> Vec3 {
+        let mut destination = target_position + self.offset;
+        let diff = destination.xy() - position.xy();
+        let dead_zone = self.dead_zone.max(Vec2::ZERO);
+        destination.x = position.x + diff.x.signum() * (diff.x.abs() - dead_zone.x).max(0.0);
+        destination.y = position.y + diff.y.signum() * (diff.y.abs() - dead_zone.y).max(0.0);
+
+        let mut position = if self.smoothing > 0.0 {
+            position.lerp(destination, 1.0 - (-delta / self.smoothing).exp())
+        } else {
+            destination
+        };
+        if self.use_bounds {
+            let min = self.bounds_min.min(self.bounds_max);
+            let max = self.bounds_min.max(self.bounds_max);
+            let xy = position.xy().clamp(min, max);
+            position.x = xy.x;
+            position.y = xy.y;
+        }
+        position
+    }
+}
+
+/// Move entities which have [CameraFollow] component toward their targets.
+pub fn camera_follow_system(
+    follow: View<CameraFollow>,
+    parent: View<Parent>,
+    mut transform: ViewMut<Transform>,
+    time: UniqueView<Time>,
+) {
+    for (e, follow) in follow.iter().with_id() {
+        let mut model_cache = None;
+        let mut scale_cache = None;
+        let Some(target_model) = Transform::entity_final_model(
+            follow.target,
+            &parent,
+            &transform,
+            &mut model_cache,
+            &mut scale_cache,
+        ) else {
+            continue;
+        };
+        let Some(final_model) = Transform::entity_final_model(
+            e,
+            &parent,
+            &transform,
+            &mut model_cache,
+            &mut scale_cache,
+        ) else {
+            continue;
+        };
+        let parent_model = Transform::entity_final_model(
+            parent.get(e).map(|p| **p).unwrap_or_default(),
+            &parent,
+            &transform,
+            &mut model_cache,
+            &mut scale_cache,
+        );
+
+        let position = follow.follow(
+            final_model.translation.into(),
+            target_model.translation.into(),
+            time.delta(),
+        );
+        let position = match parent_model {
+            Some(parent_model) => parent_model.inverse().transform_point3(position),
+            None => position,
+        };
+        if let Ok(mut transform) = (&mut transform).get(e) {
+            transform.position = position;
+        }
+    }
+}
+
+/// The CameraConfiner component keeps the view of the [Camera] on this entity inside a rect on xy plane,
+/// so that the camera never shows anything outside the level. The camera position is clamped after it
+/// is moved by [CameraFollow] and other systems in [crate::app::Schedule::Update]. If the rect is smaller
+/// than the view, the camera is centered on the rect. Only orthographic cameras without rotation are fully
+/// confined, the position of perspective cameras is clamped into the rect like [CameraFollow::use_bounds].
+/// The entity should be a root entity, because the position of its [Transform] is clamped as world position.
+/// A rect with zero width or height confines nothing, so that the camera is not pinned while it is being set up.
+#[derive(Component, Edit, Debug)]
+pub struct CameraConfiner {
+    /// The min corner of the level rect in world space.
+    pub min: Vec2,
+    /// The max corner of the level rect in world space.
+    pub max: Vec2,
+}
+
+impl Default for CameraConfiner {
+    fn default() -> Self {
+        CameraConfiner {
+            min: Vec2::splat(-50.0),
+            max: Vec2::splat(50.0),
+        }
+    }
+}
+
+impl CameraConfiner {
+    /// Whether the rect of this confiner has zero width or height.
+    pub fn is_empty(&self) -> bool {
+        self.min.x == self.max.x || self.min.y == self.max.y
+    }
+
+    /// Clamp position so that the area of half_size around it is inside the rect of this confiner.
+    pub fn confine(&self, position: Vec3, half_size: Vec2) -> Vec3 {
+        let rect_min = self.min.min(self.max);
+        let rect_max = self.min.max(self.max);
+        let center = (rect_min + rect_max) / 2.0;
+        let min = (rect_min + half_size).min(center);
+        let max = (rect_max - half_size).max(center);
+        position.xy().clamp(min, max).extend(position.z)
+    }
+}
+
+/// Clamp the positions of entities which have [CameraConfiner] and [Camera] components.
+pub fn camera_confiner_system(
+    confiner: View<CameraConfiner>,
+    camera: View<Camera>,
+    mut transform: ViewMut<Transform>,
+    window: UniqueView<Window>,
+) {
+    let window_size = window.size();
+    if window_size.x == 0 || window_size.y == 0 {
+        return;
+    }
+    for (confiner, camera, transform) in (&confiner, &camera, &mut transform).iter() {
+        if confiner.is_empty() {
+            continue;
+        }
+        let info = CameraInfo {
+            settings: camera.settings,
+            viewport: camera.viewport,
+            ..CameraInfo::new()
+        };
+        let (_, viewport_extent) = info.viewport_rect(window_size);
+        let half_size = info
+            .orthographic_half_size(&viewport_extent)
+            .unwrap_or_default();
+        let position = confiner.confine(transform.position, half_size);
+        if position != transform.position {
+            transform.position = position;
+        }
+    }
+}
@@ -2,26 +2,119 @@ pub use steel_common::data::*;
 
 use crate::{
     asset::AssetManager,
+    dynamic::DynamicComponent,
     edit::Edit,
-    hierarchy::{Children, Parent},
+    hierarchy::{Children, Hierarchy, Parent},
+    transform::Transform,
 };
 use indexmap::IndexMap;
 use shipyard::{
     track::{All, Deletion, Insertion, Modification, Removal, Untracked},
-    AddComponent, Component, EntitiesView, EntityId, Get, IntoIter, IntoWithId, Unique, UniqueView,
-    UniqueViewMut, View, ViewMut, World,
+    AddComponent, Component, Delete, EntitiesView, EntityId, Get, IntoIter, IntoWithId, Unique,
+    UniqueView, UniqueViewMut, View, ViewMut, World,
 };
-use std::{collections::HashMap, sync::Arc};
+use std::{borrow::Cow, collections::HashMap, sync::Arc};
 use steel_common::{asset::AssetId, platform::Platform};
 
 /// ComponentFn stores many functions of a component, like component create and destroy functions.
 /// These functions are used by steel-editor so that we can use steel-editor ui to edit this component.
+/// The functions of a [dynamic component](crate::dynamic) need its name and schema which can not be captured
+/// by fn pointers, so call the methods of ComponentFn which have the same names as the fields instead of the
+/// fields themselves, the methods also work for dynamic components.
 pub struct ComponentFn {
     pub create: fn(&mut World, EntityId),
     pub create_with_data: fn(&mut World, EntityId, &Data),
     pub destroy: fn(&mut World, EntityId),
     pub save_to_data: fn(&mut WorldData, &World),
+    pub save_entity_to_data: fn(&mut EntitiesData, &World, EntityId),
     pub load_from_data: fn(&mut World, &WorldData),
+    /// Set the data of the component of an entity, does nothing if the entity does not have this component.
+    pub load_entity_from_data: fn(&mut World, EntityId, &Data),
+    /// Check if an entity has this component.
+    pub contains: fn(&World, EntityId) -> bool,
+    /// Count the entities which have this component.
+    pub count: fn(&World) -> usize,
+    /// Get the data of the default value of this component.
+    pub default_data: fn() -> Data,
+    /// The dynamic component which is used by the methods instead of the fields, None if this is a rust type.
+    pub dynamic: Option<DynamicComponent>,
+}
+
+impl ComponentFn {
+    pub fn create(&self, world: &mut World, entity: EntityId) {
+        match &self.dynamic {
+            Some(dynamic) => dynamic.create(world, entity),
+            None => (self.create)(world, entity),
+        }
+    }
+
+    pub fn create_with_data(&self, world: &mut World, entity: EntityId, data: &Data) {
+        match &self.dynamic {
+            Some(dynamic) => dynamic.create_with_data(world, entity, data),
+            None => (self.create_with_data)(world, entity, data),
+        }
+    }
+
+    pub fn destroy(&self, world: &mut World, entity: EntityId) {
+        match &self.dynamic {
+            Some(dynamic) => dynamic.destroy(world, entity),
+            None => (self.destroy)(world, entity),
+        }
+    }
+
+    pub fn save_to_data(&self, world_data: &mut WorldData, world: &World) {
+        match &self.dynamic {
+            Some(dynamic) => dynamic.save_to_data(world_data, world),
+            None => (self.save_to_data)(world_data, world),
+        }
+    }
+
+    pub fn save_entity_to_data(
+        &self,
+        entities_data: &mut EntitiesData,
+        world: &World,
+        entity: EntityId,
+    ) {
+        match &self.dynamic {
+            Some(dynamic) => dynamic.save_entity_to_data(entities_data, world, entity),
+            None => (self.save_entity_to_data)(entities_data, world, entity),
+        }
+    }
+
+    pub fn load_from_data(&self, world: &mut World, world_data: &WorldData) {
+        match &self.dynamic {
+            Some(dynamic) => dynamic.load_from_data(world, world_data),
+            None => (self.load_from_data)(world, world_data),
+        }
+    }
+
+    pub fn load_entity_from_data(&self, world: &mut World, entity: EntityId, data: &Data) {
+        match &self.dynamic {
+            Some(dynamic) => dynamic.load_entity_from_data(world, entity, data),
+            None => (self.load_entity_from_data)(world, entity, data),
+        }
+    }
+
+    pub fn contains(&self, world: &World, entity: EntityId) -> bool {
+        match &self.dynamic {
+            Some(dynamic) => dynamic.contains(world, entity),
+            None => (self.contains)(world, entity),
+        }
+    }
+
+    pub fn count(&self, world: &World) -> usize {
+        match &self.dynamic {
+            Some(dynamic) => dynamic.count(world),
+            None => (self.count)(world),
+        }
+    }
+
+    pub fn default_data(&self) -> Data {
+        match &self.dynamic {
+            Some(dynamic) => dynamic.default_data(),
+            None => (self.default_data)(),
+        }
+    }
 }
 
 /// A map of ComponentFn, key is component name.
@@ -53,6 +146,16 @@ impl ComponentRegistry {
         C::register(self);
     }
 
+    /// Insert a dynamic component named name which is described by schema, see [crate::dynamic] for more information.
+    /// A component with the same name is replaced.
+    pub fn register_dynamic(&mut self, name: impl Into<String>, schema: Data) {
+        let name = crate::dynamic::intern(name.into());
+        if self.contains_key(name) {
+            log::warn!("ComponentRegistry::register_dynamic: component {name} is replaced");
+        }
+        self.insert(name, crate::dynamic::component_fn(name, schema));
+    }
+
     /// Insert a type of Component<Tracking = Untracked> to ComponentRegistry.
     fn register_untracked<C: Component<Tracking = Untracked> + Edit + Default + Send + Sync>(
         &mut self,
@@ -64,7 +167,13 @@ impl ComponentRegistry {
                 create_with_data: Self::create_with_data_fn::<C>,
                 destroy: Self::destroy_fn::<C>,
                 save_to_data: Self::save_to_data_fn::<C>,
+                save_entity_to_data: Self::save_entity_to_data_fn::<C>,
                 load_from_data: Self::load_from_data_untracked_fn::<C>,
+                load_entity_from_data: Self::load_entity_from_data_fn::<C>,
+                contains: Self::contains_fn::<C>,
+                count: Self::count_fn::<C>,
+                default_data: Self::default_data_fn::<C>,
+                dynamic: None,
             },
         );
     }
@@ -82,7 +191,13 @@ impl ComponentRegistry {
                 create_with_data: Self::create_with_data_fn::<C>,
                 destroy: Self::destroy_fn::<C>,
                 save_to_data: Self::save_to_data_fn::<C>,
+                save_entity_to_data: Self::save_entity_to_data_fn::<C>,
                 load_from_data: Self::load_from_data_track_insertion_fn::<C>,
+                load_entity_from_data: Self::load_entity_from_data_fn::<C>,
+                contains: Self::contains_fn::<C>,
+                count: Self::count_fn::<C>,
+                default_data: Self::default_data_fn::<C>,
+                dynamic: None,
             },
         );
     }
@@ -100,7 +215,13 @@ impl ComponentRegistry {
                 create_with_data: Self::create_with_data_fn::<C>,
                 destroy: Self::destroy_fn::<C>,
                 save_to_data: Self::save_to_data_fn::<C>,
+                save_entity_to_data: Self::save_entity_to_data_fn::<C>,
                 load_from_data: Self::load_from_data_track_modification_fn::<C>,
+                load_entity_from_data: Self::load_entity_from_data_fn::<C>,
+                contains: Self::contains_fn::<C>,
+                count: Self::count_fn::<C>,
+                default_data: Self::default_data_fn::<C>,
+                dynamic: None,
             },
         );
     }
@@ -116,7 +237,13 @@ impl ComponentRegistry {
                 create_with_data: Self::create_with_data_fn::<C>,
                 destroy: Self::destroy_fn::<C>,
                 save_to_data: Self::save_to_data_fn::<C>,
+                save_entity_to_data: Self::save_entity_to_data_fn::<C>,
                 load_from_data: Self::load_from_data_track_deletion_fn::<C>,
+                load_entity_from_data: Self::load_entity_from_data_fn::<C>,
+                contains: Self::contains_fn::<C>,
+                count: Self::count_fn::<C>,
+                default_data: Self::default_data_fn::<C>,
+                dynamic: None,
             },
         );
     }
@@ -132,7 +259,13 @@ impl ComponentRegistry {
                 create_with_data: Self::create_with_data_fn::<C>,
                 destroy: Self::destroy_fn::<C>,
                 save_to_data: Self::save_to_data_fn::<C>,
+                save_entity_to_data: Self::save_entity_to_data_fn::<C>,
                 load_from_data: Self::load_from_data_track_removal_fn::<C>,
+                load_entity_from_data: Self::load_entity_from_data_fn::<C>,
+                contains: Self::contains_fn::<C>,
+                count: Self::count_fn::<C>,
+                default_data: Self::default_data_fn::<C>,
+                dynamic: None,
             },
         );
     }
@@ -146,7 +279,13 @@ impl ComponentRegistry {
                 create_with_data: Self::create_with_data_fn::<C>,
                 destroy: Self::destroy_fn::<C>,
                 save_to_data: Self::save_to_data_fn::<C>,
+                save_entity_to_data: Self::save_entity_to_data_fn::<C>,
                 load_from_data: Self::load_from_data_track_all_fn::<C>,
+                load_entity_from_data: Self::load_entity_from_data_fn::<C>,
+                contains: Self::contains_fn::<C>,
+                count: Self::count_fn::<C>,
+                default_data: Self::default_data_fn::<C>,
+                dynamic: None,
             },
         );
     }
@@ -160,7 +299,8 @@ impl ComponentRegistry {
         entity: EntityId,
         data: &Data,
     ) {
-        world.add_component(entity, (C::from_data(data),))
+        let data = validate_data(&C::default(), data);
+        world.add_component(entity, (C::from_data(&data),))
     }
 
     fn destroy_fn<C: Component + Edit + Send + Sync>(world: &mut World, entity: EntityId) {
@@ -184,6 +324,22 @@ impl ComponentRegistry {
         });
     }
 
+    fn save_entity_to_data_fn<C: Component + Edit + Send + Sync>(
+        entities_data: &mut EntitiesData,
+        world: &World,
+        entity: EntityId,
+    ) {
+        world.run(|c: View<C>| {
+            if let Ok(c) = c.get(entity) {
+                entities_data
+                    .entry(entity)
+                    .or_default()
+                    .components
+                    .insert(C::name().into(), c.get_data());
+            }
+        });
+    }
+
     /// Currently we must write different generic functions for different tracking type, see https://github.com/leudz/shipyard/issues/157.
     /// TODO: find a way to write only one generic function to cover all tracking type.
     fn load_from_data_untracked_fn<C: Component<Tracking = Untracked> + Edit + Send + Sync>(
@@ -256,10 +412,37 @@ impl ComponentRegistry {
         })
     }
 
+    fn load_entity_from_data_fn<C: Component + Edit + Send + Sync>(
+        world: &mut World,
+        entity: EntityId,
+        data: &Data,
+    ) {
+        world.run(|mut c: ViewMut<C>| {
+            if let Ok(mut c) = (&mut c).get(entity) {
+                let data = validate_data(c.as_ref(), data);
+                c.set_data(&data);
+            }
+        })
+    }
+
+    fn contains_fn<C: Component + Send + Sync>(world: &World, entity: EntityId) -> bool {
+        world.run(|c: View<C>| c.contains(entity))
+    }
+
+    fn count_fn<C: Component + Send + Sync>(world: &World) -> usize {
+        world.run(|c: View<C>| c.len())
+    }
+
+    fn default_data_fn<C: Edit + Default>() -> Data {
+        C::default().get_data()
+    }
+
+    /// Used by [CommandMut::Load](steel_common::app::CommandMut::Load) which is sent by editor every frame,
+    /// so the data is validated by its own limits, see [validate_loaded_data].
     fn _load_from_data<C: Edit>(id: EntityId, c: &mut C, world_data: &WorldData) {
         if let Some(entity_data) = world_data.entities.get(&id) {
             if let Some(component_data) = entity_data.components.get(C::name()) {
-                c.set_data(component_data);
+                c.set_data(&validate_loaded_data::<C>(component_data));
             }
         }
     }
@@ -347,6 +530,14 @@ pub struct UniqueFn {
     pub save_to_data: fn(&mut WorldData, &World),
     pub load_from_data: fn(&mut World, &WorldData),
     pub load_from_scene_data: fn(&mut World, &WorldData),
+    /// The data of this unique when it is added by [crate::app::SteelApp::add_and_register_unique],
+    /// this unique is reset to it when the scene is cleared.
+    pub default_data: Option<Data>,
+    /// If false, this unique is not saved into or loaded from scene files, see [crate::app::SteelApp::mark_unique_transient].
+    pub persistent: bool,
+    /// Reset this unique to its Default value, which is used instead of default_data for transient uniques
+    /// when the scene is cleared. This is set by [crate::app::SteelApp::mark_unique_transient].
+    pub reset: Option<fn(&mut World)>,
 }
 
 /// A map of UniqueFn, key is unique name.
@@ -380,6 +571,9 @@ impl UniqueRegistry {
                 save_to_data: Self::save_to_data_fn::<U>,
                 load_from_data: Self::load_from_data_fn::<U>,
                 load_from_scene_data: Self::load_from_scene_data_fn::<U>,
+                default_data: None,
+                persistent: true,
+                reset: None,
             },
         );
     }
@@ -393,17 +587,59 @@ impl UniqueRegistry {
         world_data: &WorldData,
     ) {
         if let Some(unique_data) = world_data.uniques.get(U::name()) {
-            world.run(|mut u: UniqueViewMut<U>| u.set_data(unique_data));
+            let unique_data = validate_loaded_data::<U>(unique_data);
+            world.run(|mut u: UniqueViewMut<U>| u.set_data(&unique_data));
         }
     }
 
+    /// Reset unique U to its Default value.
+    pub(crate) fn reset_fn<U: Unique + Default + Send + Sync>(world: &mut World) {
+        world.run(|mut u: UniqueViewMut<U>| *u = U::default());
+    }
+
     fn load_from_scene_data_fn<U: Unique + Edit + Send + Sync>(
         world: &mut World,
         world_data: &WorldData,
     ) {
         if let Some(unique_data) = world_data.uniques.get(U::name()) {
-            world.run(|mut u: UniqueViewMut<U>| u.load_data(unique_data));
+            world.run(|mut u: UniqueViewMut<U>| {
+                let unique_data = validate_data(&*u, unique_data);
+                u.load_data(&unique_data)
+            });
+        }
+    }
+}
+
+/// Check data against the limits of e before it is set to e, so that a hand-edited
+/// scene file can not produce an invalid component or unique. Logs a warning for each fixed value.
+fn validate_data<'a, E: Edit>(e: &E, data: &'a Data) -> Cow<'a, Data> {
+    validate_data_with_limits::<E>(data, &e.get_data().limits)
+}
+
+/// Check data against the limits in data itself, which is cheap enough for
+/// [CommandMut::Load](steel_common::app::CommandMut::Load) sent by editor every frame,
+/// because it does not call [Edit::get_data]. The limits of the data sent by editor come from [Edit::get_data].
+fn validate_loaded_data<E: Edit>(data: &Data) -> Cow<'_, Data> {
+    validate_data_with_limits::<E>(data, &data.limits)
+}
+
+fn validate_data_with_limits<'a, E: Edit>(
+    data: &'a Data,
+    limits: &HashMap<String, Limit>,
+) -> Cow<'a, Data> {
+    match data.validate(limits) {
+        Some((fixed_data, names)) => {
+            for name in names {
+                log::warn!(
+                    "validate_data: invalid value {name} in {}: {:?}, fixed to {:?}",
+                    E::name(),
+                    data.get(&name),
+                    fixed_data.get(&name)
+                );
+            }
+            Cow::Owned(fixed_data)
         }
+        None => Cow::Borrowed(data),
     }
 }
 
@@ -445,7 +681,10 @@ impl WorldDataExt for WorldData {
         create_components_in_world(&new_world_data.entities, world, component_registry);
 
         // load uniques in ecs world.
-        for unique_fn in unique_registry.values() {
+        for unique_fn in unique_registry
+            .values()
+            .filter(|unique_fn| unique_fn.persistent)
+        {
             (unique_fn.load_from_scene_data)(world, &new_world_data);
         }
 
@@ -481,6 +720,127 @@ impl EntitiesDataExt for EntitiesData {
     }
 }
 
+/// Apply entities_data onto ecs world without touching other entities. Components of existing entities are
+/// set to the data, or created if they do not exist. Entities which do not exist are added with new entity ids
+/// like [EntitiesDataExt::add_to_world], and entity ids in entities_data are changed to the new ids.
+/// Returns old_id_to_new_id map of the added entities.
+pub fn load_entities_data(
+    world: &mut World,
+    component_registry: &ComponentRegistry,
+    entities_data: &EntitiesData,
+) -> HashMap<EntityId, EntityId> {
+    let (existing_entities_data, missing_entities_data): (EntitiesData, EntitiesData) =
+        world.run(|entities: EntitiesView| {
+            let (existing, missing): (Vec<_>, Vec<_>) = entities_data
+                .iter()
+                .map(|(eid, entity_data)| (*eid, entity_data.clone()))
+                .partition(|(eid, _)| entities.is_alive(*eid));
+            (
+                EntitiesData(existing.into_iter().collect()),
+                EntitiesData(missing.into_iter().collect()),
+            )
+        });
+    let old_id_to_new_id = missing_entities_data.add_to_world(world, component_registry);
+
+    for (eid, entity_data) in &existing_entities_data {
+        for (component_name, component_data) in &entity_data.components {
+            if let Some(component_fn) = component_registry.get(component_name.as_str()) {
+                let component_data = update_eid_in_data(component_data, &old_id_to_new_id, world);
+                if component_fn.contains(world, *eid) {
+                    component_fn.load_entity_from_data(world, *eid, &component_data);
+                } else {
+                    component_fn.create_with_data(world, *eid, &component_data);
+                }
+            }
+        }
+    }
+    old_id_to_new_id
+}
+
+/// Get EntitiesData of entity and all its descendants. The input entity is kept as the
+/// first entity in returned EntitiesData. Returns empty EntitiesData if entity does not exist.
+pub fn get_entities_data_of_entity(
+    world: &World,
+    component_registry: &ComponentRegistry,
+    entity: EntityId,
+) -> EntitiesData {
+    let mut entities_data = EntitiesData::default();
+    if !world.run(|entities: EntitiesView| entities.is_alive(entity)) {
+        return entities_data;
+    }
+    let mut entities_to_add = vec![entity];
+    while !entities_to_add.is_empty() {
+        let mut new_entities_to_add = Vec::new();
+        for e in entities_to_add {
+            entities_data.entry(e).or_default();
+            for component_fn in component_registry.values() {
+                component_fn.save_entity_to_data(&mut entities_data, world, e);
+            }
+            new_entities_to_add.extend(world.run(|childrens: View<Children>| {
+                childrens.get(e).map(|c| c.to_vec()).unwrap_or_default()
+            }));
+        }
+        entities_to_add = new_entities_to_add;
+    }
+    entities_data
+}
+
+/// Duplicate entity and all its descendants with new entity ids, and attach the new entity next to entity.
+/// The duplicated prefab instances whose root entities are also duplicated stay linked to their prefabs,
+/// other duplicated entities are not linked to any prefab. Returns the new entity id, or EntityId::dead()
+/// if entity does not exist.
+pub fn duplicate_entity(
+    world: &mut World,
+    component_registry: &ComponentRegistry,
+    entity: EntityId,
+) -> EntityId {
+    let entities_data = get_entities_data_of_entity(world, component_registry, entity);
+    if entities_data.is_empty() {
+        log::warn!("duplicate_entity: non-existent entity: {entity:?}");
+        return EntityId::dead();
+    }
+    let old_id_to_new_id = entities_data.add_to_world(world, component_registry);
+    let new_entity = old_id_to_new_id[&entity];
+    let parent = entities_data[&entity].parent();
+    world.run(|mut prefabs: ViewMut<Prefab>| {
+        // Prefab values are read-only so that they are not copied by add_to_world, copy them here
+        for (old_id, new_id) in &old_id_to_new_id {
+            let new_prefab = prefabs.get(*old_id).ok().and_then(|prefab| {
+                old_id_to_new_id
+                    .get(&prefab.root_entity)
+                    .map(|new_root_entity| Prefab {
+                        asset: prefab.asset,
+                        entity_index: prefab.entity_index,
+                        entity_path: prefab.entity_path.clone(),
+                        root_entity: *new_root_entity,
+                    })
+            });
+            if let Some(new_prefab) = new_prefab {
+                prefabs.add_component_unchecked(*new_id, new_prefab);
+            } else {
+                prefabs.delete(*new_id);
+            }
+        }
+    });
+    world.run(
+        |mut hierarchy: UniqueViewMut<Hierarchy>,
+         mut childrens: ViewMut<Children>,
+         mut parents: ViewMut<Parent>,
+         entities: EntitiesView| {
+            crate::hierarchy::attach_after(
+                &mut hierarchy,
+                &mut childrens,
+                &mut parents,
+                &entities,
+                new_entity,
+                parent,
+                entity,
+            );
+        },
+    );
+    new_entity
+}
+
 /// Create old_id_to_new_id map, the new ids are generated by adding new entities in ecs world.
 fn create_old_id_to_new_id_map(
     entities_data: &EntitiesData,
@@ -552,7 +912,7 @@ fn create_components_in_world(
     for (eid, entity_data) in entities_data {
         for (component_name, component_data) in &entity_data.components {
             if let Some(component_fn) = component_registry.get(component_name.as_str()) {
-                (component_fn.create_with_data)(world, *eid, component_data);
+                component_fn.create_with_data(world, *eid, component_data);
             }
         }
     }
@@ -681,6 +1041,55 @@ pub(crate) fn load_prefab_system(
     }
 }
 
+/// Create the entities of prefab_asset in world at runtime, the root entity is placed at transform.
+/// [Prefab] components are updated like loading a prefab in steel-editor.
+/// Returns the root entity, or EntityId::dead() if failed to load the prefab.
+/// Game systems can use [PrefabAssets::instantiate] instead.
+pub(crate) fn instantiate_prefab(
+    world: &mut World,
+    component_registry: &ComponentRegistry,
+    prefab_asset: AssetId,
+    transform: Transform,
+) -> EntityId {
+    let get_prefab_data_fn = |prefab_asset: AssetId| {
+        world.run(
+            |mut prefab_assets: UniqueViewMut<PrefabAssets>,
+             mut asset_manager: UniqueViewMut<AssetManager>,
+             platform: UniqueView<Platform>| {
+                prefab_assets.get_prefab_data(prefab_asset, &mut asset_manager, &platform)
+            },
+        )
+    };
+    let Some(prefab_data) = get_prefab_data_fn(prefab_asset) else {
+        log::warn!("instantiate_prefab: failed to get prefab data of {prefab_asset:?}");
+        return EntityId::dead();
+    };
+    let (mut entities_data, entity_map) = prefab_data.to_entities_data(get_prefab_data_fn);
+    let Some(root) = entities_data.root() else {
+        log::warn!("instantiate_prefab: there is no root entity in prefab {prefab_asset:?}");
+        return EntityId::dead();
+    };
+    entities_data
+        .get_mut(&root)
+        .expect("root must be in entities_data")
+        .components
+        .insert(Transform::name().into(), transform.get_data());
+    let old_id_to_new_id = entities_data.add_to_world(world, component_registry);
+
+    let prefab_root_entity = old_id_to_new_id[&root];
+    world.add_unique(LoadPrefabParam {
+        prefab_root_entity,
+        prefab_asset,
+        entity_id_to_prefab_entity_id_with_path: entity_map
+            .into_iter()
+            .map(|(entity_id_with_path, old_id)| (old_id_to_new_id[&old_id], entity_id_with_path))
+            .collect(),
+    });
+    world.run(load_prefab_system);
+    world.remove_unique::<LoadPrefabParam>().unwrap();
+    prefab_root_entity
+}
+
 /// Parameters for [load_scene_prefabs_system].
 #[derive(Unique)]
 pub(crate) struct LoadScenePrefabsParam {
@@ -739,12 +1148,57 @@ struct PrefabAsset {
 }
 
 #[derive(Unique, Default)]
-/// Cache [PrefabData] in assets.
+/// Cache [PrefabData] in assets. You can also use PrefabAssets::instantiate to spawn prefabs at runtime.
+/// # Example
+/// ```rust
+/// use shipyard::UniqueViewMut;
+/// use steel::{asset::AssetId, data::PrefabAssets, transform::Transform};
+///
+/// fn fire(mut prefab_assets: UniqueViewMut<PrefabAssets>, bullet: AssetId) {
+///     prefab_assets.instantiate(bullet, Transform::default());
+/// }
+/// ```
 pub struct PrefabAssets {
     prefabs: HashMap<AssetId, PrefabAsset>,
+    to_instantiate: Vec<(AssetId, Transform)>,
+    instantiated: Vec<(AssetId, EntityId)>,
 }
 
 impl PrefabAssets {
+    /// Instantiate the entities of prefab_asset at the start of next frame, the root entity is placed at transform.
+    /// Use PrefabAssets::instantiated to get the root entity in next frame.
+    pub fn instantiate(&mut self, prefab_asset: AssetId, transform: Transform) {
+        self.to_instantiate.push((prefab_asset, transform));
+    }
+
+    /// Get the (prefab asset, root entity) of prefabs which are instantiated at the start of this frame
+    /// by PrefabAssets::instantiate. The prefabs which are failed to load are not included.
+    pub fn instantiated(&self) -> &[(AssetId, EntityId)] {
+        &self.instantiated
+    }
+
+    /// Instantiate the prefabs which are requested by PrefabAssets::instantiate.
+    pub(crate) fn maintain_system(world: &mut World, component_registry: &ComponentRegistry) {
+        let to_instantiate = world.run(|mut prefab_assets: UniqueViewMut<PrefabAssets>| {
+            prefab_assets.instantiated.clear();
+            std::mem::take(&mut prefab_assets.to_instantiate)
+        });
+        if to_instantiate.is_empty() {
+            return;
+        }
+        let instantiated = to_instantiate
+            .into_iter()
+            .map(|(prefab_asset, transform)| {
+                let root = instantiate_prefab(world, component_registry, prefab_asset, transform);
+                (prefab_asset, root)
+            })
+            .filter(|(_, root)| *root != EntityId::dead())
+            .collect();
+        world.run(|mut prefab_assets: UniqueViewMut<PrefabAssets>| {
+            prefab_assets.instantiated = instantiated;
+        });
+    }
+
     pub fn get_prefab_data(
         &mut self,
         asset_id: AssetId,
@@ -0,0 +1,275 @@
+//! Dynamic components are described by a [Data] schema at runtime instead of a rust type, so that
+//! data-driven tools and mods can add components which the game does not know about at compile time.
+//! Register them with [crate::app::SteelApp::register_dynamic_component], then they can be created,
+//! edited, saved and loaded like other components. The data of all dynamic components of an entity
+//! is stored in its [DynamicComponents] component.
+
+use crate::data::{ComponentFn, Data, EntitiesData, WorldData};
+use indexmap::IndexMap;
+use shipyard::{
+    Component, EntitiesView, EntityId, Get, IntoIter, IntoWithId, View, ViewMut, World,
+};
+use std::{
+    collections::BTreeSet,
+    sync::{Arc, Mutex},
+};
+
+/// The DynamicComponents component stores the data of all dynamic components of an entity, key is component name.
+/// # Example
+/// ```rust
+/// use steel::{app::{App, SteelApp}, data::{Data, Limit, Value}, dynamic::DynamicComponents};
+/// use shipyard::{IntoIter, View};
+///
+/// #[no_mangle]
+/// pub fn create() -> Box<dyn App> {
+///     let health = Data::new()
+///         .insert_with_limit("hp", Value::Int32(100), Limit::Int32Range(0..=100));
+///     SteelApp::new().register_dynamic_component("Health", health).boxed()
+/// }
+///
+/// fn print_health_system(dynamic_components: View<DynamicComponents>) {
+///     for dynamic_components in dynamic_components.iter() {
+///         if let Some(Value::Int32(hp)) = dynamic_components.get("Health").and_then(|data| data.get("hp")) {
+///             log::info!("hp: {hp}");
+///         }
+///     }
+/// }
+/// ```
+#[derive(Component, Default, Debug, Clone)]
+pub struct DynamicComponents(pub IndexMap<String, Data>);
+
+impl DynamicComponents {
+    /// Get the data of the dynamic component named name.
+    pub fn get(&self, name: impl AsRef<str>) -> Option<&Data> {
+        self.0.get(name.as_ref())
+    }
+
+    /// Get the mutable data of the dynamic component named name.
+    pub fn get_mut(&mut self, name: impl AsRef<str>) -> Option<&mut Data> {
+        self.0.get_mut(name.as_ref())
+    }
+}
+
+/// Names of dynamic components are &'static str like the names of other components, so they are interned
+/// here to leak every name only once no matter how many times it is registered.
+static NAMES: Mutex<BTreeSet<&'static str>> = Mutex::new(BTreeSet::new());
+
+/// Get the interned &'static str of name, which is leaked if name is not interned yet.
+pub(crate) fn intern(name: String) -> &'static str {
+    let mut names = NAMES.lock().unwrap();
+    if let Some(interned) = names.get(name.as_str()) {
+        return interned;
+    }
+    let interned: &'static str = Box::leak(name.into_boxed_str());
+    names.insert(interned);
+    interned
+}
+
+/// Create the [ComponentFn] of the dynamic component named name which is described by schema.
+/// The fn pointers do nothing because the methods of ComponentFn use [ComponentFn::dynamic] instead.
+pub(crate) fn component_fn(name: &'static str, schema: Data) -> ComponentFn {
+    ComponentFn {
+        create: |_, _| (),
+        create_with_data: |_, _, _| (),
+        destroy: |_, _| (),
+        save_to_data: |_, _| (),
+        save_entity_to_data: |_, _, _| (),
+        load_from_data: |_, _| (),
+        load_entity_from_data: |_, _, _| (),
+        contains: |_, _| false,
+        count: |_| 0,
+        default_data: Data::new,
+        dynamic: Some(DynamicComponent {
+            name,
+            schema: Arc::new(schema),
+        }),
+    }
+}
+
+/// DynamicComponent is the name and schema of a dynamic component, which implements the functions of
+/// [ComponentFn] for it.
+#[derive(Debug, Clone)]
+pub struct DynamicComponent {
+    name: &'static str,
+    schema: Arc<Data>,
+}
+
+impl DynamicComponent {
+    /// The name of this dynamic component.
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// The schema which describes this dynamic component.
+    pub fn schema(&self) -> &Data {
+        &self.schema
+    }
+
+    pub(crate) fn create(&self, world: &mut World, entity: EntityId) {
+        insert(world, entity, self.name, self.default_data())
+    }
+
+    pub(crate) fn create_with_data(&self, world: &mut World, entity: EntityId, data: &Data) {
+        insert(
+            world,
+            entity,
+            self.name,
+            from_schema(self.name, &self.schema, data),
+        )
+    }
+
+    pub(crate) fn destroy(&self, world: &mut World, entity: EntityId) {
+        remove(world, entity, self.name)
+    }
+
+    pub(crate) fn save_to_data(&self, world_data: &mut WorldData, world: &World) {
+        world.run(|dynamic_components: View<DynamicComponents>| {
+            for (e, dynamic_components) in dynamic_components.iter().with_id() {
+                if let Some(data) = dynamic_components.get(self.name) {
+                    world_data
+                        .entities
+                        .entry(e)
+                        .or_default()
+                        .components
+                        .insert(self.name.into(), data.clone());
+                }
+            }
+        })
+    }
+
+    pub(crate) fn save_entity_to_data(
+        &self,
+        entities_data: &mut EntitiesData,
+        world: &World,
+        entity: EntityId,
+    ) {
+        world.run(|dynamic_components: View<DynamicComponents>| {
+            if let Some(data) = dynamic_components
+                .get(entity)
+                .ok()
+                .and_then(|dynamic_components| dynamic_components.get(self.name))
+            {
+                entities_data
+                    .entry(entity)
+                    .or_default()
+                    .components
+                    .insert(self.name.into(), data.clone());
+            }
+        })
+    }
+
+    pub(crate) fn load_from_data(&self, world: &mut World, world_data: &WorldData) {
+        world.run(|mut dynamic_components: ViewMut<DynamicComponents>| {
+            for (e, dynamic_components) in (&mut dynamic_components).iter().with_id() {
+                if let (Some(data), Some(component_data)) = (
+                    dynamic_components.get_mut(self.name),
+                    world_data
+                        .entities
+                        .get(&e)
+                        .and_then(|entity_data| entity_data.components.get(self.name)),
+                ) {
+                    *data = from_schema(self.name, &self.schema, component_data);
+                }
+            }
+        })
+    }
+
+    pub(crate) fn load_entity_from_data(
+        &self,
+        world: &mut World,
+        entity: EntityId,
+        component_data: &Data,
+    ) {
+        world.run(|mut dynamic_components: ViewMut<DynamicComponents>| {
+            if let Ok(mut dynamic_components) = (&mut dynamic_components).get(entity) {
+                if let Some(data) = dynamic_components.get_mut(self.name) {
+                    *data = from_schema(self.name, &self.schema, component_data);
+                }
+            }
+        })
+    }
+
+    pub(crate) fn contains(&self, world: &World, entity: EntityId) -> bool {
+        world.run(|dynamic_components: View<DynamicComponents>| {
+            dynamic_components
+                .get(entity)
+                .is_ok_and(|dynamic_components| dynamic_components.0.contains_key(self.name))
+        })
+    }
+
+    pub(crate) fn count(&self, world: &World) -> usize {
+        world.run(|dynamic_components: View<DynamicComponents>| {
+            dynamic_components
+                .iter()
+                .filter(|dynamic_components| dynamic_components.0.contains_key(self.name))
+                .count()
+        })
+    }
+
+    pub(crate) fn default_data(&self) -> Data {
+        self.schema.as_ref().clone()
+    }
+}
+
+/// Create the data of a dynamic component from schema, values in schema are replaced by the values in data
+/// which have the same names and value types, then the values are validated by the limits in schema.
+fn from_schema(name: &str, schema: &Data, data: &Data) -> Data {
+    let mut result = schema.clone();
+    for (value_name, value) in result.values.iter_mut() {
+        match data.values.get(value_name) {
+            Some(v) if std::mem::discriminant(v) == std::mem::discriminant(value) => {
+                *value = v.clone()
+            }
+            Some(v) => log::warn!(
+                "dynamic::from_schema: value {value_name} in {name} has wrong type: {v:?}, expected: {value:?}"
+            ),
+            None => (),
+        }
+    }
+    match result.validate(&schema.limits) {
+        Some((fixed_data, names)) => {
+            for value_name in names {
+                log::warn!(
+                    "dynamic::from_schema: invalid value {value_name} in {name}: {:?}, fixed to {:?}",
+                    result.get(&value_name),
+                    fixed_data.get(&value_name)
+                );
+            }
+            fixed_data
+        }
+        None => result,
+    }
+}
+
+/// Insert the dynamic component named name with data to entity, the old data is replaced if it exists.
+fn insert(world: &mut World, entity: EntityId, name: &str, data: Data) {
+    world.run(
+        |entities: EntitiesView, mut dynamic_components: ViewMut<DynamicComponents>| {
+            if let Ok(mut dynamic_components) = (&mut dynamic_components).get(entity) {
+                dynamic_components.0.insert(name.into(), data);
+            } else {
+                entities.add_component(
+                    entity,
+                    &mut dynamic_components,
+                    DynamicComponents(IndexMap::from([(name.into(), data)])),
+                );
+            }
+        },
+    )
+}
+
+/// Remove the dynamic component named name from entity,
+/// [DynamicComponents] is also removed if there is no dynamic component left.
+fn remove(world: &mut World, entity: EntityId, name: &str) {
+    let empty = world.run(|mut dynamic_components: ViewMut<DynamicComponents>| {
+        (&mut dynamic_components)
+            .get(entity)
+            .is_ok_and(|mut dynamic_components| {
+                dynamic_components.0.shift_remove(name);
+                dynamic_components.0.is_empty()
+            })
+    });
+    if empty {
+        world.delete_component::<DynamicComponents>(entity);
+    }
+}
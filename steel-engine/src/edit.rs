@@ -18,8 +18,14 @@ use steel_common::data::Data;
 ///     pub int: i32,
 ///     #[edit(limit = "Limit::ReadOnly", name = "f32_renamed")]
 ///     pub float: f32,
+///     #[edit(step = 8)] // only multiples of 8 can be set in steel-editor
+///     pub grid_size: i32,
+///     #[edit(asset = "texture")] // only texture assets can be selected in steel-editor
+///     pub texture: steel::asset::AssetId,
 ///     pub string: String,
 ///     pub vec3: glam::Vec3,
+///     pub spawn_delay: steel::data::MinMax<f32>, // min <= max is guaranteed in steel-editor
+///     pub damage: std::ops::Range<i32>, // Range<f32> and Range<i32> are edited like MinMax
 ///     pub other: Other, // not supported field is ignored
 /// }
 /// ```
@@ -1,7 +1,7 @@
 use crate::edit::Edit;
 use shipyard::{
     AllStoragesViewMut, Component, EntitiesView, EntityId, Get, Remove, Unique, UniqueViewMut,
-    ViewMut,
+    View, ViewMut,
 };
 use std::collections::HashSet;
 use steel_common::data::{Data, Limit, Value};
@@ -295,6 +295,31 @@ pub fn attach_after(
     );
 }
 
+/// Swap an entity with its previous sibling if up is true, or with its next sibling if up is false,
+/// the parent of the entity is unchanged. Does nothing if the entity is already the first or the last child.
+/// This function must be called after hierarchy_maintain_system, or may panic.
+pub fn move_sibling(
+    hierarchy: &mut UniqueViewMut<Hierarchy>,
+    childrens: &mut ViewMut<Children>,
+    parents: &View<Parent>,
+    eid: EntityId,
+    up: bool,
+) {
+    let siblings = if let Ok(parent) = parents.get(eid) {
+        &mut (&mut *childrens).get(**parent).unwrap().0
+    } else {
+        &mut hierarchy.roots
+    };
+    let Some(i) = siblings.iter().position(|e| *e == eid) else {
+        return;
+    };
+    let j = if up { i.checked_sub(1) } else { Some(i + 1) };
+    if let Some(j) = j.filter(|j| *j < siblings.len()) {
+        log::trace!("Move {eid:?} from {i} to {j} among siblings");
+        siblings.swap(i, j);
+    }
+}
+
 /// Attach a child to a parent adjacent to adjacent entity.
 /// ### If prev is true:
 /// attach previous to adjacent. If adjacent is EntityId::dead(), attach as the last child.
@@ -0,0 +1,104 @@
+use crate::{edit::Edit, physics2d::RigidBody2D, time::Time, transform::Transform};
+use shipyard::{
+    Component, EntityId, Get, IntoIter, IntoWithId, Unique, UniqueView, UniqueViewMut, View,
+    ViewMut,
+};
+use std::collections::HashMap;
+use steel_common::data::{Data, Value};
+
+/// The InterpolateTransform component makes the [Transform] of an entity interpolated when rendering,
+/// see [TransformInterpolation]. Entities with [RigidBody2D] are interpolated without this component.
+#[derive(Component, Edit, Default, Debug)]
+pub struct InterpolateTransform;
+
+/// TransformInterpolation unique smooths rendering when [Time::update_rate] makes [crate::app::Schedule::Update]
+/// run less often than frames are rendered. If enabled, the [Transform]s of entities with [RigidBody2D] or
+/// [InterpolateTransform] after the last two updates are recorded, and during [crate::app::Schedule::PostUpdate]
+/// they are replaced by the blend of the two by [Time::update_alpha], so that the draw data built in PostUpdate
+/// moves smoothly. The recorded Transforms are restored after PostUpdate, so changes to these Transforms made
+/// by PostUpdate systems are discarded.
+#[derive(Unique, Default)]
+pub struct TransformInterpolation {
+    /// Whether to interpolate transforms, this has no effect if [Time::update_rate] is not greater than 0.
+    pub enabled: bool,
+    /// The (previous, current) Transforms after the last two updates.
+    states: HashMap<EntityId, (Transform, Transform)>,
+    /// The current Transforms which are replaced during PostUpdate.
+    replaced: Vec<(EntityId, Transform)>,
+}
+
+impl Edit for TransformInterpolation {
+    fn name() -> &'static str {
+        "TransformInterpolation"
+    }
+
+    fn get_data(&self) -> Data {
+        Data::new().insert("enabled", Value::Bool(self.enabled))
+    }
+
+    fn set_data(&mut self, data: &Data) {
+        if let Some(Value::Bool(v)) = data.get("enabled") {
+            self.enabled = *v
+        }
+    }
+}
+
+/// Record the Transforms of interpolated entities after every [crate::app::Schedule::Update].
+pub fn transform_interpolation_record_system(
+    mut interpolation: UniqueViewMut<TransformInterpolation>,
+    transforms: View<Transform>,
+    rb2d: View<RigidBody2D>,
+    interpolate_transforms: View<InterpolateTransform>,
+) {
+    if !interpolation.enabled {
+        interpolation.states.clear();
+        return;
+    }
+    let mut states = HashMap::new();
+    for (eid, transform) in transforms.iter().with_id() {
+        if rb2d.contains(eid) || interpolate_transforms.contains(eid) {
+            let previous = match interpolation.states.remove(&eid) {
+                Some((_, current)) => current,
+                None => transform.clone(),
+            };
+            states.insert(eid, (previous, transform.clone()));
+        }
+    }
+    interpolation.states = states;
+}
+
+/// Replace the Transforms of interpolated entities by the blend of the last two updates before
+/// [crate::app::Schedule::PostUpdate], see [TransformInterpolation].
+pub fn transform_interpolation_blend_system(
+    mut interpolation: UniqueViewMut<TransformInterpolation>,
+    time: UniqueView<Time>,
+    mut transforms: ViewMut<Transform>,
+) {
+    let interpolation = interpolation.as_mut();
+    if !interpolation.enabled || time.update_rate <= 0.0 {
+        return;
+    }
+    let alpha = time.update_alpha();
+    for (eid, (previous, current)) in &interpolation.states {
+        if let Ok(mut transform) = (&mut transforms).get(*eid) {
+            // skip the transform which is changed after the last update, like teleported in PreUpdate
+            if *transform != *current {
+                continue;
+            }
+            *transform = previous.lerp(current, alpha);
+            interpolation.replaced.push((*eid, current.clone()));
+        }
+    }
+}
+
+/// Restore the Transforms replaced by [transform_interpolation_blend_system] after [crate::app::Schedule::PostUpdate].
+pub fn transform_interpolation_restore_system(
+    mut interpolation: UniqueViewMut<TransformInterpolation>,
+    mut transforms: ViewMut<Transform>,
+) {
+    for (eid, current) in interpolation.replaced.drain(..) {
+        if let Ok(mut transform) = (&mut transforms).get(eid) {
+            *transform = current;
+        }
+    }
+}
@@ -1,22 +1,33 @@
 //! The [steel game engine](https://github.com/SSSxCCC/steel) core library.
 
+pub mod animation;
 pub mod app;
 pub mod asset;
+pub mod behavior;
 pub mod camera;
 pub mod data;
+pub mod dynamic;
 pub mod edit;
 pub mod hierarchy;
 pub mod input;
+pub mod interpolation;
 pub mod name;
 pub mod physics2d;
 pub mod platform;
+pub mod pool;
+pub mod reload;
 pub mod render;
 pub mod scene;
 pub mod shape;
 pub mod shape2d;
+pub mod spatial;
+pub mod tag;
+#[cfg(any(test, feature = "test-util"))]
+pub mod test_util;
 pub mod time;
 pub mod transform;
 pub mod ui;
+pub mod window;
 pub mod ext {
     pub use steel_common::ext::*;
 }
@@ -29,3 +40,10 @@ pub fn setup_logger(logger: &'static dyn Log, level: LevelFilter) -> Result<(),
     log::set_max_level(level);
     log::set_logger(logger)
 }
+
+/// This function is used by steel-editor to re-seed reload-sensitive statics after loading steel-dynlib,
+/// see [reload] for more information.
+#[no_mangle]
+pub fn on_library_loaded() {
+    reload::on_library_loaded()
+}
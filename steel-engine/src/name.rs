@@ -1,5 +1,6 @@
 use crate::edit::Edit;
-use shipyard::Component;
+use shipyard::{AddComponent, Component, EntityId, Get, IntoIter, IntoWithId, View, ViewMut};
+use std::collections::HashSet;
 use steel_common::data::{Data, Value};
 
 /// The component that defines the name of an entity.
@@ -12,6 +13,37 @@ impl Name {
     pub fn new(name: impl Into<String>) -> Self {
         name.into().into()
     }
+
+    /// Get the name of entity eid. Returns None if eid does not have a Name component.
+    pub fn get<'a>(eid: EntityId, names: &'a View<Name>) -> Option<&'a str> {
+        names.get(eid).ok().map(|name| name.as_str())
+    }
+
+    /// Set the name of entity eid, a Name component is added if eid does not have one.
+    pub fn set(eid: EntityId, name: impl Into<String>, names: &mut ViewMut<Name>) {
+        if let Ok(mut n) = names.get(eid) {
+            n.0 = name.into();
+        } else {
+            names.add_component_unchecked(eid, Name::new(name));
+        }
+    }
+
+    /// Find the first entity whose name is equal to name.
+    pub fn find(name: impl AsRef<str>, names: &View<Name>) -> Option<EntityId> {
+        names
+            .iter()
+            .with_id()
+            .find_map(|(eid, n)| (n.as_str() == name.as_ref()).then_some(eid))
+    }
+
+    /// Create a name like "{prefix} {N}" which is not used by any entity, N starts from 1.
+    pub fn unique_name(prefix: impl AsRef<str>, names: &View<Name>) -> String {
+        let used_names = names.iter().map(|n| n.as_str()).collect::<HashSet<_>>();
+        (1..)
+            .map(|n| format!("{} {n}", prefix.as_ref()))
+            .find(|name| !used_names.contains(name.as_str()))
+            .unwrap()
+    }
 }
 
 impl std::ops::Deref for Name {
@@ -2,9 +2,10 @@ use crate::{
     app::{Plugin, Schedule, SteelApp},
     edit::Edit,
     hierarchy::Parent,
+    pool::Pooled,
     render::canvas::Canvas,
     shape2d::Shape2D,
-    time::Time,
+    time::{FixedStepAccumulator, Time},
     transform::Transform,
 };
 use glam::{Affine2, Quat, Vec2, Vec3, Vec4};
@@ -16,6 +17,8 @@ use shipyard::{
 use std::{collections::HashMap, num::NonZero};
 use steel_common::data::{Data, Limit, Value};
 
+pub use crate::time::MAX_FIXED_STEPS;
+
 /// Maximum difference between two f32 values to be considered equal.
 pub const F32_MAX_DIFF: f32 = 0.0001;
 
@@ -25,6 +28,8 @@ pub const F32_MAX_DIFF: f32 = 0.0001;
 pub struct RigidBody2D {
     handle: RigidBodyHandle,
     pub body_type: RigidBodyType,
+    /// If true, the rigid body can not rotate, which is useful for top-down characters that should not tumble.
+    pub lock_rotation: bool,
     last_transform: Option<(Vec2, f32)>, // translation and rotation
 }
 
@@ -34,6 +39,7 @@ impl RigidBody2D {
         RigidBody2D {
             handle: RigidBodyHandle::invalid(),
             body_type,
+            lock_rotation: false,
             last_transform: None,
         }
     }
@@ -73,6 +79,7 @@ impl Default for RigidBody2D {
         Self {
             handle: RigidBodyHandle::invalid(),
             body_type: RigidBodyType::Dynamic,
+            lock_rotation: false,
             last_transform: None,
         }
     }
@@ -100,12 +107,16 @@ impl Edit for RigidBody2D {
                     (3, "KinematicVelocityBased".into()),
                 ]),
             )
+            .insert("lock_rotation", Value::Bool(self.lock_rotation))
     }
 
     fn set_data(&mut self, data: &Data) {
         if let Some(Value::Int32(i)) = data.get("body_type") {
             self.body_type = Self::i32_to_rigid_body_type(i)
         }
+        if let Some(Value::Bool(b)) = data.get("lock_rotation") {
+            self.lock_rotation = *b
+        }
     }
 }
 
@@ -215,6 +226,12 @@ pub struct Physics2DManager {
     pub collider_set: ColliderSet,
     pub gravity: Vector<Real>,
     pub integration_parameters: IntegrationParameters,
+    /// If greater than 0, physics world is stepped with this fixed timestep, zero or more times per update
+    /// so that simulation speed does not depend on frame rate, see [MAX_FIXED_STEPS]. Otherwise physics world
+    /// is stepped once per update with integration_parameters.dt following [Time::delta], clamped to at most 0.1.
+    pub fixed_dt: f32,
+    /// The [Time::delta] which is not simulated yet when [Physics2DManager::fixed_dt] is used.
+    fixed_dt_accumulator: FixedStepAccumulator,
     pub physics_pipeline: PhysicsPipeline,
     pub island_manager: IslandManager,
     pub broad_phase: DefaultBroadPhase,
@@ -225,6 +242,8 @@ pub struct Physics2DManager {
     pub physics_hooks: Box<dyn PhysicsHooks>,
     pub event_handler: Box<dyn EventHandler>,
     pub debug_render_pipeline: DebugRenderPipeline,
+    /// Whether to draw physics debug lines in the scene window of steel-editor, see [physics2d_debug_render_system].
+    pub debug_render: bool,
 }
 
 impl Physics2DManager {
@@ -246,6 +265,52 @@ impl Physics2DManager {
             self.event_handler.as_ref(),
         );
     }
+
+    /// Get the rigid body in physics world of the [RigidBody2D] component of entity,
+    /// None if the entity does not have RigidBody2D or the rigid body has not been created yet.
+    pub fn rigid_body_mut(
+        &mut self,
+        rb2d: &View<RigidBody2D>,
+        entity: EntityId,
+    ) -> Option<&mut RigidBody> {
+        let handle = rb2d.get(entity).ok()?.handle;
+        self.rigid_body_set.get_mut(handle)
+    }
+
+    /// Set the angular velocity in radians per second of the rigid body of entity, see [Physics2DManager::rigid_body_mut].
+    /// Returns false if the rigid body does not exist.
+    pub fn set_angular_velocity(
+        &mut self,
+        rb2d: &View<RigidBody2D>,
+        entity: EntityId,
+        angvel: f32,
+    ) -> bool {
+        self.rigid_body_mut(rb2d, entity)
+            .map(|rigid_body| rigid_body.set_angvel(angvel, true))
+            .is_some()
+    }
+
+    /// Apply a torque impulse to the rigid body of entity, which changes its angular velocity according to its
+    /// angular inertia, see [Physics2DManager::rigid_body_mut]. Returns false if the rigid body does not exist.
+    pub fn apply_torque_impulse(
+        &mut self,
+        rb2d: &View<RigidBody2D>,
+        entity: EntityId,
+        torque_impulse: f32,
+    ) -> bool {
+        self.rigid_body_mut(rb2d, entity)
+            .map(|rigid_body| rigid_body.apply_torque_impulse(torque_impulse, true))
+            .is_some()
+    }
+
+    /// Get the 2D axis-aligned bounding box (min, max) enclosing all colliders, None if there is no collider.
+    pub fn collider_bounds(&self) -> Option<(Vec2, Vec2)> {
+        self.collider_set
+            .iter()
+            .map(|(_, collider)| collider.compute_aabb())
+            .map(|aabb| (Vec2::from(aabb.mins), Vec2::from(aabb.maxs)))
+            .reduce(|(min1, max1), (min2, max2)| (min1.min(min2), max1.max(max2)))
+    }
 }
 
 impl Default for Physics2DManager {
@@ -255,6 +320,8 @@ impl Default for Physics2DManager {
             collider_set: ColliderSet::new(),
             gravity: vector![0.0, -9.81],
             integration_parameters: IntegrationParameters::default(),
+            fixed_dt: 0.0,
+            fixed_dt_accumulator: FixedStepAccumulator::default(),
             physics_pipeline: PhysicsPipeline::new(),
             island_manager: IslandManager::new(),
             broad_phase: DefaultBroadPhase::new(),
@@ -265,6 +332,7 @@ impl Default for Physics2DManager {
             physics_hooks: Box::new(()),
             event_handler: Box::new(()),
             debug_render_pipeline: DebugRenderPipeline::default(),
+            debug_render: true,
         }
     }
 }
@@ -277,7 +345,13 @@ impl Edit for Physics2DManager {
     fn get_data(&self) -> Data {
         Data::new()
             .insert("gravity", Value::Vec2(self.gravity.into()))
-            // Currently integration_parameters.dt is dynamically changed so that it should not be edited
+            .insert("debug_render", Value::Bool(self.debug_render))
+            // integration_parameters.dt is overwritten in every step, so we edit fixed_dt instead
+            .insert_with_limit(
+                "fixed_dt",
+                Value::Float32(self.fixed_dt),
+                Limit::Float32Range(0.0..=f32::MAX),
+            )
             .insert(
                 "min_ccd_dt",
                 Value::Float32(self.integration_parameters.min_ccd_dt),
@@ -358,6 +432,12 @@ impl Edit for Physics2DManager {
         if let Some(Value::Vec2(v)) = data.get("gravity") {
             self.gravity = (*v).into()
         }
+        if let Some(Value::Bool(v)) = data.get("debug_render") {
+            self.debug_render = *v
+        }
+        if let Some(Value::Float32(v)) = data.get("fixed_dt") {
+            self.fixed_dt = *v
+        }
         if let Some(Value::Float32(v)) = data.get("min_ccd_dt") {
             self.integration_parameters.min_ccd_dt = *v
         }
@@ -467,6 +547,7 @@ pub fn physics2d_maintain_system(
     for (e, rb2d) in rb2d.inserted_or_modified_mut().iter().with_id() {
         if let Some(rigid_body) = physics2d_manager.rigid_body_set.get_mut(rb2d.handle) {
             rigid_body.set_body_type(rb2d.body_type, true);
+            rigid_body.lock_rotations(rb2d.lock_rotation, true);
         } else {
             if !transforms.contains(e) {
                 transforms.add_component_unchecked(e, Transform::default());
@@ -479,10 +560,11 @@ pub fn physics2d_maintain_system(
             )
             .unwrap();
             let (_, rotation, position) = model_without_scale.to_scale_angle_translation();
-            let rigid_body = RigidBodyBuilder::new(rb2d.body_type)
+            let mut rigid_body = RigidBodyBuilder::new(rb2d.body_type)
                 .translation(position.into())
                 .rotation(rotation)
                 .build();
+            rigid_body.lock_rotations(rb2d.lock_rotation, true);
             rb2d.handle = physics2d_manager.rigid_body_set.insert(rigid_body);
         }
 
@@ -555,6 +637,36 @@ pub fn physics2d_maintain_system(
 }
 
 /// Update rigid bodies and colliders according to Transform component.
+/// Disable the bodies and colliders of entities which are waiting in [crate::pool::EntityPool],
+/// and enable them with zero velocity when they are taken from the pool.
+fn physics2d_update_enabled(
+    physics2d_manager: &mut Physics2DManager,
+    rb2d: &ViewMut<RigidBody2D>,
+    col2d: &ViewMut<Collider2D>,
+    pooled: &View<Pooled>,
+) {
+    for (e, rb2d) in rb2d.iter().with_id() {
+        if let Some(rigid_body) = physics2d_manager.rigid_body_set.get_mut(rb2d.handle) {
+            let enabled = !pooled.contains(e);
+            if rigid_body.is_enabled() != enabled {
+                rigid_body.set_enabled(enabled);
+                if enabled {
+                    rigid_body.set_linvel(vector![0.0, 0.0], true);
+                    rigid_body.set_angvel(0.0, true);
+                }
+            }
+        }
+    }
+    for (e, col2d) in col2d.iter().with_id() {
+        if let Some(collider) = physics2d_manager.collider_set.get_mut(col2d.handle) {
+            let enabled = !pooled.contains(e);
+            if collider.is_enabled() != enabled {
+                collider.set_enabled(enabled);
+            }
+        }
+    }
+}
+
 fn physics2d_update_from_transform(
     physics2d_manager: &mut Physics2DManager,
     transforms: &ViewMut<Transform>,
@@ -612,14 +724,21 @@ pub fn physics2d_update_system(
     mut col2d: ViewMut<Collider2D>,
     mut transforms: ViewMut<Transform>,
     parents: View<Parent>,
+    pooled: View<Pooled>,
 ) {
     let physics2d_manager = physics2d_manager.as_mut();
 
-    // dynamically change integration_parameters.dt according to time.delta()
-    physics2d_manager.integration_parameters.dt = if time.delta() < 0.1 {
-        time.delta()
+    // use fixed_dt if it is set, otherwise dynamically change integration_parameters.dt according to time.delta()
+    let steps = if physics2d_manager.fixed_dt > 0.0 {
+        let fixed_dt = physics2d_manager.fixed_dt;
+        physics2d_manager.integration_parameters.dt = fixed_dt;
+        physics2d_manager
+            .fixed_dt_accumulator
+            .accumulate(time.delta(), fixed_dt)
     } else {
-        0.1
+        physics2d_manager.fixed_dt_accumulator.reset();
+        physics2d_manager.integration_parameters.dt = time.delta().min(0.1);
+        1
     };
 
     physics2d_update_from_transform(
@@ -630,7 +749,11 @@ pub fn physics2d_update_system(
         &mut col2d,
     );
 
-    physics2d_manager.update();
+    physics2d_update_enabled(physics2d_manager, &rb2d, &col2d, &pooled);
+
+    for _ in 0..steps {
+        physics2d_manager.update();
+    }
 
     let mut model_cache = Some(HashMap::new());
     let mut final_position_and_rotation = HashMap::new();
@@ -725,13 +848,43 @@ pub fn physics2d_update_system(
 
 struct DebugRenderer<'a> {
     canvas: &'a mut Canvas,
+    rigid_body_set: &'a RigidBodySet,
+}
+
+impl DebugRenderer<'_> {
+    /// The color of collider outlines: yellow for sensors, and for other colliders,
+    /// blue for dynamic bodies, purple for kinematic bodies, and green for fixed bodies.
+    fn collider_color(&self, collider: &Collider) -> Vec4 {
+        if collider.is_sensor() {
+            return Vec4::new(1.0, 0.9, 0.1, 1.0);
+        }
+        match collider
+            .parent()
+            .and_then(|handle| self.rigid_body_set.get(handle))
+            .map(|body| body.body_type())
+        {
+            Some(RigidBodyType::Dynamic) => Vec4::new(0.2, 0.6, 1.0, 1.0),
+            Some(RigidBodyType::KinematicPositionBased)
+            | Some(RigidBodyType::KinematicVelocityBased) => Vec4::new(0.8, 0.3, 0.9, 1.0),
+            Some(RigidBodyType::Fixed) | None => Vec4::new(0.2, 0.9, 0.3, 1.0),
+        }
+    }
 }
 
 impl DebugRenderBackend for DebugRenderer<'_> {
-    fn draw_line(&mut self, _: DebugRenderObject, a: Point<Real>, b: Point<Real>, color: [f32; 4]) {
+    fn draw_line(
+        &mut self,
+        object: DebugRenderObject,
+        a: Point<Real>,
+        b: Point<Real>,
+        color: [f32; 4],
+    ) {
+        let color = match object {
+            DebugRenderObject::Collider(_, collider) => self.collider_color(collider),
+            _ => Vec4::from_array(color),
+        };
         // currently we use a big z value to make sure that debug render content can be seen
         // TODO: find a better way to make sure the visiblity of debug render content
-        let color = Vec4::from_array(color);
         self.canvas.line(
             Vec3::new(a.x, a.y, 1000.0),
             Vec3::new(b.x, b.y, 1000.0),
@@ -741,14 +894,18 @@ impl DebugRenderBackend for DebugRenderer<'_> {
     }
 }
 
-/// Drawing physics debug lines to the Canvas.
+/// Drawing physics debug lines to the Canvas if [Physics2DManager::debug_render] is true.
 pub fn physics2d_debug_render_system(
     mut physics2d_manager: UniqueViewMut<Physics2DManager>,
     mut canvas: UniqueViewMut<Canvas>,
 ) {
     let physics2d_manager = physics2d_manager.as_mut();
+    if !physics2d_manager.debug_render {
+        return;
+    }
     let mut debug_render_backend = DebugRenderer {
         canvas: &mut canvas,
+        rigid_body_set: &physics2d_manager.rigid_body_set,
     };
     physics2d_manager.debug_render_pipeline.render(
         &mut debug_render_backend,
@@ -0,0 +1,123 @@
+use crate::{
+    app::{Plugin, Schedule, SteelApp},
+    edit::Edit,
+};
+use shipyard::{
+    AddComponent, Component, EntityId, IntoIter, IntoWithId, Remove, Unique, UniqueViewMut, View,
+    ViewMut,
+};
+use std::collections::HashMap;
+use steel_common::data::{Data, Value};
+
+/// The Pooled component marks an entity which is released into [EntityPool] and is waiting to be reused.
+/// A pooled entity is not drawn, its [crate::behavior::Behavior] is not called, and its
+/// [crate::physics2d::RigidBody2D] and [crate::physics2d::Collider2D] are disabled instead of removed
+/// from physics world. Your systems can skip pooled entities by iterating with `!&pooled`.
+/// Note that children of a pooled entity are not affected.
+///
+/// Pooled is saved with the kind of the pool, so that [EntityPool] can be rebuilt from Pooled components
+/// when a scene is loaded, see [entity_pool_rebuild_system].
+#[derive(Component, Edit, Default, Debug)]
+pub struct Pooled {
+    /// The kind of the pool which this entity is released into.
+    pub kind: String,
+}
+
+/// EntityPool unique recycles entities of high-churn kinds like bullets and particles, so that they do not
+/// need to be destroyed and recreated with all their components and physics bodies. Each kind of entities
+/// has its own pool, the kind is usually the name of a prefab or an archetype.
+/// # Example
+/// ```rust
+/// use shipyard::{AddComponent, EntitiesViewMut, UniqueViewMut, ViewMut};
+/// use steel::{pool::{EntityPool, Pooled}, transform::Transform};
+///
+/// fn fire(
+///     mut pool: UniqueViewMut<EntityPool>,
+///     mut pooled: ViewMut<Pooled>,
+///     mut entities: EntitiesViewMut,
+///     mut transforms: ViewMut<Transform>,
+/// ) {
+///     let bullet = pool
+///         .take("Bullet", &mut pooled)
+///         .unwrap_or_else(|| entities.add_entity((), ()));
+///     entities.add_component(bullet, &mut transforms, Transform::default());
+/// }
+///
+/// fn hit(mut pool: UniqueViewMut<EntityPool>, mut pooled: ViewMut<Pooled>, bullet: shipyard::EntityId) {
+///     pool.release("Bullet", bullet, &mut pooled);
+/// }
+/// ```
+#[derive(Unique, Default)]
+pub struct EntityPool {
+    pools: HashMap<String, Vec<EntityId>>,
+}
+
+impl EntityPool {
+    /// Take an entity of kind from pool and remove its [Pooled] component, None if the pool of kind is empty.
+    /// The components of the entity keep the values when it is released, so you should reset them.
+    pub fn take(&mut self, kind: &str, pooled: &mut ViewMut<Pooled>) -> Option<EntityId> {
+        let pool = self.pools.get_mut(kind)?;
+        while let Some(eid) = pool.pop() {
+            // skip the entity which is destroyed while it is in pool
+            if pooled.remove(eid).is_some() {
+                return Some(eid);
+            }
+        }
+        None
+    }
+
+    /// Release an entity of kind into pool by adding a [Pooled] component to it, instead of destroying it.
+    pub fn release(
+        &mut self,
+        kind: impl Into<String>,
+        eid: EntityId,
+        pooled: &mut ViewMut<Pooled>,
+    ) {
+        if pooled.contains(eid) {
+            log::warn!("EntityPool::release: entity {eid:?} is already released");
+            return;
+        }
+        let kind = kind.into();
+        pooled.add_component_unchecked(eid, Pooled { kind: kind.clone() });
+        self.pools.entry(kind).or_default().push(eid);
+    }
+
+    /// Get the number of entities of kind in pool, which may include entities destroyed while they are in pool.
+    pub fn len(&self, kind: &str) -> usize {
+        self.pools
+            .get(kind)
+            .map(|pool| pool.len())
+            .unwrap_or_default()
+    }
+
+    /// Forget all pooled entities, they still exist with [Pooled] component.
+    pub fn clear(&mut self) {
+        self.pools.clear();
+    }
+}
+
+/// Rebuild [EntityPool] from [Pooled] components when a scene is loaded, because the entities in pool
+/// are destroyed, and the loaded entities with [Pooled] component have new entity ids.
+pub fn entity_pool_rebuild_system(mut pool: UniqueViewMut<EntityPool>, pooled: View<Pooled>) {
+    pool.clear();
+    for (eid, pooled) in pooled.iter().with_id() {
+        pool.pools.entry(pooled.kind.clone()).or_default().push(eid);
+    }
+}
+
+/// The entity pool plugin. This plugin contains:
+/// - [EntityPool]
+/// - [Pooled]
+/// - [entity_pool_rebuild_system]
+pub struct EntityPoolPlugin;
+
+impl Plugin for EntityPoolPlugin {
+    fn apply(self, app: SteelApp) -> SteelApp {
+        app.add_unique(EntityPool::default())
+            .register_component::<Pooled>()
+            .add_system(
+                Schedule::SceneLoaded,
+                crate::pool::entity_pool_rebuild_system,
+            )
+    }
+}
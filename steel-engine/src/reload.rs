@@ -0,0 +1,69 @@
+//! Steel-editor reloads the game library after every compile, but global state like statics
+//! is not guaranteed to be reset by reloading a dynamic library. Register reload-sensitive
+//! statics with [register_reload_static] so that they are re-seeded after each reload.
+
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Mutex,
+};
+
+/// A registered reload-sensitive static, which is a name and a function to re-seed it.
+type ReloadStatic = (&'static str, fn());
+
+/// All registered reload-sensitive statics.
+static RELOAD_STATICS: Mutex<Vec<ReloadStatic>> = Mutex::new(Vec::new());
+
+/// Set to true when the library is loaded. If it is already true when the library is loaded,
+/// the global state of the library is left from the previous load.
+static LOADED: AtomicBool = AtomicBool::new(false);
+
+/// Register a reload-sensitive static named name, reseed is called to re-seed it after the game library
+/// is reloaded by steel-editor with the global state left from the previous load. Registering the same
+/// name again replaces the previous reseed function.
+/// # Example
+/// ```rust
+/// use std::sync::atomic::{AtomicU32, Ordering};
+/// use steel::reload::register_reload_static;
+///
+/// static SCORE: AtomicU32 = AtomicU32::new(0);
+///
+/// register_reload_static("SCORE", || SCORE.store(0, Ordering::Relaxed));
+/// ```
+pub fn register_reload_static(name: &'static str, reseed: fn()) {
+    let Ok(mut statics) = RELOAD_STATICS.lock() else {
+        return;
+    };
+    match statics.iter_mut().find(|(n, _)| *n == name) {
+        Some(entry) => entry.1 = reseed,
+        None => statics.push((name, reseed)),
+    }
+}
+
+/// Get the names of all registered reload-sensitive statics.
+pub fn reload_static_names() -> Vec<&'static str> {
+    RELOAD_STATICS
+        .lock()
+        .map(|statics| statics.iter().map(|(name, _)| *name).collect())
+        .unwrap_or_default()
+}
+
+/// Called by steel-editor right after the game library is loaded. If the global state of the previous
+/// load is still alive, re-seed all registered statics and warn about it.
+pub(crate) fn on_library_loaded() {
+    if !LOADED.swap(true, Ordering::SeqCst) {
+        return;
+    }
+    let statics = RELOAD_STATICS
+        .lock()
+        .map(|statics| statics.clone())
+        .unwrap_or_default();
+    log::warn!(
+        "reload::on_library_loaded: global state is left from the previous load of the game library, \
+        {} registered statics are re-seeded, other statics may be stale",
+        statics.len()
+    );
+    for (name, reseed) in statics {
+        log::debug!("reload::on_library_loaded: re-seed {name}");
+        reseed();
+    }
+}
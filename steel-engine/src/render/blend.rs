@@ -0,0 +1,69 @@
+use crate::edit::Edit;
+use shipyard::Component;
+use steel_common::data::{Data, Limit, Value};
+
+/// The BlendMode component selects how the cuboid shape or the texture of a [crate::render::renderer2d::Renderer2D]
+/// is blended with what is already drawn in the rasterization pipeline. Entities without this component use
+/// [BlendMode::Alpha]. Additive and multiply blended draws are drawn after all other draws without depth write,
+/// so they do not hide each other. Blended draws ignore [crate::render::pipeline::raytracing::material::Material],
+/// and the ray tracing pipeline draws them with the default material without blending.
+#[derive(Component, Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BlendMode {
+    /// Blend by the alpha of color, which is the default.
+    #[default]
+    Alpha,
+    /// Add the color multiplied by its alpha, which brightens what is behind. Useful for glows, fire and particles.
+    Additive,
+    /// Multiply the color with what is behind by the alpha of color, which darkens it. Useful for shadows and tints.
+    Multiply,
+}
+
+impl BlendMode {
+    /// Helper function for [Limit::Int32Enum].
+    pub fn to_i32(&self) -> i32 {
+        match self {
+            BlendMode::Alpha => 0,
+            BlendMode::Additive => 1,
+            BlendMode::Multiply => 2,
+        }
+    }
+
+    /// Helper function for [Limit::Int32Enum].
+    pub fn from_i32(i: i32) -> Self {
+        match i {
+            0 => BlendMode::Alpha,
+            1 => BlendMode::Additive,
+            2 => BlendMode::Multiply,
+            _ => Self::default(),
+        }
+    }
+
+    /// Helper function for [Limit::Int32Enum].
+    pub fn enum_vector() -> Vec<(i32, String)> {
+        vec![
+            (0, "Alpha".into()),
+            (1, "Additive".into()),
+            (2, "Multiply".into()),
+        ]
+    }
+}
+
+impl Edit for BlendMode {
+    fn name() -> &'static str {
+        "BlendMode"
+    }
+
+    fn get_data(&self) -> Data {
+        Data::new().insert_with_limit(
+            "mode",
+            Value::Int32(self.to_i32()),
+            Limit::Int32Enum(Self::enum_vector()),
+        )
+    }
+
+    fn set_data(&mut self, data: &Data) {
+        if let Some(Value::Int32(v)) = data.get("mode") {
+            *self = Self::from_i32(*v);
+        }
+    }
+}
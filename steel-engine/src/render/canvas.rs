@@ -1,23 +1,26 @@
+use super::layer::{HideInGame, RenderLayers};
 use super::{
+    blend::BlendMode,
     image::ImageAssets,
+    mesh::MeshData,
     model::ModelAssets,
     pipeline::{
         rasterization::RasterizationPipeline,
         raytracing::{material::Material, RayTracingPipeline},
     },
-    texture::TextureAssets,
+    texture::{TextureAssets, TextureTiling},
     FrameRenderInfo, RenderContext, RenderManager,
 };
-use crate::{asset::AssetManager, camera::CameraInfo};
-use glam::{Affine3A, UVec2, Vec3, Vec4};
-use shipyard::{EntityId, Unique, UniqueView, UniqueViewMut};
-use std::sync::Arc;
-use steel_common::{asset::AssetId, platform::Platform};
+use crate::{asset::AssetManager, camera::CameraInfo, hierarchy::Children, pool::Pooled};
+use glam::{Affine3A, Mat4, UVec2, Vec3, Vec4};
+use shipyard::{EntityId, Get, Unique, UniqueView, UniqueViewMut, View};
+use std::{collections::HashSet, sync::Arc};
+use steel_common::{app::WindowIndex, asset::AssetId, platform::Platform};
 use vulkano::{
-    buffer::{Buffer, BufferCreateInfo, BufferUsage},
+    buffer::{Buffer, BufferContents, BufferCreateInfo, BufferUsage},
     command_buffer::{
-        AutoCommandBufferBuilder, CommandBufferUsage, CopyImageToBufferInfo,
-        PrimaryAutoCommandBuffer, PrimaryCommandBufferAbstract,
+        AutoCommandBufferBuilder, BufferImageCopy, CommandBufferUsage, CopyImageToBufferInfo,
+        PrimaryCommandBufferAbstract,
     },
     format::Format,
     image::{view::ImageView, Image, ImageCreateInfo, ImageUsage},
@@ -40,16 +43,33 @@ pub struct Canvas {
     pub(crate) rectangles: Vec<(Affine3A, Vec4, Material, EntityId)>,
     /// (model matrix, color, eid)
     pub(crate) cicles: Vec<(Affine3A, Vec4, EntityId)>,
-    /// (texture asset, model matrix, color, material, eid)
-    pub(crate) textures: Vec<(AssetId, Affine3A, Vec4, Material, EntityId)>,
+    /// (texture asset, model matrix, color, material, texture tiling, eid)
+    pub(crate) textures: Vec<(AssetId, Affine3A, Vec4, Material, TextureTiling, EntityId)>,
     /// (model matrix, color, material, eid)
     pub(crate) cuboids: Vec<(Affine3A, Vec4, Material, EntityId)>,
     /// (model matrix, color, material, eid)
     pub(crate) spheres: Vec<(Affine3A, Vec4, Material, EntityId)>,
-    /// (model asset, texture asset, model matrix, color, material, eid)
-    pub(crate) models: Vec<(AssetId, AssetId, Affine3A, Vec4, Material, EntityId)>,
+    /// (mesh, model matrix, color, material, eid)
+    pub(crate) meshes: Vec<(Arc<MeshData>, Affine3A, Vec4, Material, EntityId)>,
+    pub(crate) models: Vec<ModelDraw>,
+    /// (blend mode, model matrix, color, eid), see [BlendMode]
+    pub(crate) blended_rectangles: Vec<(BlendMode, Affine3A, Vec4, EntityId)>,
+    /// (blend mode, texture asset, model matrix, color, texture tiling, eid), see [BlendMode]
+    pub(crate) blended_textures: Vec<(BlendMode, AssetId, Affine3A, Vec4, TextureTiling, EntityId)>,
 }
 
+/// (model asset, texture asset, normal map asset, model matrix, color, material, texture tiling, eid)
+pub(crate) type ModelDraw = (
+    AssetId,
+    AssetId,
+    AssetId,
+    Affine3A,
+    Vec4,
+    Material,
+    TextureTiling,
+    EntityId,
+);
+
 impl Canvas {
     /// Draw a point with position p, color, and [EntityId].
     pub fn point(&mut self, p: Vec3, color: Vec4, eid: EntityId) {
@@ -72,6 +92,18 @@ impl Canvas {
         self.rectangles.push((model, color, material, eid));
     }
 
+    /// Draw a rectangle with blend mode, model matrix, color, and [EntityId], see [BlendMode].
+    pub fn blended_rectangle(
+        &mut self,
+        blend_mode: BlendMode,
+        model: Affine3A,
+        color: Vec4,
+        eid: EntityId,
+    ) {
+        self.blended_rectangles
+            .push((blend_mode, model, color, eid));
+    }
+
     /// Draw a circle with model matrix, color, and [EntityId].
     /// are the center and radius of the circle. The eid is used for screen object picking.
     pub fn circle(&mut self, model: Affine3A, color: Vec4, eid: EntityId) {
@@ -87,7 +119,36 @@ impl Canvas {
         material: Material,
         eid: EntityId,
     ) {
-        self.textures.push((asset, model, color, material, eid));
+        self.texture_tiled(asset, model, color, material, TextureTiling::default(), eid);
+    }
+
+    /// Draw a texture like [Canvas::texture], and the texture coordinates are scaled and offset by tiling.
+    pub fn texture_tiled(
+        &mut self,
+        asset: AssetId,
+        model: Affine3A,
+        color: Vec4,
+        material: Material,
+        tiling: TextureTiling,
+        eid: EntityId,
+    ) {
+        self.textures
+            .push((asset, model, color, material, tiling, eid));
+    }
+
+    /// Draw a texture with blend mode, texture asset, model matrix, color, texture tiling, and [EntityId],
+    /// see [BlendMode].
+    pub fn blended_texture(
+        &mut self,
+        blend_mode: BlendMode,
+        asset: AssetId,
+        model: Affine3A,
+        color: Vec4,
+        tiling: TextureTiling,
+        eid: EntityId,
+    ) {
+        self.blended_textures
+            .push((blend_mode, asset, model, color, tiling, eid));
     }
 
     /// Draw a cuboid with model matrix, color, material, and [EntityId].
@@ -100,18 +161,237 @@ impl Canvas {
         self.spheres.push((model, color, material, eid));
     }
 
-    /// Draw a model with model asset, texture asset, model matrix, color, material, and [EntityId].
+    /// Draw a procedural mesh with model matrix, color, material, and [EntityId]. Reuse the same [Arc] of mesh
+    /// across entities so that all entities with the same mesh are drawn in one draw call, like
+    /// [crate::render::shape3d::Primitive::shared_mesh]. The vertices and indices of each mesh are uploaded
+    /// to gpu every frame like other shapes. Meshes are only drawn by the rasterization pipeline.
+    pub fn mesh(
+        &mut self,
+        mesh: Arc<MeshData>,
+        model: Affine3A,
+        color: Vec4,
+        material: Material,
+        eid: EntityId,
+    ) {
+        self.meshes.push((mesh, model, color, material, eid));
+    }
+
+    /// Draw a model with model asset, texture asset, normal map asset, model matrix, color, material, and [EntityId].
     pub fn model(
         &mut self,
         model_asset: AssetId,
         texture_asset: AssetId,
+        normal_map_asset: AssetId,
         model: Affine3A,
         color: Vec4,
         material: Material,
         eid: EntityId,
     ) {
-        self.models
-            .push((model_asset, texture_asset, model, color, material, eid));
+        self.model_tiled(
+            model_asset,
+            texture_asset,
+            normal_map_asset,
+            model,
+            color,
+            material,
+            TextureTiling::default(),
+            eid,
+        );
+    }
+
+    /// Draw a model like [Canvas::model], and the texture coordinates are scaled and offset by tiling.
+    #[allow(clippy::too_many_arguments)]
+    pub fn model_tiled(
+        &mut self,
+        model_asset: AssetId,
+        texture_asset: AssetId,
+        normal_map_asset: AssetId,
+        model: Affine3A,
+        color: Vec4,
+        material: Material,
+        tiling: TextureTiling,
+        eid: EntityId,
+    ) {
+        self.models.push((
+            model_asset,
+            texture_asset,
+            normal_map_asset,
+            model,
+            color,
+            material,
+            tiling,
+            eid,
+        ));
+    }
+
+    /// Create a new canvas which only contains the drawing data whose [EntityId] satisfies predicate.
+    pub fn filter(&self, predicate: impl Fn(EntityId) -> bool) -> Canvas {
+        Canvas {
+            points: self
+                .points
+                .iter()
+                .filter(|p| predicate(p.2))
+                .cloned()
+                .collect(),
+            lines: self
+                .lines
+                .iter()
+                .filter(|l| predicate(l[0].2))
+                .cloned()
+                .collect(),
+            triangles: self
+                .triangles
+                .iter()
+                .filter(|t| predicate(t[0].2))
+                .cloned()
+                .collect(),
+            rectangles: self
+                .rectangles
+                .iter()
+                .filter(|r| predicate(r.3))
+                .cloned()
+                .collect(),
+            cicles: self
+                .cicles
+                .iter()
+                .filter(|c| predicate(c.2))
+                .cloned()
+                .collect(),
+            textures: self
+                .textures
+                .iter()
+                .filter(|t| predicate(t.5))
+                .cloned()
+                .collect(),
+            cuboids: self
+                .cuboids
+                .iter()
+                .filter(|c| predicate(c.3))
+                .cloned()
+                .collect(),
+            spheres: self
+                .spheres
+                .iter()
+                .filter(|s| predicate(s.3))
+                .cloned()
+                .collect(),
+            meshes: self
+                .meshes
+                .iter()
+                .filter(|m| predicate(m.4))
+                .cloned()
+                .collect(),
+            models: self
+                .models
+                .iter()
+                .filter(|m| predicate(m.7))
+                .cloned()
+                .collect(),
+            blended_rectangles: self
+                .blended_rectangles
+                .iter()
+                .filter(|r| predicate(r.3))
+                .cloned()
+                .collect(),
+            blended_textures: self
+                .blended_textures
+                .iter()
+                .filter(|t| predicate(t.5))
+                .cloned()
+                .collect(),
+        }
+    }
+
+    /// Get the number of each kind of shape drawn on the canvas, used by [steel_common::app::Command::GetSceneStats].
+    pub(crate) fn draw_counts(&self) -> Vec<(String, usize)> {
+        [
+            ("Point", self.points.len()),
+            ("Line", self.lines.len()),
+            ("Triangle", self.triangles.len()),
+            (
+                "Rectangle",
+                self.rectangles.len() + self.blended_rectangles.len(),
+            ),
+            ("Circle", self.cicles.len()),
+            ("Texture", self.textures.len() + self.blended_textures.len()),
+            ("Cuboid", self.cuboids.len()),
+            ("Sphere", self.spheres.len()),
+            ("Mesh", self.meshes.len()),
+            ("Model", self.models.len()),
+        ]
+        .into_iter()
+        .map(|(name, count)| (name.to_string(), count))
+        .collect()
+    }
+
+    /// Get the axis-aligned bounding box (min, max) of all drawing data, None if nothing is drawn.
+    /// The bounds of models are approximated by their positions since their vertices are not loaded here.
+    pub(crate) fn bounds(&self) -> Option<(Vec3, Vec3)> {
+        const SQUARE: [Vec3; 4] = [
+            Vec3::new(-0.5, -0.5, 0.0),
+            Vec3::new(0.5, -0.5, 0.0),
+            Vec3::new(-0.5, 0.5, 0.0),
+            Vec3::new(0.5, 0.5, 0.0),
+        ];
+        let cube = [-0.5, 0.5].into_iter().flat_map(|x| {
+            [-0.5, 0.5]
+                .into_iter()
+                .flat_map(move |y| [-0.5, 0.5].into_iter().map(move |z| Vec3::new(x, y, z)))
+        });
+        let square_points = |model: &Affine3A| {
+            let model = *model;
+            SQUARE.into_iter().map(move |p| model.transform_point3(p))
+        };
+        let cube_points = |model: &Affine3A| {
+            let model = *model;
+            cube.clone().map(move |p| model.transform_point3(p))
+        };
+
+        let points = self
+            .points
+            .iter()
+            .map(|p| p.0)
+            .chain(self.lines.iter().flat_map(|l| l.iter().map(|v| v.0)))
+            .chain(self.triangles.iter().flat_map(|t| t.iter().map(|v| v.0)))
+            .chain(self.rectangles.iter().flat_map(|r| square_points(&r.0)))
+            .chain(self.cicles.iter().flat_map(|c| square_points(&c.0)))
+            .chain(self.textures.iter().flat_map(|t| square_points(&t.1)))
+            .chain(self.cuboids.iter().flat_map(|c| cube_points(&c.0)))
+            .chain(self.spheres.iter().flat_map(|s| cube_points(&s.0)))
+            .chain(self.meshes.iter().flat_map(|(mesh, model, ..)| {
+                mesh.vertices.iter().map(|v| model.transform_point3(*v))
+            }))
+            .chain(self.models.iter().map(|m| m.3.translation.into()))
+            .chain(
+                self.blended_rectangles
+                    .iter()
+                    .flat_map(|r| square_points(&r.1)),
+            )
+            .chain(
+                self.blended_textures
+                    .iter()
+                    .flat_map(|t| square_points(&t.2)),
+            );
+        points.fold(None, |bounds, p| match bounds {
+            Some((min, max)) => Some((p.min(min), p.max(max))),
+            None => Some((p, p)),
+        })
+    }
+
+    /// Whether there is no drawing data.
+    pub(crate) fn is_empty(&self) -> bool {
+        self.points.is_empty()
+            && self.lines.is_empty()
+            && self.triangles.is_empty()
+            && self.rectangles.is_empty()
+            && self.cicles.is_empty()
+            && self.textures.is_empty()
+            && self.cuboids.is_empty()
+            && self.spheres.is_empty()
+            && self.meshes.is_empty()
+            && self.models.is_empty()
+            && self.blended_rectangles.is_empty()
+            && self.blended_textures.is_empty()
     }
 
     /// Clear all drawing data.
@@ -124,7 +404,10 @@ impl Canvas {
         self.textures.clear();
         self.cuboids.clear();
         self.spheres.clear();
+        self.meshes.clear();
         self.models.clear();
+        self.blended_rectangles.clear();
+        self.blended_textures.clear();
     }
 }
 
@@ -136,30 +419,45 @@ pub fn canvas_clear_system(mut canvas: UniqueViewMut<Canvas>) {
 /// CanvasRenderContext stores many render objects that exist between frames.
 pub(crate) struct CanvasRenderContext {
     pub eid_images: [Vec<Arc<ImageView>>; 2],
-    pub rasterization: RasterizationPipeline,
+    /// The (projection view matrix, viewport offset, viewport extent) of each camera used by rasterization in the
+    /// last frame of game window and scene window, used to unproject depth into world position.
+    /// Empty if the window was drawn by ray tracing.
+    pub projection_views: [Vec<(Mat4, UVec2, UVec2)>; 2],
+    /// The pipelines are created when they are used for the first time, and are kept alive after that,
+    /// so that switching between rasterization and ray tracing at runtime is fast.
+    pub rasterization: Option<RasterizationPipeline>,
     pub ray_tracing: Option<RayTracingPipeline>,
 }
 
 impl CanvasRenderContext {
-    pub fn new(
-        context: &RenderContext,
-        info: &FrameRenderInfo,
-        ray_tracing_supported: bool,
-    ) -> Self {
+    pub fn new() -> Self {
         CanvasRenderContext {
             eid_images: [Vec::new(), Vec::new()],
-            rasterization: RasterizationPipeline::new(context, info),
-            ray_tracing: if ray_tracing_supported {
-                Some(RayTracingPipeline::new(context))
-            } else {
-                None
-            },
+            projection_views: [Vec::new(), Vec::new()],
+            rasterization: None,
+            ray_tracing: None,
         }
     }
 
-    pub fn update(&mut self, context: &RenderContext, info: &FrameRenderInfo) {
+    /// Create the pipeline which is going to be used if it does not exist, and only update the used pipeline.
+    pub fn update(&mut self, context: &RenderContext, info: &FrameRenderInfo, ray_tracing: bool) {
         self.update_eid_images(context, info);
-        self.rasterization.update(context, info); // TODO: not update unused pipeline
+        if ray_tracing {
+            self.ray_tracing.get_or_insert_with(|| {
+                log::info!("CanvasRenderContext::update: create ray tracing pipeline");
+                RayTracingPipeline::new(context)
+            });
+        } else {
+            match &mut self.rasterization {
+                Some(rasterization) => rasterization.update(context, info),
+                None => {
+                    log::info!("CanvasRenderContext::update: create rasterization pipeline");
+                    let mut rasterization = RasterizationPipeline::new(context, info);
+                    rasterization.update(context, info);
+                    self.rasterization = Some(rasterization);
+                }
+            }
+        }
     }
 
     fn update_eid_images(&mut self, context: &RenderContext, info: &FrameRenderInfo) {
@@ -179,7 +477,10 @@ impl CanvasRenderContext {
                     ImageCreateInfo {
                         format: Format::R32G32_UINT,
                         extent: [info.window_size.x, info.window_size.y, 1],
-                        usage: ImageUsage::COLOR_ATTACHMENT | ImageUsage::TRANSFER_SRC,
+                        // storage usage is for ray tracing pipeline to write eids in shader
+                        usage: ImageUsage::COLOR_ATTACHMENT
+                            | ImageUsage::STORAGE
+                            | ImageUsage::TRANSFER_SRC,
                         ..Default::default()
                     },
                     AllocationCreateInfo::default(),
@@ -191,55 +492,129 @@ impl CanvasRenderContext {
     }
 }
 
-/// Send all canvas drawing data to the gpu to draw.
+/// Send all canvas drawing data to the gpu to draw after before_future, returns the after future.
+/// This system should be run by [shipyard::World::run_with_data] with before_future as data.
 pub fn canvas_render_system(
+    before_future: Box<dyn GpuFuture>,
     info: UniqueView<FrameRenderInfo>,
     camera: UniqueView<CameraInfo>,
     canvas: UniqueView<Canvas>,
+    (render_layers, hide_in_games, pooled, childrens): (
+        View<RenderLayers>,
+        View<HideInGame>,
+        View<Pooled>,
+        View<Children>,
+    ),
     mut render_manager: UniqueViewMut<RenderManager>,
     mut model_assets: UniqueViewMut<ModelAssets>,
     mut texture_assets: UniqueViewMut<TextureAssets>,
     mut image_assets: UniqueViewMut<ImageAssets>,
     mut asset_manager: UniqueViewMut<AssetManager>,
     platform: UniqueView<Platform>,
-) -> (Box<dyn GpuFuture>, Arc<PrimaryAutoCommandBuffer>) {
+) -> Box<dyn GpuFuture> {
     let render_manager = render_manager.as_mut();
-    render_manager.update(&info, render_manager.ray_tracing_supported());
+    render_manager.update(&info);
     let context = &render_manager.context;
     let canvas_context = render_manager.canvas_context.as_mut().unwrap();
-    let eid_image = canvas_context.eid_images[info.window_index][info.image_index].clone();
-    if render_manager.ray_tracing {
-        canvas_context.ray_tracing.as_mut().unwrap().draw(
-            context,
-            &info,
-            &camera,
-            &render_manager.ray_tracing_settings,
-            &canvas,
-            &mut model_assets,
-            &mut texture_assets,
-            &mut image_assets,
-            &mut asset_manager,
-            &platform,
-            eid_image,
-        )
-    } else {
-        (
-            vulkano::sync::now(context.device.clone()).boxed(),
-            canvas_context.rasterization.draw(
+    canvas_context.projection_views[info.window_index].clear();
+
+    // the solo entity and all its descendants
+    let solo_entities = info.solo_entity.map(|solo_entity| {
+        let mut solo_entities = HashSet::new();
+        let mut stack = vec![solo_entity];
+        while let Some(eid) = stack.pop() {
+            solo_entities.insert(eid);
+            if let Ok(children) = childrens.get(eid) {
+                stack.extend(children);
+            }
+        }
+        solo_entities
+    });
+
+    // draw the first camera and then the other cameras into their viewports
+    let mut after_future = before_future;
+    for (i, camera) in std::iter::once(&*camera)
+        .chain(camera.others.iter())
+        .enumerate()
+    {
+        // skip the drawing data of entities which are not in the layers of camera,
+        // skip the drawing data of entities which are hidden in game window,
+        // skip the drawing data of entities which are waiting in entity pool,
+        // and skip the drawing data of entities which are not the solo entity or its descendants
+        let filter_layers = !render_layers.is_empty() && camera.render_layers != RenderLayers::ALL;
+        let filter_hidden = !hide_in_games.is_empty() && info.window_index == WindowIndex::GAME;
+        let filter_pooled = !pooled.is_empty();
+        let filtered_canvas =
+            (filter_layers || filter_hidden || filter_pooled || solo_entities.is_some()).then(
+                || {
+                    canvas.filter(|eid| {
+                        !(filter_layers
+                            && render_layers
+                                .get(eid)
+                                .is_ok_and(|layers| !layers.intersects(camera.render_layers))
+                            || filter_hidden && hide_in_games.contains(eid)
+                            || filter_pooled && pooled.contains(eid)
+                            || solo_entities
+                                .as_ref()
+                                .is_some_and(|solo_entities| !solo_entities.contains(&eid)))
+                    })
+                },
+            );
+        let canvas = filtered_canvas.as_ref().unwrap_or(&canvas);
+
+        let eid_image = canvas_context.eid_images[info.window_index][info.image_index].clone();
+        let (viewport_offset, viewport_extent) = camera.viewport_rect(info.window_size);
+        if !render_manager.ray_tracing {
+            canvas_context.projection_views[info.window_index].push((
+                camera.projection_view_reverse_z(&viewport_extent),
+                viewport_offset,
+                viewport_extent,
+            ));
+        }
+        let (gpu_future, command_buffer) = if render_manager.ray_tracing {
+            canvas_context.ray_tracing.as_mut().unwrap().draw(
                 context,
                 &info,
-                &camera,
-                &render_manager.rasterization_settings,
-                &canvas,
+                camera,
+                &render_manager.ray_tracing_settings,
+                render_manager.skybox,
+                canvas,
                 &mut model_assets,
                 &mut texture_assets,
                 &mut image_assets,
                 &mut asset_manager,
                 &platform,
                 eid_image,
-            ),
-        )
+            )
+        } else {
+            (
+                vulkano::sync::now(context.device.clone()).boxed(),
+                canvas_context.rasterization.as_mut().unwrap().draw(
+                    context,
+                    &info,
+                    camera,
+                    i == 0,
+                    &render_manager.rasterization_settings,
+                    render_manager.skybox,
+                    canvas,
+                    &mut model_assets,
+                    &mut texture_assets,
+                    &mut image_assets,
+                    &mut asset_manager,
+                    &platform,
+                    eid_image,
+                ),
+            )
+        };
+        after_future = command_buffer
+            .execute_after(
+                after_future.join(gpu_future),
+                context.graphics_queue.clone(),
+            )
+            .unwrap()
+            .boxed();
     }
+    after_future
 }
 
 /// Parameters for [get_entity_at_screen_system].
@@ -251,55 +626,15 @@ pub(crate) struct GetEntityAtScreenParam {
 
 /// Screen object picking system.
 pub(crate) fn get_entity_at_screen_system(
-    mut render_manager: UniqueViewMut<RenderManager>,
+    render_manager: UniqueView<RenderManager>,
     param: UniqueView<GetEntityAtScreenParam>,
 ) -> EntityId {
-    let render_manager = render_manager.as_mut();
-    if let Some(canvas_contex) = render_manager.canvas_context.as_mut() {
+    if let Some(canvas_context) = render_manager.canvas_context.as_ref() {
         let image_index = render_manager.image_index[param.window_index];
-        if canvas_contex.eid_images[param.window_index].len() > image_index {
-            let eid_image = &canvas_contex.eid_images[param.window_index][image_index];
-            let [width, height, _] = eid_image.image().extent();
-            let buffer = Buffer::from_iter(
-                render_manager.context.memory_allocator.clone(),
-                BufferCreateInfo {
-                    usage: BufferUsage::TRANSFER_DST,
-                    ..Default::default()
-                },
-                AllocationCreateInfo {
-                    memory_type_filter: MemoryTypeFilter::PREFER_HOST
-                        | MemoryTypeFilter::HOST_RANDOM_ACCESS,
-                    ..Default::default()
-                },
-                (0..width * height * 2).map(|_| 0u32),
-            )
-            .unwrap();
-            let mut builder = AutoCommandBufferBuilder::primary(
-                &render_manager.context.command_buffer_allocator,
-                render_manager.context.graphics_queue.queue_family_index(),
-                CommandBufferUsage::OneTimeSubmit,
-            )
-            .unwrap();
-            builder
-                .copy_image_to_buffer(CopyImageToBufferInfo::image_buffer(
-                    eid_image.image().clone(),
-                    buffer.clone(),
-                ))
-                .unwrap();
-            builder
-                .build()
-                .unwrap()
-                // no need to execute after previous drawing future because they are excuting on the same vk queue
-                .execute(render_manager.context.graphics_queue.clone())
-                .unwrap()
-                .then_signal_fence_and_flush()
-                .unwrap()
-                .wait(None)
-                .unwrap();
-            let index = ((param.screen_position.x + param.screen_position.y * width) * 2) as usize;
-            let buffer_read = buffer.read().unwrap();
-            if index + 1 < buffer_read.len() {
-                let eid_array = [buffer_read[index], buffer_read[index + 1]];
+        if let Some(eid_image) = canvas_context.eid_images[param.window_index].get(image_index) {
+            if let Some(eid_array) =
+                read_texel::<[u32; 2]>(&render_manager, eid_image, param.screen_position)
+            {
                 return u32_array_to_eid(eid_array);
             }
         }
@@ -307,6 +642,93 @@ pub(crate) fn get_entity_at_screen_system(
     EntityId::dead()
 }
 
+/// Get the world position at screen position by reading the depth image and unprojecting it with the camera
+/// of last frame. Returns None if nothing is drawn at screen position or the window is drawn by ray tracing.
+pub(crate) fn get_position_at_screen_system(
+    render_manager: UniqueView<RenderManager>,
+    param: UniqueView<GetEntityAtScreenParam>,
+) -> Option<Vec3> {
+    let canvas_context = render_manager.canvas_context.as_ref()?;
+    // the last drawn camera whose viewport contains screen position is visible there
+    let (projection_view, viewport_offset, viewport_extent) = *canvas_context.projection_views
+        [param.window_index]
+        .iter()
+        .rev()
+        .find(|(_, offset, extent)| {
+            param.screen_position.cmpge(*offset).all()
+                && param.screen_position.cmplt(*offset + *extent).all()
+        })?;
+    let image_index = render_manager.image_index[param.window_index];
+    let depth_image = canvas_context
+        .rasterization
+        .as_ref()?
+        .depth_image(param.window_index, image_index)?;
+    let depth = read_texel::<f32>(&render_manager, depth_image, param.screen_position)?;
+    if depth <= 0.0 {
+        return None; // reverse-Z: depth 0 is the far plane, which means nothing is drawn here
+    }
+    let position_in_viewport = (param.screen_position - viewport_offset).as_vec2() + 0.5;
+    let ndc = (position_in_viewport / viewport_extent.as_vec2() * 2.0 - 1.0).extend(depth);
+    Some(projection_view.inverse().project_point3(ndc))
+}
+
+/// Copy one texel of image at screen position to cpu. Returns None if screen position is outside of image.
+fn read_texel<T: BufferContents + Copy>(
+    render_manager: &RenderManager,
+    image_view: &Arc<ImageView>,
+    screen_position: UVec2,
+) -> Option<T> {
+    let image = image_view.image();
+    let [width, height, _] = image.extent();
+    if screen_position.x >= width || screen_position.y >= height {
+        return None;
+    }
+    let buffer = Buffer::new_sized::<T>(
+        render_manager.context.memory_allocator.clone(),
+        BufferCreateInfo {
+            usage: BufferUsage::TRANSFER_DST,
+            ..Default::default()
+        },
+        AllocationCreateInfo {
+            memory_type_filter: MemoryTypeFilter::PREFER_HOST
+                | MemoryTypeFilter::HOST_RANDOM_ACCESS,
+            ..Default::default()
+        },
+    )
+    .unwrap();
+    let mut builder = AutoCommandBufferBuilder::primary(
+        &render_manager.context.command_buffer_allocator,
+        render_manager.context.graphics_queue.queue_family_index(),
+        CommandBufferUsage::OneTimeSubmit,
+    )
+    .unwrap();
+    builder
+        .copy_image_to_buffer(CopyImageToBufferInfo {
+            regions: [BufferImageCopy {
+                image_subresource: image.subresource_layers(),
+                image_offset: [screen_position.x, screen_position.y, 0],
+                image_extent: [1, 1, 1],
+                ..Default::default()
+            }]
+            .into(),
+            ..CopyImageToBufferInfo::image_buffer(image.clone(), buffer.clone())
+        })
+        .unwrap();
+    builder
+        .build()
+        .unwrap()
+        // no need to execute after previous drawing future because they are excuting on the same vk queue
+        .execute(render_manager.context.graphics_queue.clone())
+        .unwrap()
+        // the copy must be finished before the buffer is read
+        .then_signal_fence_and_flush()
+        .unwrap()
+        .wait(None)
+        .unwrap();
+    let texel = *buffer.read().unwrap();
+    Some(texel)
+}
+
 /// Helper function to convert [EntityId] to [[u32; 2]].
 pub fn eid_to_u32_array(eid: EntityId) -> [u32; 2] {
     let eid = eid.inner();
@@ -1,5 +1,5 @@
 use crate::asset::AssetManager;
-use image::{DynamicImage, ImageError, ImageReader};
+use image::{DynamicImage, ImageError, ImageFormat, ImageReader};
 use shipyard::Unique;
 use std::{collections::HashMap, io::Cursor, sync::Arc};
 use steel_common::{asset::AssetId, platform::Platform};
@@ -22,6 +22,10 @@ impl ImageAssets {
         asset_manager: &mut AssetManager,
         platform: &Platform,
     ) -> Option<Arc<DynamicImage>> {
+        // file extension is used when image format can not be guessed from content, e.g. tga
+        let format = asset_manager
+            .get_asset_path(asset_id)
+            .and_then(|path| ImageFormat::from_path(path).ok());
         if let Some(bytes) = asset_manager.get_asset_content(asset_id, platform) {
             if let Some(image_asset) = self.images.get(&asset_id) {
                 if Arc::ptr_eq(bytes, &image_asset.bytes) {
@@ -30,7 +34,7 @@ impl ImageAssets {
                 }
             }
             // cache is not valid, reload data
-            match Self::get_image_from_bytes(&bytes) {
+            match Self::get_image_from_bytes(bytes, format) {
                 Ok(data) => {
                     let image_data = Arc::new(data);
                     self.images.insert(
@@ -42,16 +46,27 @@ impl ImageAssets {
                     );
                     return Some(image_data);
                 }
-                Err(e) => log::error!("ImageAssets::get_image: error: {}", e),
+                Err(e) => log::error!(
+                    "ImageAssets::get_image: failed to decode {:?}, error: {}",
+                    asset_manager.get_asset_path(asset_id),
+                    e
+                ),
             }
         }
         self.images.remove(&asset_id);
         None
     }
 
-    fn get_image_from_bytes(bytes: &[u8]) -> Result<DynamicImage, ImageError> {
-        ImageReader::new(Cursor::new(bytes))
-            .with_guessed_format()?
-            .decode()
+    /// Decode image from bytes. Image format is guessed from content first, then falls back to format.
+    /// Supports at least png, jpeg, bmp and tga.
+    fn get_image_from_bytes(
+        bytes: &[u8],
+        format: Option<ImageFormat>,
+    ) -> Result<DynamicImage, ImageError> {
+        let mut reader = ImageReader::new(Cursor::new(bytes));
+        if let Some(format) = format {
+            reader.set_format(format);
+        }
+        reader.with_guessed_format()?.decode()
     }
 }
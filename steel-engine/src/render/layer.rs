@@ -0,0 +1,209 @@
+use crate::edit::Edit;
+use shipyard::{Component, Unique, UniqueView};
+use std::sync::RwLock;
+use steel_common::data::{Data, Limit, Value};
+
+/// The RenderLayers component is a bit mask that defines which layers an entity is in.
+/// An entity with RenderLayers is only rendered by cameras whose layer mask has a same bit.
+/// Entities without RenderLayers are rendered by all cameras.
+///
+/// RenderLayers on an entity with [crate::camera::Camera] component is the layer mask of the camera,
+/// a camera without RenderLayers renders all layers. The scene window in steel-editor always renders all layers.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RenderLayers(pub u32);
+
+impl RenderLayers {
+    /// The mask that contains all layers.
+    pub const ALL: RenderLayers = RenderLayers(u32::MAX);
+
+    /// Create a RenderLayers which only contains layer, layer must be less than 32.
+    pub fn layer(layer: u32) -> Self {
+        RenderLayers(1 << layer)
+    }
+
+    /// Returns true if self and other have any same layer.
+    pub fn intersects(&self, other: RenderLayers) -> bool {
+        self.0 & other.0 != 0
+    }
+}
+
+impl Default for RenderLayers {
+    fn default() -> Self {
+        RenderLayers::layer(0)
+    }
+}
+
+impl std::ops::Deref for RenderLayers {
+    type Target = u32;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl std::ops::DerefMut for RenderLayers {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl Edit for RenderLayers {
+    fn name() -> &'static str {
+        "RenderLayers"
+    }
+
+    fn get_data(&self) -> Data {
+        Data::new().insert("layers", Value::UInt32(self.0))
+    }
+
+    fn set_data(&mut self, data: &Data) {
+        if let Some(Value::UInt32(v)) = data.get("layers") {
+            self.0 = *v;
+        }
+    }
+}
+
+/// The HideInGame component hides an entity in game window, but the entity is still visible in scene window.
+/// This is useful for authoring aids like spawn markers and trigger visualizers.
+#[derive(Component, Edit, Default, Debug)]
+pub struct HideInGame;
+
+/// The z distance between two adjacent sorting layers when drawing 2D objects, see [SortingLayers::z_offset].
+pub const SORTING_LAYER_SPACING: f32 = 100.0;
+/// The z distance between two adjacent orders in a sorting layer when drawing 2D objects, see [SortingLayers::z_offset].
+pub const SORTING_ORDER_SPACING: f32 = 0.01;
+/// The z range in front of their z that coplanar 2D objects with the same sorting key are spread into when
+/// [SortingLayers::sorted_depth] is true, which is less than [SORTING_ORDER_SPACING] so that the orders are kept.
+pub const SORTED_DEPTH_RANGE: f32 = SORTING_ORDER_SPACING / 2.0;
+
+/// A copy of [SortingLayers::layers], which is used by [SortingLayer] to show layer names in steel-editor.
+/// [SortingLayer] is saved by layer name, so it does not depend on this copy when loading and saving.
+static SORTING_LAYER_NAMES: RwLock<Vec<String>> = RwLock::new(Vec::new());
+
+/// The SortingLayers unique is the ordered list of 2D sorting layer names, from back to front.
+/// Entities without [SortingLayer] component are drawn in the layer named "Default",
+/// or in the first layer if there is no "Default" layer.
+#[derive(Unique, Edit, Debug)]
+pub struct SortingLayers {
+    pub layers: Vec<String>,
+    /// If true, 2D objects with the same sorting key, which is (layer, order, z), are sorted and spread within
+    /// [SORTED_DEPTH_RANGE] in front of their z, so that they never z-fight even if they are coplanar. The draw order
+    /// of 2D objects with the same sorting key is stable but unspecified. 3D objects are not affected.
+    pub sorted_depth: bool,
+}
+
+impl Default for SortingLayers {
+    fn default() -> Self {
+        SortingLayers {
+            layers: vec![
+                "Background".into(),
+                "Default".into(),
+                "Foreground".into(),
+                "UI".into(),
+            ],
+            sorted_depth: false,
+        }
+    }
+}
+
+impl SortingLayers {
+    /// Get the index of the layer named name.
+    pub fn index(&self, name: impl AsRef<str>) -> Option<usize> {
+        self.layers.iter().position(|layer| layer == name.as_ref())
+    }
+
+    /// Get the z offset to draw a 2D object of an entity with sorting_layer, which is relative to the default layer,
+    /// so that entities without [SortingLayer] component have a z offset of 0. Entities in a later layer are always
+    /// drawn in front of entities in an earlier layer, unless their z values differ by more than [SORTING_LAYER_SPACING].
+    pub fn z_offset(&self, sorting_layer: Option<&SortingLayer>) -> f32 {
+        let Some(sorting_layer) = sorting_layer else {
+            return 0.0;
+        };
+        let default_index = self.default_index() as f32;
+        let index = self.layer_index(sorting_layer) as f32;
+        (index - default_index) * SORTING_LAYER_SPACING
+            + sorting_layer.order as f32 * SORTING_ORDER_SPACING
+    }
+
+    /// Get the (layer index, order) of an entity with sorting_layer, which is the first part of the sorting key
+    /// used when [SortingLayers::sorted_depth] is true. Entities without [SortingLayer] are in the default layer with order 0.
+    pub fn sorting_key(&self, sorting_layer: Option<&SortingLayer>) -> (usize, i32) {
+        match sorting_layer {
+            Some(sorting_layer) => (self.layer_index(sorting_layer), sorting_layer.order),
+            None => (self.default_index(), 0),
+        }
+    }
+
+    /// The index of the layer of sorting_layer, or the default layer index if the layer does not exist.
+    fn layer_index(&self, sorting_layer: &SortingLayer) -> usize {
+        self.index(&sorting_layer.layer)
+            .unwrap_or_else(|| self.default_index())
+    }
+
+    /// The index of the layer named "Default", or 0 if there is no "Default" layer.
+    fn default_index(&self) -> usize {
+        self.index("Default").unwrap_or(0)
+    }
+}
+
+/// Copy [SortingLayers::layers] so that [SortingLayer] can show layer names in steel-editor.
+pub fn sorting_layers_maintain_system(sorting_layers: UniqueView<SortingLayers>) {
+    if let Ok(mut names) = SORTING_LAYER_NAMES.write() {
+        if *names != sorting_layers.layers {
+            names.clone_from(&sorting_layers.layers);
+        }
+    }
+}
+
+/// The SortingLayer component defines the draw order of the 2D object of an entity. The layer is one of the
+/// layer names in [SortingLayers] unique, and entities with greater order are drawn in front in the same layer.
+#[derive(Component, Debug, Clone, PartialEq)]
+pub struct SortingLayer {
+    pub layer: String,
+    pub order: i32,
+}
+
+impl SortingLayer {
+    /// Create a SortingLayer component.
+    pub fn new(layer: impl Into<String>, order: i32) -> Self {
+        SortingLayer {
+            layer: layer.into(),
+            order,
+        }
+    }
+}
+
+impl Default for SortingLayer {
+    fn default() -> Self {
+        SortingLayer::new("Default", 0)
+    }
+}
+
+impl Edit for SortingLayer {
+    fn name() -> &'static str {
+        "SortingLayer"
+    }
+
+    fn get_data(&self) -> Data {
+        let names = SORTING_LAYER_NAMES
+            .read()
+            .map(|names| names.clone())
+            .unwrap_or_default();
+        Data::new()
+            .insert_with_limit(
+                "layer",
+                Value::String(self.layer.clone()),
+                Limit::StringEnum(names),
+            )
+            .insert("order", Value::Int32(self.order))
+    }
+
+    fn set_data(&mut self, data: &Data) {
+        if let Some(Value::String(v)) = data.get("layer") {
+            self.layer.clone_from(v);
+        }
+        if let Some(Value::Int32(v)) = data.get("order") {
+            self.order = *v;
+        }
+    }
+}
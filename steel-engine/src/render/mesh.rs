@@ -1,4 +1,4 @@
-use glam::{Vec2, Vec3};
+use glam::{UVec2, Vec2, Vec3};
 use std::sync::LazyLock;
 
 // Rectangle vertices with positions, normals, and texture coordinates.
@@ -193,7 +193,7 @@ pub const CUBOID_INDICES_V2: [u16; 36] = [
     20, 21, 22, 20, 22, 23, // Bottom face
 ];
 
-pub const SPHERE_VERTICES: LazyLock<[Vec3; SPHERE_VERTEX_COUNT]> =
+pub static SPHERE_VERTICES: LazyLock<[Vec3; SPHERE_VERTEX_COUNT]> =
     LazyLock::new(|| generate_sphere_vertices(0.5));
 pub const SPHERE_INDICES: [u16; SPHERE_INDEX_COUNT] = generate_sphere_indices();
 const SPHERE_LATITUDE_COUNT: usize = 16;
@@ -254,3 +254,107 @@ const fn generate_sphere_indices() -> [u16; SPHERE_INDEX_COUNT] {
     }
     indices
 }
+
+/// The vertex positions and indices of a procedural mesh, which can be drawn by [crate::render::canvas::Canvas::mesh].
+/// Triangles are in counter-clockwise (CCW) winding when looking from outside.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MeshData {
+    pub vertices: Vec<Vec3>,
+    pub indices: Vec<u16>,
+}
+
+impl MeshData {
+    /// Generate a box centered at origin with size.
+    pub fn cuboid(size: Vec3) -> Self {
+        MeshData {
+            vertices: CUBOID_VERTICES.iter().map(|v| *v * size).collect(),
+            // CUBOID_INDICES are in clockwise winding, so we reverse each triangle
+            indices: CUBOID_INDICES
+                .chunks_exact(3)
+                .flat_map(|t| [t[0], t[2], t[1]])
+                .collect(),
+        }
+    }
+
+    /// Generate a cylinder centered at origin along y axis. The side is approximated by segments quads,
+    /// segments is clamped to [3, 1024].
+    pub fn cylinder(radius: f32, height: f32, segments: u32) -> Self {
+        let segments = segments.clamp(3, 1024) as u16;
+        let half_height = height / 2.0;
+        let mut vertices = Vec::with_capacity(2 * (segments as usize + 1) + 2);
+        for (x, z) in Self::circle(radius, segments) {
+            vertices.push(Vec3::new(x, -half_height, z)); // bottom: 2 * k
+            vertices.push(Vec3::new(x, half_height, z)); // top: 2 * k + 1
+        }
+        let bottom_center = vertices.len() as u16;
+        vertices.push(Vec3::new(0.0, -half_height, 0.0));
+        let top_center = bottom_center + 1;
+        vertices.push(Vec3::new(0.0, half_height, 0.0));
+
+        let mut indices = Vec::with_capacity(segments as usize * 12);
+        for k in 0..segments {
+            let (bottom, top) = (2 * k, 2 * k + 1);
+            let (next_bottom, next_top) = (bottom + 2, top + 2);
+            indices.extend([top, next_top, bottom, bottom, next_top, next_bottom]);
+            indices.extend([top_center, next_top, top]);
+            indices.extend([bottom_center, bottom, next_bottom]);
+        }
+        MeshData { vertices, indices }
+    }
+
+    /// Generate a cone centered at origin along y axis, whose apex is at the top. The side is approximated by
+    /// segments triangles, segments is clamped to [3, 1024].
+    pub fn cone(radius: f32, height: f32, segments: u32) -> Self {
+        let segments = segments.clamp(3, 1024) as u16;
+        let half_height = height / 2.0;
+        let mut vertices = Self::circle(radius, segments)
+            .map(|(x, z)| Vec3::new(x, -half_height, z))
+            .collect::<Vec<_>>();
+        let bottom_center = vertices.len() as u16;
+        vertices.push(Vec3::new(0.0, -half_height, 0.0));
+        let apex = bottom_center + 1;
+        vertices.push(Vec3::new(0.0, half_height, 0.0));
+
+        let mut indices = Vec::with_capacity(segments as usize * 6);
+        for k in 0..segments {
+            indices.extend([apex, k + 1, k]);
+            indices.extend([bottom_center, k, k + 1]);
+        }
+        MeshData { vertices, indices }
+    }
+
+    /// Generate a plane centered at origin on xz plane facing up, size is the size in x and z.
+    /// The plane is divided into subdivisions.x * subdivisions.y cells, subdivisions is clamped to [1, 128].
+    pub fn plane(size: Vec2, subdivisions: UVec2) -> Self {
+        let subdivisions = subdivisions.clamp(UVec2::ONE, UVec2::splat(128));
+        let (columns, rows) = (subdivisions.x as u16, subdivisions.y as u16);
+        let mut vertices = Vec::with_capacity((columns as usize + 1) * (rows as usize + 1));
+        for i in 0..=columns {
+            for j in 0..=rows {
+                vertices.push(Vec3::new(
+                    size.x * (i as f32 / columns as f32 - 0.5),
+                    0.0,
+                    size.y * (j as f32 / rows as f32 - 0.5),
+                ));
+            }
+        }
+        let mut indices = Vec::with_capacity(columns as usize * rows as usize * 6);
+        for i in 0..columns {
+            for j in 0..rows {
+                let a = i * (rows + 1) + j;
+                let (b, c) = (a + 1, a + rows + 1);
+                let d = c + 1;
+                indices.extend([a, b, c, c, b, d]);
+            }
+        }
+        MeshData { vertices, indices }
+    }
+
+    /// The (x, z) of segments + 1 points on a circle, the last point is the same as the first point.
+    fn circle(radius: f32, segments: u16) -> impl Iterator<Item = (f32, f32)> {
+        (0..=segments).map(move |k| {
+            let theta = 2.0 * std::f32::consts::PI * k as f32 / segments as f32;
+            (radius * theta.cos(), radius * theta.sin())
+        })
+    }
+}
@@ -1,12 +1,15 @@
+pub mod blend;
 pub mod canvas;
 pub mod image;
+pub mod layer;
+pub mod mesh;
 pub mod model;
 pub mod pipeline;
 pub mod renderer;
 pub mod renderer2d;
+pub mod shape3d;
 pub mod texture;
-
-mod mesh;
+pub mod world_ui;
 
 use self::canvas::CanvasRenderContext;
 use crate::edit::Edit;
@@ -15,10 +18,11 @@ use pipeline::{
     rasterization::RasterizationSettings,
     raytracing::{util::ash::AshContext, RayTracingSettings},
 };
-use shipyard::Unique;
+use shipyard::{EntityId, Unique};
 use std::sync::Arc;
 use steel_common::{
     app::{DrawInfo, WindowIndex},
+    asset::AssetId,
     data::{Data, Limit, Value},
     ext::VulkanoWindowRendererExt,
 };
@@ -48,8 +52,12 @@ pub struct FrameRenderInfo {
     pub image: Arc<ImageView>,
     /// The image format.
     pub format: Format,
-    // We can not store before_future here because VulkanoWindowRenderer::acquire can not return Box<dyn GpuFuture + Send + Sync>.
-    // TODO: store before_future here and return after_future in render::canvas::canvas_render_system.
+    /// The number of samples per pixel for multisample anti-aliasing, see [DrawInfo::msaa_samples].
+    pub msaa_samples: u32,
+    /// If some, only this entity and its descendants are drawn, see [steel_common::app::EditorInfo::solo_entity].
+    pub solo_entity: Option<EntityId>,
+    // We can not store before_future here because VulkanoWindowRenderer::acquire can not return Box<dyn GpuFuture + Send + Sync>,
+    // so it is passed to render::canvas::canvas_render_system as system data instead.
 }
 
 impl FrameRenderInfo {
@@ -68,6 +76,11 @@ impl FrameRenderInfo {
             image_index: info.renderer.image_index() as usize,
             image: info.image.clone(),
             format: info.renderer.swapchain_format(),
+            msaa_samples: info.msaa_samples,
+            solo_entity: info
+                .editor_info
+                .as_ref()
+                .and_then(|editor_info| editor_info.solo_entity),
         }
     }
 }
@@ -101,6 +114,9 @@ pub struct RenderManager {
     // TODO: move pipeline settings to Camera component
     pub rasterization_settings: RasterizationSettings,
     pub ray_tracing_settings: RayTracingSettings,
+    /// The environment texture in equirectangular projection which is drawn behind the scene,
+    /// and is also the miss color of ray tracing. Default asset id means no skybox.
+    pub skybox: AssetId,
 }
 
 impl RenderManager {
@@ -127,17 +143,16 @@ impl RenderManager {
             ray_tracing: false,
             rasterization_settings: RasterizationSettings::default(),
             ray_tracing_settings: RayTracingSettings::default(),
+            skybox: AssetId::default(),
         }
     }
 
     /// Update RenderManager from FrameRenderInfo.
-    pub(crate) fn update(&mut self, info: &FrameRenderInfo, ray_tracing_supported: bool) {
+    pub(crate) fn update(&mut self, info: &FrameRenderInfo) {
         self.image_index[info.window_index] = info.image_index;
         self.canvas_context
-            .get_or_insert_with(|| {
-                CanvasRenderContext::new(&self.context, info, ray_tracing_supported)
-            })
-            .update(&self.context, info);
+            .get_or_insert_with(CanvasRenderContext::new)
+            .update(&self.context, info, self.ray_tracing);
     }
 
     /// The render context.
@@ -156,6 +171,8 @@ impl RenderManager {
     }
 
     /// Turn ray tracing on or off. If [Self::ray_tracing_supported] is false, this dose nothing.
+    /// This can be called at any time, the pipeline is switched from next frame, which is created
+    /// when it is used for the first time and kept alive after that.
     pub fn set_ray_tracing(&mut self, on: bool) {
         if self.ray_tracing_supported {
             self.ray_tracing = on;
@@ -180,10 +197,26 @@ impl Edit for RenderManager {
             );
         }
 
+        data.add_value("skybox", Value::Asset(self.skybox));
+
         if self.ray_tracing {
             self.ray_tracing_settings.get_data(&mut data);
         } else {
             self.rasterization_settings.get_data(&mut data);
+            if let Some(rasterization) = self
+                .canvas_context
+                .as_ref()
+                .and_then(|canvas_context| canvas_context.rasterization.as_ref())
+            {
+                let stats = rasterization.stats(WindowIndex::GAME);
+                for (name, value) in [
+                    ("draw_calls", stats.draw_calls),
+                    ("instances", stats.instances),
+                    ("vertices", stats.vertices),
+                ] {
+                    data.add_value_with_limit(name, Value::UInt32(value), Limit::ReadOnly);
+                }
+            }
         }
 
         data
@@ -196,6 +229,10 @@ impl Edit for RenderManager {
             }
         }
 
+        if let Some(Value::Asset(v)) = data.get("skybox") {
+            self.skybox = *v;
+        }
+
         self.ray_tracing_settings.set_data(data);
         self.rasterization_settings.set_data(data);
     }
@@ -1,16 +1,101 @@
 use crate::asset::AssetManager;
+use glam::{Vec2, Vec3};
 use obj::{Obj, ObjError, TexturedVertex};
 use shipyard::Unique;
-use std::{collections::HashMap, io::Cursor, sync::Arc};
+use std::{borrow::Cow, collections::HashMap, io::Cursor, sync::Arc};
 use steel_common::{asset::AssetId, platform::Platform};
 
 struct ModelAsset {
     bytes: Arc<Vec<u8>>,
-    data: Arc<Obj<TexturedVertex>>,
+    data: Arc<ModelData>,
 }
 
+/// The data of a model asset, which is an OBJ file optionally with vertex colors.
+/// Vertex colors are written as "v x y z r g b" lines, which is a widely supported extension of OBJ format.
+pub struct ModelData {
+    obj: Obj<TexturedVertex>,
+    /// The colors of obj.vertices, None if the OBJ file has no vertex colors.
+    colors: Option<Vec<[f32; 4]>>,
+    /// The tangents of obj.vertices, xyz is the tangent and w is the handedness of bitangent.
+    /// OBJ format has no tangents, so they are computed from positions, normals and texture coordinates when loading.
+    tangents: Vec<[f32; 4]>,
+}
+
+impl ModelData {
+    /// Get the color of vertex at index, which is white if the model has no vertex colors.
+    pub fn vertex_color(&self, index: usize) -> [f32; 4] {
+        self.colors
+            .as_ref()
+            .and_then(|colors| colors.get(index).copied())
+            .unwrap_or([1.0; 4])
+    }
+
+    /// Get the tangent of vertex at index.
+    pub fn vertex_tangent(&self, index: usize) -> [f32; 4] {
+        self.tangents[index]
+    }
+
+    /// Compute the tangent of every vertex by accumulating the tangents of triangles it belongs to.
+    fn compute_tangents(obj: &Obj<TexturedVertex>) -> Vec<[f32; 4]> {
+        let mut tangents = vec![Vec3::ZERO; obj.vertices.len()];
+        let mut bitangents = vec![Vec3::ZERO; obj.vertices.len()];
+        for triangle in obj.indices.chunks_exact(3) {
+            let [i0, i1, i2] = [0, 1, 2].map(|i| triangle[i] as usize);
+            let [v0, v1, v2] = [i0, i1, i2].map(|i| &obj.vertices[i]);
+            let edge1 = Vec3::from(v1.position) - Vec3::from(v0.position);
+            let edge2 = Vec3::from(v2.position) - Vec3::from(v0.position);
+            let uv0 = Vec2::new(v0.texture[0], v0.texture[1]);
+            let delta_uv1 = Vec2::new(v1.texture[0], v1.texture[1]) - uv0;
+            let delta_uv2 = Vec2::new(v2.texture[0], v2.texture[1]) - uv0;
+            let determinant = delta_uv1.perp_dot(delta_uv2);
+            if determinant.abs() < f32::EPSILON {
+                continue; // degenerated texture coordinates
+            }
+            let tangent = (edge1 * delta_uv2.y - edge2 * delta_uv1.y) / determinant;
+            let bitangent = (edge2 * delta_uv1.x - edge1 * delta_uv2.x) / determinant;
+            for i in [i0, i1, i2] {
+                tangents[i] += tangent;
+                bitangents[i] += bitangent;
+            }
+        }
+        obj.vertices
+            .iter()
+            .zip(tangents.into_iter().zip(bitangents))
+            .map(|(v, (tangent, bitangent))| {
+                let normal = Vec3::from(v.normal).normalize_or_zero();
+                // Gram-Schmidt orthogonalize
+                let mut tangent = (tangent - normal * normal.dot(tangent)).normalize_or_zero();
+                if tangent == Vec3::ZERO {
+                    tangent = if normal == Vec3::ZERO {
+                        Vec3::X
+                    } else {
+                        normal.any_orthonormal_vector()
+                    };
+                }
+                let handedness = if normal.cross(tangent).dot(bitangent) < 0.0 {
+                    -1.0
+                } else {
+                    1.0
+                };
+                tangent.extend(handedness).to_array()
+            })
+            .collect()
+    }
+}
+
+impl std::ops::Deref for ModelData {
+    type Target = Obj<TexturedVertex>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.obj
+    }
+}
+
+/// A map from the bits of vertex position to vertex color.
+type PositionToColor = HashMap<[u32; 3], [f32; 4]>;
+
 #[derive(Unique, Default)]
-/// Cache [Obj<TexturedVertex>] in assets.
+/// Cache [ModelData] in assets.
 pub struct ModelAssets {
     models: HashMap<AssetId, ModelAsset>,
 }
@@ -21,7 +106,7 @@ impl ModelAssets {
         asset_id: AssetId,
         asset_manager: &mut AssetManager,
         platform: &Platform,
-    ) -> Option<Arc<Obj<TexturedVertex>>> {
+    ) -> Option<Arc<ModelData>> {
         if let Some(bytes) = asset_manager.get_asset_content(asset_id, platform) {
             if let Some(model_asset) = self.models.get(&asset_id) {
                 if Arc::ptr_eq(bytes, &model_asset.bytes) {
@@ -30,7 +115,7 @@ impl ModelAssets {
                 }
             }
             // cache is not valid, reload data
-            match Self::get_model_from_bytes(&bytes) {
+            match Self::get_model_from_bytes(bytes) {
                 Ok(data) => {
                     let model_data = Arc::new(data);
                     self.models.insert(
@@ -49,7 +134,54 @@ impl ModelAssets {
         None
     }
 
-    fn get_model_from_bytes(bytes: &[u8]) -> Result<Obj<TexturedVertex>, ObjError> {
-        obj::load_obj(Cursor::new(bytes))
+    fn get_model_from_bytes(bytes: &[u8]) -> Result<ModelData, ObjError> {
+        let (bytes, position_to_color) = Self::split_vertex_colors(bytes);
+        let obj: Obj<TexturedVertex> = obj::load_obj(Cursor::new(bytes.as_ref()))?;
+        let colors = (!position_to_color.is_empty()).then(|| {
+            obj.vertices
+                .iter()
+                .map(|v| {
+                    let key = v.position.map(f32::to_bits);
+                    position_to_color.get(&key).copied().unwrap_or([1.0; 4])
+                })
+                .collect()
+        });
+        let tangents = ModelData::compute_tangents(&obj);
+        Ok(ModelData {
+            obj,
+            colors,
+            tangents,
+        })
+    }
+
+    /// Remove colors from "v x y z r g b" lines because they can not be parsed by [obj::load_obj].
+    /// Returns the OBJ bytes without vertex colors and a map from vertex position to vertex color.
+    fn split_vertex_colors(bytes: &[u8]) -> (Cow<'_, [u8]>, PositionToColor) {
+        let mut position_to_color = HashMap::new();
+        let text = String::from_utf8_lossy(bytes);
+        let mut stripped = String::with_capacity(text.len());
+        for line in text.lines() {
+            let mut words = line.split_whitespace();
+            if words.next() == Some("v") {
+                let words = words.collect::<Vec<_>>();
+                let values = words
+                    .iter()
+                    .map(|word| word.parse::<f32>())
+                    .collect::<Result<Vec<_>, _>>();
+                if let Ok([x, y, z, r, g, b]) = values.as_deref() {
+                    position_to_color
+                        .insert([x.to_bits(), y.to_bits(), z.to_bits()], [*r, *g, *b, 1.0]);
+                    stripped.push_str(&format!("v {} {} {}\n", words[0], words[1], words[2]));
+                    continue;
+                }
+            }
+            stripped.push_str(line);
+            stripped.push('\n');
+        }
+        if position_to_color.is_empty() {
+            (Cow::Borrowed(bytes), position_to_color)
+        } else {
+            (Cow::Owned(stripped.into_bytes()), position_to_color)
+        }
     }
 }
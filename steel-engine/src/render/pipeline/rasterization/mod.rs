@@ -4,11 +4,16 @@ use crate::{
     asset::AssetManager,
     camera::CameraInfo,
     render::{
-        canvas::Canvas, image::ImageAssets, mesh, model::ModelAssets, texture::TextureAssets,
+        blend::BlendMode,
+        canvas::Canvas,
+        image::ImageAssets,
+        mesh::{self, MeshData},
+        model::ModelAssets,
+        texture::{TextureAssets, TextureTiling},
         FrameRenderInfo, RenderContext,
     },
 };
-use glam::{Affine3A, Vec3, Vec4};
+use glam::{Affine3A, Mat4, Vec3, Vec4};
 use shipyard::EntityId;
 use std::{collections::HashMap, iter::zip, sync::Arc};
 use steel_common::{
@@ -25,16 +30,22 @@ use vulkano::{
     descriptor_set::{layout::DescriptorBindingFlags, PersistentDescriptorSet, WriteDescriptorSet},
     device::Device,
     format::Format,
-    image::{view::ImageView, Image, ImageCreateInfo, ImageUsage},
+    image::{
+        sampler::Sampler, view::ImageView, Image, ImageCreateInfo, ImageUsage, SampleCount,
+        SampleCounts,
+    },
     memory::allocator::{AllocationCreateInfo, MemoryTypeFilter, StandardMemoryAllocator},
     pipeline::{
         graphics::{
-            color_blend::{AttachmentBlend, ColorBlendAttachmentState, ColorBlendState},
-            depth_stencil::{DepthState, DepthStencilState},
+            color_blend::{
+                AttachmentBlend, BlendFactor, BlendOp, ColorBlendAttachmentState, ColorBlendState,
+                ColorComponents,
+            },
+            depth_stencil::{CompareOp, DepthState, DepthStencilState},
             input_assembly::{InputAssemblyState, PrimitiveTopology},
             multisample::MultisampleState,
             rasterization::{CullMode, PolygonMode, RasterizationState},
-            vertex_input::{Vertex, VertexDefinition},
+            vertex_input::{Vertex, VertexBufferDescription, VertexDefinition},
             viewport::{Viewport, ViewportState},
             GraphicsPipelineCreateInfo,
         },
@@ -43,7 +54,7 @@ use vulkano::{
         PipelineShaderStageCreateInfo,
     },
     render_pass::{Framebuffer, FramebufferCreateInfo, RenderPass, Subpass},
-    shader::{EntryPoint, ShaderModule},
+    shader::{EntryPoint, ShaderModule, SpecializationConstant},
     Validated, VulkanError,
 };
 
@@ -51,12 +62,22 @@ use vulkano::{
 pub struct RasterizationSettings {
     /// The color to clear the image before drawing.
     pub clear_color: Vec4,
+    /// The width in pixels of the antialiased edge of circles, 0 means hard edge.
+    pub circle_edge_smoothing: f32,
+    /// Log a warning if a single draw call has more instances than this, 0 means no warning.
+    pub instance_budget: u32,
+    /// Draw 3D shapes and models to depth only before drawing their colors, so that hidden fragments are
+    /// rejected by depth test before shading. This is only faster for scenes with a lot of overdraw.
+    pub depth_prepass: bool,
 }
 
 impl Default for RasterizationSettings {
     fn default() -> Self {
         RasterizationSettings {
             clear_color: Vec4::ZERO,
+            circle_edge_smoothing: 1.0,
+            instance_budget: 0,
+            depth_prepass: false,
         }
     }
 }
@@ -67,20 +88,96 @@ impl RasterizationSettings {
             "clear_color",
             Value::Vec4(self.clear_color),
             Limit::Vec4Color,
-        )
+        );
+        data.add_value_with_limit(
+            "circle_edge_smoothing",
+            Value::Float32(self.circle_edge_smoothing),
+            Limit::Float32Range(0.0..=4.0),
+        );
+        data.add_value("instance_budget", Value::UInt32(self.instance_budget));
+        data.add_value("depth_prepass", Value::Bool(self.depth_prepass));
     }
 
     pub fn set_data(&mut self, data: &Data) {
         if let Some(Value::Vec4(v)) = data.get("clear_color") {
             self.clear_color = *v;
         }
+        if let Some(Value::Float32(v)) = data.get("circle_edge_smoothing") {
+            self.circle_edge_smoothing = *v;
+        }
+        if let Some(Value::UInt32(v)) = data.get("instance_budget") {
+            self.instance_budget = *v;
+        }
+        if let Some(Value::Bool(v)) = data.get("depth_prepass") {
+            self.depth_prepass = *v;
+        }
     }
 }
 
+/// The numbers of draw calls, instances and vertices of a frame drawn by rasterization pipeline.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct RenderStats {
+    pub draw_calls: u32,
+    pub instances: u32,
+    pub vertices: u32,
+    /// See [RasterizationSettings::instance_budget].
+    instance_budget: u32,
+}
+
+impl RenderStats {
+    fn new(instance_budget: u32) -> Self {
+        RenderStats {
+            instance_budget,
+            ..Default::default()
+        }
+    }
+
+    /// Record a draw call which draws instances, each instance has vertices_per_instance vertices.
+    fn record(&mut self, name: &str, instances: u32, vertices_per_instance: u32) {
+        self.draw_calls += 1;
+        self.instances += instances;
+        self.vertices += instances * vertices_per_instance;
+        if self.instance_budget > 0 && instances > self.instance_budget {
+            log::warn!(
+                "RenderStats::record: draw call of {name} has {instances} instances, which exceeds instance budget {}",
+                self.instance_budget
+            );
+        }
+    }
+}
+
+/// How a graphics pipeline uses the depth buffer.
+#[derive(Clone, Copy, PartialEq)]
+enum DepthMode {
+    /// No depth test and no depth write.
+    None,
+    /// Depth test and depth write.
+    TestWrite,
+    /// Depth test and depth write without writing colors, used by depth prepass.
+    Prepass,
+    /// Depth test against the depth written by depth prepass without depth write.
+    AfterPrepass,
+    /// Depth test without depth write, and colors are blended by the blend mode instead of alpha.
+    Blended(BlendMode),
+}
+
 /// RasterizationPipeline stores many render objects that exist between frames.
 pub(crate) struct RasterizationPipeline {
+    /// The sample count of render pass and pipelines, which is Sample1 if multisampling is disabled.
+    samples: SampleCount,
     /// The image vectors whose index at WindowIndex::GAME and WindowIndex::SCENE are for game window and scene window.
     depth_stencil_images: [Vec<Arc<ImageView>>; 2],
+    /// The multisampled (color, eid) images which are resolved into the final images, empty if multisampling is disabled.
+    multisample_images: [Vec<(Arc<ImageView>, Arc<ImageView>)>; 2],
+    /// The depth images which sample 0 of the multisampled depth images is resolved into,
+    /// used to read depth at screen position. Empty if multisampling is disabled.
+    resolved_depth_images: [Vec<Arc<ImageView>>; 2],
+    /// The cached framebuffers at image index of game window and scene window, which are reused
+    /// as long as their attachments are not changed.
+    framebuffers: [Vec<Option<Arc<Framebuffer>>>; 2],
+    /// The render stats of last frame of game window and scene window.
+    stats: [RenderStats; 2],
+    format: Format,
     render_pass: Arc<RenderPass>,
     pipeline_point: Arc<GraphicsPipeline>,
     pipeline_line: Arc<GraphicsPipeline>,
@@ -93,11 +190,25 @@ pub(crate) struct RasterizationPipeline {
     pipeline_circle: Arc<GraphicsPipeline>,
     pipeline_texture: Arc<GraphicsPipeline>,
     pipeline_model: Arc<GraphicsPipeline>,
+    /// Used to draw skybox behind the scene, which has no depth test.
+    pipeline_skybox: Arc<GraphicsPipeline>,
+    /// The (depth prepass, color pass) pipelines used instead of [RasterizationPipeline::pipeline_shape]
+    /// if [RasterizationSettings::depth_prepass] is enabled.
+    pipelines_shape_prepass: (Arc<GraphicsPipeline>, Arc<GraphicsPipeline>),
+    /// The (depth prepass, color pass) pipelines used instead of [RasterizationPipeline::pipeline_model]
+    /// if [RasterizationSettings::depth_prepass] is enabled.
+    pipelines_model_prepass: (Arc<GraphicsPipeline>, Arc<GraphicsPipeline>),
+    /// The (blend mode, shape2d pipeline, texture pipeline) used to draw blended rectangles and textures
+    /// of each blend mode except [BlendMode::Alpha], see [Canvas::blended_rectangle] and [Canvas::blended_texture].
+    pipelines_blended: Vec<(BlendMode, Arc<GraphicsPipeline>, Arc<GraphicsPipeline>)>,
+    /// Used by the second subpass to resolve the multisampled images, None if multisampling is disabled.
+    pipeline_resolve: Option<Arc<GraphicsPipeline>>,
 }
 
 impl RasterizationPipeline {
     pub fn new(context: &RenderContext, info: &FrameRenderInfo) -> Self {
-        let render_pass = Self::create_render_pass(context, info.format);
+        let samples = Self::supported_samples(context, info.msaa_samples);
+        let render_pass = Self::create_render_pass(context, info.format, samples);
         let (
             pipeline_point,
             pipeline_line,
@@ -107,9 +218,21 @@ impl RasterizationPipeline {
             pipeline_circle,
             pipeline_texture,
             pipeline_model,
+            pipeline_skybox,
+            pipelines_shape_prepass,
+            pipelines_model_prepass,
+            pipelines_blended,
         ) = Self::create_pipelines(context, render_pass.clone());
+        let pipeline_resolve = (samples != SampleCount::Sample1)
+            .then(|| Self::create_resolve_pipeline(context, render_pass.clone()));
         RasterizationPipeline {
+            samples,
             depth_stencil_images: [Vec::new(), Vec::new()],
+            multisample_images: [Vec::new(), Vec::new()],
+            resolved_depth_images: [Vec::new(), Vec::new()],
+            framebuffers: [Vec::new(), Vec::new()],
+            stats: [RenderStats::default(); 2],
+            format: info.format,
             render_pass,
             pipeline_point,
             pipeline_line,
@@ -119,11 +242,54 @@ impl RasterizationPipeline {
             pipeline_circle,
             pipeline_texture,
             pipeline_model,
+            pipeline_skybox,
+            pipelines_shape_prepass,
+            pipelines_model_prepass,
+            pipelines_blended,
+            pipeline_resolve,
         }
     }
 
     pub fn update(&mut self, context: &RenderContext, info: &FrameRenderInfo) {
+        self.update_samples(context, info);
         self.update_depth_stencil_images(context, info);
+        self.update_multisample_images(context, info);
+    }
+
+    /// Get the highest sample count supported by the color, depth and integer color attachments
+    /// of the device which is not more than msaa_samples.
+    fn supported_samples(context: &RenderContext, msaa_samples: u32) -> SampleCount {
+        let properties = context.device.physical_device().properties();
+        let supported = properties.framebuffer_color_sample_counts
+            & properties.framebuffer_depth_sample_counts
+            & properties
+                .framebuffer_integer_color_sample_counts
+                .unwrap_or(SampleCounts::SAMPLE_1);
+        [
+            SampleCount::Sample64,
+            SampleCount::Sample32,
+            SampleCount::Sample16,
+            SampleCount::Sample8,
+            SampleCount::Sample4,
+            SampleCount::Sample2,
+        ]
+        .into_iter()
+        .find(|samples| u32::from(*samples) <= msaa_samples && supported.contains_enum(*samples))
+        .unwrap_or(SampleCount::Sample1)
+    }
+
+    /// Recreate render pass, pipelines and images if sample count is changed.
+    fn update_samples(&mut self, context: &RenderContext, info: &FrameRenderInfo) {
+        let samples = Self::supported_samples(context, info.msaa_samples);
+        if samples == self.samples {
+            return;
+        }
+        log::info!(
+            "RasterizationPipeline::update_samples: {:?} -> {:?}",
+            self.samples,
+            samples
+        );
+        *self = Self::new(context, info);
     }
 
     fn update_depth_stencil_images(&mut self, context: &RenderContext, info: &FrameRenderInfo) {
@@ -136,8 +302,9 @@ impl RasterizationPipeline {
             }
         }
         log::trace!(
-            "Create depth stencil images, image_count={}",
-            info.image_count
+            "Create depth stencil images, image_count={}, samples={:?}",
+            info.image_count,
+            self.samples
         );
         *depth_stencil_images = (0..info.image_count)
             .map(|_| {
@@ -146,7 +313,10 @@ impl RasterizationPipeline {
                     ImageCreateInfo {
                         format: Format::D32_SFLOAT,
                         extent: [info.window_size.x, info.window_size.y, 1],
-                        usage: ImageUsage::DEPTH_STENCIL_ATTACHMENT,
+                        usage: ImageUsage::DEPTH_STENCIL_ATTACHMENT
+                            | ImageUsage::TRANSFER_SRC
+                            | ImageUsage::INPUT_ATTACHMENT,
+                        samples: self.samples,
                         ..Default::default()
                     },
                     AllocationCreateInfo::default(),
@@ -157,13 +327,111 @@ impl RasterizationPipeline {
             .collect();
     }
 
-    fn create_render_pass(context: &RenderContext, format: Format) -> Arc<RenderPass> {
+    fn update_multisample_images(&mut self, context: &RenderContext, info: &FrameRenderInfo) {
+        let multisample_images = &mut self.multisample_images[info.window_index];
+        let resolved_depth_images = &mut self.resolved_depth_images[info.window_index];
+        if self.samples == SampleCount::Sample1 {
+            multisample_images.clear();
+            resolved_depth_images.clear();
+            return;
+        }
+        if multisample_images.len() >= info.image_count {
+            // TODO: use == instead of >= when we can get right image count
+            let [width, height, _] = multisample_images[0].0.image().extent();
+            if info.window_size.x == width && info.window_size.y == height {
+                return;
+            }
+        }
+        log::trace!(
+            "Create multisample images, image_count={}, samples={:?}",
+            info.image_count,
+            self.samples
+        );
+        let create_image = |format, usage, samples| {
+            let image = Image::new(
+                context.memory_allocator.clone(),
+                ImageCreateInfo {
+                    format,
+                    extent: [info.window_size.x, info.window_size.y, 1],
+                    usage,
+                    samples,
+                    ..Default::default()
+                },
+                AllocationCreateInfo::default(),
+            )
+            .unwrap();
+            ImageView::new_default(image).unwrap()
+        };
+        let multisample_usage = ImageUsage::COLOR_ATTACHMENT
+            | ImageUsage::TRANSIENT_ATTACHMENT
+            | ImageUsage::INPUT_ATTACHMENT;
+        *multisample_images = (0..info.image_count)
+            .map(|_| {
+                (
+                    create_image(self.format, multisample_usage, self.samples),
+                    create_image(Format::R32G32_UINT, multisample_usage, self.samples),
+                )
+            })
+            .collect();
+        *resolved_depth_images = (0..info.image_count)
+            .map(|_| {
+                create_image(
+                    Format::R32_SFLOAT,
+                    ImageUsage::COLOR_ATTACHMENT | ImageUsage::TRANSFER_SRC,
+                    SampleCount::Sample1,
+                )
+            })
+            .collect();
+    }
+
+    /// Get the depth image of a window which is drawn at image_index, used to read depth at screen position.
+    /// If multisampling is enabled, this is the R32_SFLOAT image which sample 0 of the depth is resolved into,
+    /// because a multisampled image can not be copied to buffer.
+    pub fn depth_image(&self, window_index: usize, image_index: usize) -> Option<&Arc<ImageView>> {
+        if self.samples != SampleCount::Sample1 {
+            return self.resolved_depth_images[window_index].get(image_index);
+        }
+        self.depth_stencil_images[window_index].get(image_index)
+    }
+
+    fn create_render_pass(
+        context: &RenderContext,
+        format: Format,
+        samples: SampleCount,
+    ) -> Arc<RenderPass> {
+        if samples != SampleCount::Sample1 {
+            // draw on multisampled images in the first subpass, and resolve them into the final color, eid and
+            // depth images in the second subpass, see RasterizationPipeline::create_resolve_pipeline
+            return vulkano::ordered_passes_renderpass!(
+                context.device.clone(),
+                attachments: {
+                    color_multisample: { format: format, samples: u32::from(samples), load_op: Clear, store_op: DontCare },
+                    eid_multisample: { format: Format::R32G32_UINT, samples: u32::from(samples), load_op: Clear, store_op: DontCare },
+                    depth_stencil: { format: Format::D32_SFLOAT, samples: u32::from(samples), load_op: Clear, store_op: DontCare },
+                    color: { format: format, samples: 1, load_op: DontCare, store_op: Store },
+                    eid: { format: Format::R32G32_UINT, samples: 1, load_op: DontCare, store_op: Store },
+                    depth: { format: Format::R32_SFLOAT, samples: 1, load_op: DontCare, store_op: Store },
+                },
+                passes: [
+                    {
+                        color: [ color_multisample, eid_multisample ],
+                        depth_stencil: { depth_stencil },
+                        input: [],
+                    },
+                    {
+                        color: [ color, eid, depth ],
+                        depth_stencil: {},
+                        input: [ color_multisample, eid_multisample, depth_stencil ],
+                    },
+                ],
+            ).unwrap();
+        }
         vulkano::single_pass_renderpass!(
             context.device.clone(),
             attachments: {
                 color: { format: format, samples: 1, load_op: Clear, store_op: Store },
                 eid: { format: Format::R32G32_UINT, samples: 1, load_op: Clear, store_op: Store },
-                depth_stencil: { format: Format::D32_SFLOAT, samples: 1, load_op: Clear, store_op: DontCare },
+                depth_stencil: { format: Format::D32_SFLOAT, samples: 1, load_op: Clear, store_op: Store },
             },
             pass: {
                 color: [ color, eid ],
@@ -184,6 +452,10 @@ impl RasterizationPipeline {
         Arc<GraphicsPipeline>,
         Arc<GraphicsPipeline>,
         Arc<GraphicsPipeline>,
+        Arc<GraphicsPipeline>,
+        (Arc<GraphicsPipeline>, Arc<GraphicsPipeline>),
+        (Arc<GraphicsPipeline>, Arc<GraphicsPipeline>),
+        Vec<(BlendMode, Arc<GraphicsPipeline>, Arc<GraphicsPipeline>)>,
     ) {
         let vs = Self::load_entry_point(context.device.clone(), shader::vertex::vs::load);
         let fs = Self::load_entry_point(context.device.clone(), shader::vertex::fs::load);
@@ -195,6 +467,7 @@ impl RasterizationPipeline {
             PrimitiveTopology::PointList,
             PolygonMode::Point,
             CullMode::None,
+            DepthMode::TestWrite,
             vs.clone(),
             fs.clone(),
             |_| {},
@@ -207,6 +480,7 @@ impl RasterizationPipeline {
             PrimitiveTopology::LineList,
             PolygonMode::Line,
             CullMode::None,
+            DepthMode::TestWrite,
             vs.clone(),
             fs.clone(),
             |_| {},
@@ -219,39 +493,56 @@ impl RasterizationPipeline {
             PrimitiveTopology::TriangleList,
             PolygonMode::Fill,
             CullMode::None,
+            DepthMode::TestWrite,
             vs.clone(),
             fs.clone(),
             |_| {},
         );
 
-        let pipeline_shape2d = Self::create_pipeline(
-            context,
-            render_pass.clone(),
-            &[
-                shader::shape::VertexData::per_vertex(),
-                shader::shape::InstanceData::per_instance(),
-            ],
-            PrimitiveTopology::TriangleList,
-            PolygonMode::Fill,
-            CullMode::None,
-            Self::load_entry_point(context.device.clone(), shader::shape::vs::load),
-            Self::load_entry_point(context.device.clone(), shader::shape::fs::load),
-            |_| {},
-        );
+        let create_pipeline_shape2d = |depth_mode| {
+            Self::create_pipeline(
+                context,
+                render_pass.clone(),
+                &[
+                    shader::shape::VertexData::per_vertex(),
+                    shader::shape::InstanceData::per_instance(),
+                ],
+                PrimitiveTopology::TriangleList,
+                PolygonMode::Fill,
+                CullMode::None,
+                depth_mode,
+                Self::load_entry_point(context.device.clone(), shader::shape::vs::load),
+                Self::load_fragment_entry_point(
+                    context.device.clone(),
+                    shader::shape::fs::load,
+                    depth_mode,
+                ),
+                |_| {},
+            )
+        };
+        let pipeline_shape2d = create_pipeline_shape2d(DepthMode::TestWrite);
 
-        let pipeline_shape = Self::create_pipeline(
-            context,
-            render_pass.clone(),
-            &[
-                shader::shape::VertexData::per_vertex(),
-                shader::shape::InstanceData::per_instance(),
-            ],
-            PrimitiveTopology::TriangleList,
-            PolygonMode::Fill,
-            CullMode::Back,
-            Self::load_entry_point(context.device.clone(), shader::shape::vs::load),
-            Self::load_entry_point(context.device.clone(), shader::shape::fs::load),
-            |_| {},
+        let create_pipeline_shape = |depth_mode| {
+            Self::create_pipeline(
+                context,
+                render_pass.clone(),
+                &[
+                    shader::shape::VertexData::per_vertex(),
+                    shader::shape::InstanceData::per_instance(),
+                ],
+                PrimitiveTopology::TriangleList,
+                PolygonMode::Fill,
+                CullMode::Back,
+                depth_mode,
+                Self::load_entry_point(context.device.clone(), shader::shape::vs::load),
+                Self::load_entry_point(context.device.clone(), shader::shape::fs::load),
+                |_| {},
+            )
+        };
+        let pipeline_shape = create_pipeline_shape(DepthMode::TestWrite);
+        let pipelines_shape_prepass = (
+            create_pipeline_shape(DepthMode::Prepass),
+            create_pipeline_shape(DepthMode::AfterPrepass),
         );
 
         let pipeline_circle = Self::create_pipeline(
@@ -264,6 +555,7 @@ impl RasterizationPipeline {
             PrimitiveTopology::TriangleList,
             PolygonMode::Fill,
             CullMode::None,
+            DepthMode::TestWrite,
             Self::load_entry_point(context.device.clone(), shader::circle::vs::load),
             Self::load_entry_point(context.device.clone(), shader::circle::fs::load),
             |_| {},
@@ -274,42 +566,81 @@ impl RasterizationPipeline {
             .max_per_stage_descriptor_samplers
             .min(properties.max_per_stage_descriptor_sampled_images);
 
-        let pipeline_texture = Self::create_pipeline(
-            context,
-            render_pass.clone(),
-            &[
-                shader::shape::VertexData::per_vertex(),
-                shader::texture::InstanceData::per_instance(),
-            ],
-            PrimitiveTopology::TriangleList,
-            PolygonMode::Fill,
-            CullMode::None,
-            Self::load_entry_point(context.device.clone(), shader::texture::vs::load),
-            Self::load_entry_point(context.device.clone(), shader::texture::fs::load),
-            |create_info| {
-                let binding = create_info.set_layouts[0].bindings.get_mut(&0).unwrap();
-                binding.binding_flags |= DescriptorBindingFlags::VARIABLE_DESCRIPTOR_COUNT;
-                binding.descriptor_count = max_descriptor_count;
-            },
+        let create_pipeline_texture = |depth_mode| {
+            Self::create_pipeline(
+                context,
+                render_pass.clone(),
+                &[
+                    shader::shape::VertexData::per_vertex(),
+                    shader::texture::InstanceData::per_instance(),
+                ],
+                PrimitiveTopology::TriangleList,
+                PolygonMode::Fill,
+                CullMode::None,
+                depth_mode,
+                Self::load_entry_point(context.device.clone(), shader::texture::vs::load),
+                Self::load_fragment_entry_point(
+                    context.device.clone(),
+                    shader::texture::fs::load,
+                    depth_mode,
+                ),
+                |create_info| {
+                    let binding = create_info.set_layouts[0].bindings.get_mut(&0).unwrap();
+                    binding.binding_flags |= DescriptorBindingFlags::VARIABLE_DESCRIPTOR_COUNT;
+                    binding.descriptor_count = max_descriptor_count;
+                },
+            )
+        };
+        let pipeline_texture = create_pipeline_texture(DepthMode::TestWrite);
+        let pipelines_blended = [BlendMode::Additive, BlendMode::Multiply]
+            .into_iter()
+            .map(|blend_mode| {
+                (
+                    blend_mode,
+                    create_pipeline_shape2d(DepthMode::Blended(blend_mode)),
+                    create_pipeline_texture(DepthMode::Blended(blend_mode)),
+                )
+            })
+            .collect();
+
+        let create_pipeline_model = |depth_mode| {
+            Self::create_pipeline(
+                context,
+                render_pass.clone(),
+                &[
+                    shader::model::VertexData::per_vertex(),
+                    shader::model::InstanceData::per_instance(),
+                ],
+                PrimitiveTopology::TriangleList,
+                PolygonMode::Fill,
+                CullMode::Back,
+                depth_mode,
+                Self::load_entry_point(context.device.clone(), shader::model::vs::load),
+                Self::load_entry_point(context.device.clone(), shader::model::fs::load),
+                |create_info| {
+                    let binding = create_info.set_layouts[0].bindings.get_mut(&0).unwrap();
+                    binding.binding_flags |= DescriptorBindingFlags::VARIABLE_DESCRIPTOR_COUNT;
+                    binding.descriptor_count = max_descriptor_count;
+                },
+            )
+        };
+        let pipeline_model = create_pipeline_model(DepthMode::TestWrite);
+        let pipelines_model_prepass = (
+            create_pipeline_model(DepthMode::Prepass),
+            create_pipeline_model(DepthMode::AfterPrepass),
         );
 
-        let pipeline_model = Self::create_pipeline(
+        let pipeline_skybox = Self::create_pipeline(
             context,
             render_pass.clone(),
-            &[
-                shader::model::VertexData::per_vertex(),
-                shader::texture::InstanceData::per_instance(),
-            ],
+            &Vec::<VertexBufferDescription>::new(),
             PrimitiveTopology::TriangleList,
             PolygonMode::Fill,
-            CullMode::Back,
-            Self::load_entry_point(context.device.clone(), shader::model::vs::load),
-            Self::load_entry_point(context.device.clone(), shader::model::fs::load),
-            |create_info| {
-                let binding = create_info.set_layouts[0].bindings.get_mut(&0).unwrap();
-                binding.binding_flags |= DescriptorBindingFlags::VARIABLE_DESCRIPTOR_COUNT;
-                binding.descriptor_count = max_descriptor_count;
-            },
+            CullMode::None,
+            DepthMode::None,
+            Self::load_entry_point(context.device.clone(), shader::skybox::vs::load),
+            Self::load_entry_point(context.device.clone(), shader::skybox::fs::load),
+            |_| {},
         );
 
         (
@@ -321,7 +652,58 @@ impl RasterizationPipeline {
             pipeline_circle,
             pipeline_texture,
             pipeline_model,
+            pipeline_skybox,
+            pipelines_shape_prepass,
+            pipelines_model_prepass,
+            pipelines_blended,
+        )
+    }
+
+    /// Create the pipeline of the second subpass of the multisampling render pass, which draws a full screen triangle
+    /// to average the color samples, and to copy sample 0 of eid and depth. Eid and depth are not averaged because
+    /// an average of entity ids or depths is not the entity id or depth of any drawn object.
+    fn create_resolve_pipeline(
+        context: &RenderContext,
+        render_pass: Arc<RenderPass>,
+    ) -> Arc<GraphicsPipeline> {
+        let stages = [
+            PipelineShaderStageCreateInfo::new(Self::load_entry_point(
+                context.device.clone(),
+                shader::resolve::vs::load,
+            )),
+            PipelineShaderStageCreateInfo::new(Self::load_entry_point(
+                context.device.clone(),
+                shader::resolve::fs::load,
+            )),
+        ];
+        let layout = PipelineLayout::new(
+            context.device.clone(),
+            PipelineDescriptorSetLayoutCreateInfo::from_stages(&stages)
+                .into_pipeline_layout_create_info(context.device.clone())
+                .unwrap(),
         )
+        .unwrap();
+        let subpass = Subpass::from(render_pass, 1).unwrap();
+        GraphicsPipeline::new(
+            context.device.clone(),
+            None,
+            GraphicsPipelineCreateInfo {
+                stages: stages.into_iter().collect(),
+                vertex_input_state: Some(Default::default()),
+                input_assembly_state: Some(InputAssemblyState::default()),
+                rasterization_state: Some(RasterizationState::default()),
+                multisample_state: Some(MultisampleState::default()),
+                color_blend_state: Some(ColorBlendState::with_attachment_states(
+                    subpass.num_color_attachments(),
+                    ColorBlendAttachmentState::default(),
+                )),
+                viewport_state: Some(ViewportState::default()),
+                dynamic_state: [DynamicState::Viewport].into_iter().collect(),
+                subpass: Some(subpass.into()),
+                ..GraphicsPipelineCreateInfo::layout(layout)
+            },
+        )
+        .unwrap()
     }
 
     fn create_pipeline(
@@ -331,6 +713,7 @@ impl RasterizationPipeline {
         topology: PrimitiveTopology,
         polygon_mode: PolygonMode,
         cull_mode: CullMode,
+        depth_mode: DepthMode,
         vs: EntryPoint,
         fs: EntryPoint,
         pipeline_descriptor_set_layout_create_info_modify: impl FnOnce(
@@ -372,19 +755,50 @@ impl RasterizationPipeline {
                     cull_mode,
                     ..Default::default()
                 }),
-                multisample_state: Some(MultisampleState::default()),
+                multisample_state: Some(MultisampleState {
+                    rasterization_samples: subpass.num_samples().unwrap_or(SampleCount::Sample1),
+                    ..Default::default()
+                }),
                 depth_stencil_state: Some(DepthStencilState {
-                    depth: Some(DepthState::simple()),
+                    // we use reverse-Z for better depth precision, see CameraInfo::projection_view_reverse_z
+                    depth: match depth_mode {
+                        DepthMode::None => None,
+                        DepthMode::TestWrite | DepthMode::Prepass => Some(DepthState {
+                            write_enable: true,
+                            compare_op: CompareOp::Greater,
+                        }),
+                        DepthMode::AfterPrepass => Some(DepthState {
+                            write_enable: false,
+                            compare_op: CompareOp::GreaterOrEqual,
+                        }),
+                        DepthMode::Blended(_) => Some(DepthState {
+                            write_enable: false,
+                            compare_op: CompareOp::Greater,
+                        }),
+                    },
                     ..Default::default()
                 }),
                 color_blend_state: Some(ColorBlendState {
-                    attachments: vec![
-                        ColorBlendAttachmentState {
-                            blend: Some(AttachmentBlend::alpha()),
-                            ..Default::default()
-                        },
-                        ColorBlendAttachmentState::default(),
-                    ],
+                    attachments: if depth_mode == DepthMode::Prepass {
+                        vec![
+                            ColorBlendAttachmentState {
+                                color_write_mask: ColorComponents::empty(),
+                                ..Default::default()
+                            };
+                            2
+                        ]
+                    } else {
+                        vec![
+                            ColorBlendAttachmentState {
+                                blend: Some(match depth_mode {
+                                    DepthMode::Blended(blend_mode) => attachment_blend(blend_mode),
+                                    _ => AttachmentBlend::alpha(),
+                                }),
+                                ..Default::default()
+                            },
+                            ColorBlendAttachmentState::default(),
+                        ]
+                    },
                     ..Default::default()
                 }),
                 viewport_state: Some(ViewportState::default()),
@@ -403,13 +817,79 @@ impl RasterizationPipeline {
         load_fn(device).unwrap().entry_point("main").unwrap()
     }
 
-    /// Send all canvas drawing data to the gpu to draw.
+    /// Load the entry point of a fragment shader which has a PREMULTIPLY_ALPHA specialization constant,
+    /// which is only enabled for [BlendMode::Multiply], see [attachment_blend].
+    fn load_fragment_entry_point(
+        device: Arc<Device>,
+        load_fn: impl Fn(Arc<Device>) -> Result<Arc<ShaderModule>, Validated<VulkanError>>,
+        depth_mode: DepthMode,
+    ) -> EntryPoint {
+        let premultiply_alpha = depth_mode == DepthMode::Blended(BlendMode::Multiply);
+        load_fn(device)
+            .unwrap()
+            .specialize(
+                [(0, SpecializationConstant::Bool(premultiply_alpha))]
+                    .into_iter()
+                    .collect(),
+            )
+            .unwrap()
+            .entry_point("main")
+            .unwrap()
+    }
+
+    /// Get the render stats of last frame of window.
+    pub fn stats(&self, window_index: usize) -> RenderStats {
+        self.stats[window_index]
+    }
+
+    /// Get the cached framebuffer at image index of window, a new framebuffer is created
+    /// if there is no cached one or its attachments are changed, for example after resizing.
+    fn get_framebuffer(
+        &mut self,
+        info: &FrameRenderInfo,
+        attachments: Vec<Arc<ImageView>>,
+    ) -> Arc<Framebuffer> {
+        let framebuffers = &mut self.framebuffers[info.window_index];
+        if framebuffers.len() <= info.image_index {
+            framebuffers.resize(info.image_index + 1, None);
+        }
+        let framebuffer = &mut framebuffers[info.image_index];
+        if let Some(framebuffer) = framebuffer {
+            if framebuffer.attachments().len() == attachments.len()
+                && zip(framebuffer.attachments(), &attachments).all(|(a, b)| Arc::ptr_eq(a, b))
+            {
+                return framebuffer.clone();
+            }
+        }
+        log::trace!(
+            "Create framebuffer, window_index={}, image_index={}",
+            info.window_index,
+            info.image_index
+        );
+        framebuffer
+            .insert(
+                Framebuffer::new(
+                    self.render_pass.clone(),
+                    FramebufferCreateInfo {
+                        attachments,
+                        ..Default::default()
+                    },
+                )
+                .unwrap(),
+            )
+            .clone()
+    }
+
+    /// Send all canvas drawing data to the gpu to draw into the viewport of camera. If first_camera is true,
+    /// the whole image is cleared, otherwise only the viewport is cleared and the stats are accumulated.
     pub fn draw(
-        &self,
+        &mut self,
         context: &RenderContext,
         info: &FrameRenderInfo,
         camera: &CameraInfo,
+        first_camera: bool,
         settings: &RasterizationSettings,
+        skybox: AssetId,
         canvas: &Canvas,
         model_assets: &mut ModelAssets,
         texture_assets: &mut TextureAssets,
@@ -418,25 +898,43 @@ impl RasterizationPipeline {
         platform: &Platform,
         eid_image: Arc<ImageView>,
     ) -> Arc<PrimaryAutoCommandBuffer> {
+        let (viewport_offset, viewport_extent) = camera.viewport_rect(info.window_size);
         let viewport = Viewport {
-            offset: [0.0, 0.0],
-            extent: info.window_size.as_vec2().to_array(),
+            offset: viewport_offset.as_vec2().to_array(),
+            extent: viewport_extent.as_vec2().to_array(),
             depth_range: 0.0..=1.0,
         };
+        let (render_area_offset, render_area_extent) = if first_camera {
+            ([0, 0], info.window_size.to_array())
+        } else {
+            (viewport_offset.to_array(), viewport_extent.to_array())
+        };
 
-        let framebuffer = Framebuffer::new(
-            // TODO: pre-create framebuffers when we can get swapchain image views from VulkanoWindowRenderer
-            self.render_pass.clone(),
-            FramebufferCreateInfo {
-                attachments: vec![
-                    info.image.clone(),
-                    eid_image,
-                    self.depth_stencil_images[info.window_index][info.image_index].clone(),
-                ],
-                ..Default::default()
-            },
-        )
-        .unwrap();
+        let depth_stencil_image =
+            self.depth_stencil_images[info.window_index][info.image_index].clone();
+        let mut clear_values = vec![
+            Some(settings.clear_color.to_array().into()),
+            Some(crate::render::canvas::eid_to_u32_array(EntityId::dead()).into()),
+            Some(0.0.into()), // reverse-Z: far plane is at depth 0
+        ];
+        let attachments = if self.samples == SampleCount::Sample1 {
+            vec![info.image.clone(), eid_image, depth_stencil_image]
+        } else {
+            let (color_multisample_image, eid_multisample_image) =
+                self.multisample_images[info.window_index][info.image_index].clone();
+            let resolved_depth_image =
+                self.resolved_depth_images[info.window_index][info.image_index].clone();
+            clear_values.extend([None, None, None]); // resolve attachments are not cleared
+            vec![
+                color_multisample_image,
+                eid_multisample_image,
+                depth_stencil_image,
+                info.image.clone(),
+                eid_image,
+                resolved_depth_image,
+            ]
+        };
+        let framebuffer = self.get_framebuffer(info, attachments);
 
         let mut command_buffer_builder = AutoCommandBufferBuilder::primary(
             &context.command_buffer_allocator,
@@ -449,11 +947,9 @@ impl RasterizationPipeline {
             .unwrap()
             .begin_render_pass(
                 RenderPassBeginInfo {
-                    clear_values: vec![
-                        Some(settings.clear_color.to_array().into()),
-                        Some(crate::render::canvas::eid_to_u32_array(EntityId::dead()).into()),
-                        Some(1.0.into()),
-                    ],
+                    clear_values,
+                    render_area_offset,
+                    render_area_extent,
                     ..RenderPassBeginInfo::framebuffer(framebuffer.clone())
                 },
                 SubpassBeginInfo {
@@ -463,16 +959,36 @@ impl RasterizationPipeline {
             )
             .unwrap();
 
-        let projection_view = camera.projection_view(&info.window_size);
+        let projection_view = camera.projection_view_reverse_z(&viewport_extent);
         let push_constants = shader::vertex::vs::PushConstants {
             projection_view: projection_view.to_cols_array_2d(),
         };
+        let mut stats = if first_camera {
+            RenderStats::new(settings.instance_budget)
+        } else {
+            self.stats[info.window_index]
+        };
 
+        draw_skybox(
+            skybox,
+            projection_view,
+            self.pipeline_skybox.clone(),
+            &mut command_buffer_builder,
+            &mut stats,
+            TextureContext {
+                render_context: context,
+                texture_assets,
+                image_assets,
+                asset_manager,
+                platform,
+            },
+        );
         draw_points(
             &canvas.points,
             self.pipeline_point.clone(),
             context.memory_allocator.clone(),
             &mut command_buffer_builder,
+            &mut stats,
             push_constants,
         );
         draw_lines(
@@ -480,6 +996,7 @@ impl RasterizationPipeline {
             self.pipeline_line.clone(),
             context.memory_allocator.clone(),
             &mut command_buffer_builder,
+            &mut stats,
             push_constants,
         );
         draw_triangles(
@@ -487,6 +1004,7 @@ impl RasterizationPipeline {
             self.pipeline_triangle.clone(),
             context.memory_allocator.clone(),
             &mut command_buffer_builder,
+            &mut stats,
             push_constants,
         );
         draw_shapes(
@@ -498,6 +1016,7 @@ impl RasterizationPipeline {
             self.pipeline_shape2d.clone(),
             context.memory_allocator.clone(),
             &mut command_buffer_builder,
+            &mut stats,
             push_constants,
             mesh::RECTANGLE_VERTICES.map(|(p, ..)| p).to_vec(),
             mesh::RECTANGLE_INDICES.to_vec(),
@@ -507,7 +1026,11 @@ impl RasterizationPipeline {
             self.pipeline_circle.clone(),
             context.memory_allocator.clone(),
             &mut command_buffer_builder,
-            push_constants,
+            &mut stats,
+            shader::circle::vs::PushConstants {
+                projection_view: push_constants.projection_view,
+                edge_smoothing: settings.circle_edge_smoothing,
+            },
             mesh::RECTANGLE_VERTICES.map(|(p, ..)| p).to_vec(),
             mesh::RECTANGLE_INDICES.to_vec(),
         );
@@ -515,10 +1038,13 @@ impl RasterizationPipeline {
             &canvas
                 .textures
                 .iter()
-                .map(|(texture, model, color, _, eid)| (*texture, *model, *color, *eid))
+                .map(|(texture, model, color, _, tiling, eid)| {
+                    (*texture, *model, *color, *tiling, *eid)
+                })
                 .collect(),
             self.pipeline_texture.clone(),
             &mut command_buffer_builder,
+            &mut stats,
             push_constants,
             context,
             texture_assets,
@@ -526,52 +1052,165 @@ impl RasterizationPipeline {
             asset_manager,
             platform,
         );
-        draw_shapes(
-            &canvas
-                .cuboids
-                .iter()
-                .map(|(model, color, _, eid)| (*model, *color, *eid))
-                .collect(),
-            self.pipeline_shape.clone(),
-            context.memory_allocator.clone(),
-            &mut command_buffer_builder,
-            push_constants,
-            mesh::CUBOID_VERTICES.to_vec(),
-            mesh::CUBOID_INDICES.to_vec(),
-        );
-        draw_shapes(
-            &canvas
-                .spheres
-                .iter()
-                .map(|(model, color, _, eid)| (*model, *color, *eid))
-                .collect(),
-            self.pipeline_shape.clone(),
-            context.memory_allocator.clone(),
-            &mut command_buffer_builder,
-            push_constants,
-            mesh::SPHERE_VERTICES.to_vec(),
-            mesh::SPHERE_INDICES.to_vec(),
-        );
-        draw_models(
-            &canvas
-                .models
-                .iter()
-                .map(
-                    |(model_asset, texture_asset, model_matrix, color, _, eid)| {
-                        (*model_asset, *texture_asset, *model_matrix, *color, *eid)
-                    },
-                )
-                .collect(),
-            self.pipeline_model.clone(),
-            &mut command_buffer_builder,
-            push_constants,
-            context,
-            model_assets,
-            texture_assets,
-            image_assets,
-            asset_manager,
-            platform,
-        );
+        let cuboids = canvas
+            .cuboids
+            .iter()
+            .map(|(model, color, _, eid)| (*model, *color, *eid))
+            .collect();
+        let spheres = canvas
+            .spheres
+            .iter()
+            .map(|(model, color, _, eid)| (*model, *color, *eid))
+            .collect();
+        // group meshes by Arc pointer so that each mesh is drawn in one draw call
+        let mut meshes = Vec::<(Arc<MeshData>, Vec<_>)>::new();
+        for (mesh_data, model, color, _, eid) in &canvas.meshes {
+            match meshes.iter_mut().find(|(m, _)| Arc::ptr_eq(m, mesh_data)) {
+                Some((_, instances)) => instances.push((*model, *color, *eid)),
+                None => meshes.push((mesh_data.clone(), vec![(*model, *color, *eid)])),
+            }
+        }
+        let models = canvas
+            .models
+            .iter()
+            .map(
+                |(
+                    model_asset,
+                    texture_asset,
+                    normal_map_asset,
+                    model_matrix,
+                    color,
+                    _,
+                    tiling,
+                    eid,
+                )| {
+                    (
+                        *model_asset,
+                        *texture_asset,
+                        *normal_map_asset,
+                        *model_matrix,
+                        *color,
+                        *tiling,
+                        *eid,
+                    )
+                },
+            )
+            .collect();
+        // with depth prepass, 3D shapes and models are drawn twice: depth only, and then colors with equal depth
+        let passes = if settings.depth_prepass {
+            vec![
+                (
+                    self.pipelines_shape_prepass.0.clone(),
+                    self.pipelines_model_prepass.0.clone(),
+                ),
+                (
+                    self.pipelines_shape_prepass.1.clone(),
+                    self.pipelines_model_prepass.1.clone(),
+                ),
+            ]
+        } else {
+            vec![(self.pipeline_shape.clone(), self.pipeline_model.clone())]
+        };
+        for (pipeline_shape, pipeline_model) in passes {
+            draw_shapes(
+                &cuboids,
+                pipeline_shape.clone(),
+                context.memory_allocator.clone(),
+                &mut command_buffer_builder,
+                &mut stats,
+                push_constants,
+                mesh::CUBOID_VERTICES.to_vec(),
+                mesh::CUBOID_INDICES.to_vec(),
+            );
+            draw_shapes(
+                &spheres,
+                pipeline_shape.clone(),
+                context.memory_allocator.clone(),
+                &mut command_buffer_builder,
+                &mut stats,
+                push_constants,
+                mesh::SPHERE_VERTICES.to_vec(),
+                mesh::SPHERE_INDICES.to_vec(),
+            );
+            for (mesh_data, instances) in &meshes {
+                draw_shapes(
+                    instances,
+                    pipeline_shape.clone(),
+                    context.memory_allocator.clone(),
+                    &mut command_buffer_builder,
+                    &mut stats,
+                    push_constants,
+                    mesh_data.vertices.clone(),
+                    mesh_data.indices.clone(),
+                );
+            }
+            draw_models(
+                &models,
+                pipeline_model,
+                &mut command_buffer_builder,
+                &mut stats,
+                push_constants,
+                context,
+                model_assets,
+                texture_assets,
+                image_assets,
+                asset_manager,
+                platform,
+            );
+        }
+        // blended draws are drawn at last without depth write, so that they do not hide each other
+        for (blend_mode, pipeline_shape2d, pipeline_texture) in &self.pipelines_blended {
+            draw_shapes(
+                &canvas
+                    .blended_rectangles
+                    .iter()
+                    .filter(|(b, ..)| b == blend_mode)
+                    .map(|(_, model, color, eid)| (*model, *color, *eid))
+                    .collect(),
+                pipeline_shape2d.clone(),
+                context.memory_allocator.clone(),
+                &mut command_buffer_builder,
+                &mut stats,
+                push_constants,
+                mesh::RECTANGLE_VERTICES.map(|(p, ..)| p).to_vec(),
+                mesh::RECTANGLE_INDICES.to_vec(),
+            );
+            draw_textures(
+                &canvas
+                    .blended_textures
+                    .iter()
+                    .filter(|(b, ..)| b == blend_mode)
+                    .map(|(_, texture, model, color, tiling, eid)| {
+                        (*texture, *model, *color, *tiling, *eid)
+                    })
+                    .collect(),
+                pipeline_texture.clone(),
+                &mut command_buffer_builder,
+                &mut stats,
+                push_constants,
+                context,
+                texture_assets,
+                image_assets,
+                asset_manager,
+                platform,
+            );
+        }
+        self.stats[info.window_index] = stats;
+
+        if let Some(pipeline_resolve) = &self.pipeline_resolve {
+            resolve_multisample(
+                pipeline_resolve.clone(),
+                &framebuffer,
+                u32::from(self.samples),
+                Viewport {
+                    offset: [render_area_offset[0] as f32, render_area_offset[1] as f32],
+                    extent: [render_area_extent[0] as f32, render_area_extent[1] as f32],
+                    depth_range: 0.0..=1.0,
+                },
+                &mut command_buffer_builder,
+                context,
+            );
+        }
 
         command_buffer_builder
             .end_render_pass(Default::default())
@@ -585,11 +1224,160 @@ impl RasterizationPipeline {
     }
 }
 
+/// Get the [AttachmentBlend] of the color attachment for blend mode.
+fn attachment_blend(blend_mode: BlendMode) -> AttachmentBlend {
+    match blend_mode {
+        BlendMode::Alpha => AttachmentBlend::alpha(),
+        // color = src * src_alpha + dst, alpha is not changed
+        BlendMode::Additive => AttachmentBlend {
+            src_color_blend_factor: BlendFactor::SrcAlpha,
+            dst_color_blend_factor: BlendFactor::One,
+            color_blend_op: BlendOp::Add,
+            src_alpha_blend_factor: BlendFactor::Zero,
+            dst_alpha_blend_factor: BlendFactor::One,
+            alpha_blend_op: BlendOp::Add,
+        },
+        // color = src * src_alpha * dst + dst * (1 - src_alpha) = dst * lerp(1, src, src_alpha),
+        // src is premultiplied by alpha in shader, alpha is not changed
+        BlendMode::Multiply => AttachmentBlend {
+            src_color_blend_factor: BlendFactor::DstColor,
+            dst_color_blend_factor: BlendFactor::OneMinusSrcAlpha,
+            color_blend_op: BlendOp::Add,
+            src_alpha_blend_factor: BlendFactor::Zero,
+            dst_alpha_blend_factor: BlendFactor::One,
+            alpha_blend_op: BlendOp::Add,
+        },
+    }
+}
+
+/// Go to the second subpass and resolve the multisampled attachments of framebuffer in viewport,
+/// which should cover the whole render area.
+fn resolve_multisample(
+    pipeline: Arc<GraphicsPipeline>,
+    framebuffer: &Arc<Framebuffer>,
+    samples: u32,
+    viewport: Viewport,
+    command_buffer_builder: &mut AutoCommandBufferBuilder<PrimaryAutoCommandBuffer>,
+    render_context: &RenderContext,
+) {
+    let attachments = framebuffer.attachments();
+    let descriptor_set = PersistentDescriptorSet::new(
+        &render_context.descriptor_set_allocator,
+        pipeline.layout().set_layouts()[0].clone(),
+        [
+            WriteDescriptorSet::image_view(0, attachments[0].clone()),
+            WriteDescriptorSet::image_view(1, attachments[1].clone()),
+            WriteDescriptorSet::image_view(2, attachments[2].clone()),
+        ],
+        [],
+    )
+    .unwrap();
+
+    command_buffer_builder
+        .next_subpass(
+            Default::default(),
+            SubpassBeginInfo {
+                contents: SubpassContents::Inline,
+                ..Default::default()
+            },
+        )
+        .unwrap()
+        .set_viewport(0, [viewport].into_iter().collect())
+        .unwrap()
+        .bind_pipeline_graphics(pipeline.clone())
+        .unwrap()
+        .push_constants(
+            pipeline.layout().clone(),
+            0,
+            shader::resolve::fs::PushConstants {
+                samples: samples as i32,
+            },
+        )
+        .unwrap()
+        .bind_descriptor_sets(
+            PipelineBindPoint::Graphics,
+            pipeline.layout().clone(),
+            0,
+            descriptor_set,
+        )
+        .unwrap()
+        .draw(3, 1, 0, 0)
+        .unwrap();
+}
+
+/// The render context, assets, and platform which are needed to get a texture when drawing.
+struct TextureContext<'a> {
+    render_context: &'a RenderContext,
+    texture_assets: &'a mut TextureAssets,
+    image_assets: &'a mut ImageAssets,
+    asset_manager: &'a mut AssetManager,
+    platform: &'a Platform,
+}
+
+impl TextureContext<'_> {
+    fn get_texture(&mut self, asset_id: AssetId) -> Option<(Arc<ImageView>, Arc<Sampler>)> {
+        self.texture_assets.get_texture(
+            asset_id,
+            self.image_assets,
+            self.asset_manager,
+            self.platform,
+            self.render_context,
+        )
+    }
+}
+
+fn draw_skybox(
+    skybox: AssetId,
+    projection_view: Mat4,
+    pipeline: Arc<GraphicsPipeline>,
+    command_buffer_builder: &mut AutoCommandBufferBuilder<PrimaryAutoCommandBuffer>,
+    stats: &mut RenderStats,
+    mut texture_context: TextureContext,
+) {
+    let Some((image_view, sampler)) = texture_context.get_texture(skybox) else {
+        return;
+    };
+    stats.record("skybox", 1, 3);
+
+    let descriptor_set = PersistentDescriptorSet::new(
+        &texture_context.render_context.descriptor_set_allocator,
+        pipeline.layout().set_layouts()[0].clone(),
+        [WriteDescriptorSet::image_view_sampler(
+            0, image_view, sampler,
+        )],
+        [],
+    )
+    .unwrap();
+
+    command_buffer_builder
+        .bind_pipeline_graphics(pipeline.clone())
+        .unwrap()
+        .push_constants(
+            pipeline.layout().clone(),
+            0,
+            shader::skybox::fs::PushConstants {
+                inverse_projection_view: projection_view.inverse().to_cols_array_2d(),
+                eid: crate::render::canvas::eid_to_u32_array(EntityId::dead()),
+            },
+        )
+        .unwrap()
+        .bind_descriptor_sets(
+            PipelineBindPoint::Graphics,
+            pipeline.layout().clone(),
+            0,
+            descriptor_set,
+        )
+        .unwrap()
+        .draw(3, 1, 0, 0)
+        .unwrap();
+}
+
 fn draw_points(
     points: &Vec<(Vec3, Vec4, EntityId)>,
     pipeline: Arc<GraphicsPipeline>,
     memory_allocator: Arc<StandardMemoryAllocator>,
     command_buffer_builder: &mut AutoCommandBufferBuilder<PrimaryAutoCommandBuffer>,
+    stats: &mut RenderStats,
     push_constants: shader::vertex::vs::PushConstants,
 ) {
     if points.is_empty() {
@@ -606,6 +1394,7 @@ fn draw_points(
         pipeline,
         memory_allocator,
         command_buffer_builder,
+        stats,
         push_constants,
     );
 }
@@ -615,6 +1404,7 @@ fn draw_lines(
     pipeline: Arc<GraphicsPipeline>,
     memory_allocator: Arc<StandardMemoryAllocator>,
     command_buffer_builder: &mut AutoCommandBufferBuilder<PrimaryAutoCommandBuffer>,
+    stats: &mut RenderStats,
     push_constants: shader::vertex::vs::PushConstants,
 ) {
     if lines.is_empty() {
@@ -632,6 +1422,7 @@ fn draw_lines(
         pipeline,
         memory_allocator,
         command_buffer_builder,
+        stats,
         push_constants,
     );
 }
@@ -641,6 +1432,7 @@ fn draw_triangles(
     pipeline: Arc<GraphicsPipeline>,
     memory_allocator: Arc<StandardMemoryAllocator>,
     command_buffer_builder: &mut AutoCommandBufferBuilder<PrimaryAutoCommandBuffer>,
+    stats: &mut RenderStats,
     push_constants: shader::vertex::vs::PushConstants,
 ) {
     if triangles.is_empty() {
@@ -658,6 +1450,7 @@ fn draw_triangles(
         pipeline,
         memory_allocator,
         command_buffer_builder,
+        stats,
         push_constants,
     );
 }
@@ -667,8 +1460,10 @@ fn draw_vertices(
     pipeline: Arc<GraphicsPipeline>,
     memory_allocator: Arc<StandardMemoryAllocator>,
     command_buffer_builder: &mut AutoCommandBufferBuilder<PrimaryAutoCommandBuffer>,
+    stats: &mut RenderStats,
     push_constants: shader::vertex::vs::PushConstants,
 ) {
+    stats.record("vertices", 1, vertices.len() as u32);
     let vertex_buffer = create_buffer(vertices, &memory_allocator, BufferUsage::VERTEX_BUFFER);
 
     command_buffer_builder
@@ -687,7 +1482,8 @@ fn draw_shapes(
     pipeline: Arc<GraphicsPipeline>,
     memory_allocator: Arc<StandardMemoryAllocator>,
     command_buffer_builder: &mut AutoCommandBufferBuilder<PrimaryAutoCommandBuffer>,
-    push_constants: shader::vertex::vs::PushConstants,
+    stats: &mut RenderStats,
+    push_constants: impl BufferContents,
     vertices: Vec<Vec3>,
     indices: Vec<u16>,
 ) {
@@ -703,6 +1499,7 @@ fn draw_shapes(
         .map(|(model, color, eid)| shader::shape::InstanceData::new(*color, *eid, *model))
         .collect::<Vec<_>>();
 
+    stats.record("shapes", instances.len() as u32, indices.len() as u32);
     let vertex_buffer = create_buffer(vertices, &memory_allocator, BufferUsage::VERTEX_BUFFER);
     let index_buffer = create_buffer(indices, &memory_allocator, BufferUsage::INDEX_BUFFER);
     let instance_buffer = create_buffer(instances, &memory_allocator, BufferUsage::VERTEX_BUFFER);
@@ -727,9 +1524,10 @@ fn draw_shapes(
 }
 
 fn draw_textures(
-    textures: &Vec<(AssetId, Affine3A, Vec4, EntityId)>,
+    textures: &Vec<(AssetId, Affine3A, Vec4, TextureTiling, EntityId)>,
     pipeline: Arc<GraphicsPipeline>,
     command_buffer_builder: &mut AutoCommandBufferBuilder<PrimaryAutoCommandBuffer>,
+    stats: &mut RenderStats,
     push_constants: shader::vertex::vs::PushConstants,
     render_context: &RenderContext,
     texture_assets: &mut TextureAssets,
@@ -744,7 +1542,7 @@ fn draw_textures(
     let mut instances = Vec::new();
     let mut image_view_samplers = Vec::new();
     let mut image_to_index = HashMap::new();
-    for (asset, model, color, eid) in textures {
+    for (asset, model, color, tiling, eid) in textures {
         if let Some((image_view, sampler)) = texture_assets.get_texture(
             *asset,
             image_assets,
@@ -763,7 +1561,11 @@ fn draw_textures(
                 image_view_samplers.len() - 1
             });
             instances.push(shader::texture::InstanceData::new(
-                *color, *eid, index, model,
+                *color,
+                *eid,
+                index,
+                tiling.to_vec4(),
+                model,
             ));
         }
     }
@@ -780,6 +1582,7 @@ fn draw_textures(
     ]
     .map(|v| shader::shape::VertexData::new(v));
     let indices = [0u16, 1, 2, 2, 3, 0];
+    stats.record("textures", instances.len() as u32, indices.len() as u32);
 
     let vertex_buffer = create_buffer(
         vertices,
@@ -836,9 +1639,18 @@ fn draw_textures(
 }
 
 fn draw_models(
-    models: &Vec<(AssetId, AssetId, Affine3A, Vec4, EntityId)>,
+    models: &Vec<(
+        AssetId,
+        AssetId,
+        AssetId,
+        Affine3A,
+        Vec4,
+        TextureTiling,
+        EntityId,
+    )>,
     pipeline: Arc<GraphicsPipeline>,
     command_buffer_builder: &mut AutoCommandBufferBuilder<PrimaryAutoCommandBuffer>,
+    stats: &mut RenderStats,
     push_constants: shader::vertex::vs::PushConstants,
     render_context: &RenderContext,
     model_assets: &mut ModelAssets,
@@ -857,13 +1669,18 @@ fn draw_models(
     let mut model_to_index = HashMap::new();
     let mut image_view_samplers = Vec::new();
     let mut image_to_index = HashMap::new();
-    for (model_asset, texture_asset, model_matrix, color, eid) in models {
+    for (model_asset, texture_asset, normal_map_asset, model_matrix, color, tiling, eid) in models {
         if let Some(model) = model_assets.get_model(*model_asset, asset_manager, platform) {
             let index = *model_to_index.entry(*model_asset).or_insert_with(|| {
-                let vertices = model.vertices.iter().map(|v| shader::model::VertexData {
-                    position: v.position,
-                    // the OBJ format assumes a coordinate system where a vertical coordinate of 0 means the bottom of the image
-                    tex_coord: [v.texture[0], 1.0 - v.texture[1]],
+                let vertices = model.vertices.iter().enumerate().map(|(i, v)| {
+                    shader::model::VertexData {
+                        position: v.position,
+                        // the OBJ format assumes a coordinate system where a vertical coordinate of 0 means the bottom of the image
+                        tex_coord: [v.texture[0], 1.0 - v.texture[1]],
+                        vertex_color: model.vertex_color(i),
+                        normal: v.normal,
+                        tangent: model.vertex_tangent(i),
+                    }
                 });
                 let vertex_buffer = create_buffer(
                     vertices,
@@ -880,24 +1697,36 @@ fn draw_models(
                 instances.push(Vec::new());
                 instances.len() - 1
             });
-            let texture_index = if let Some((image_view, sampler)) = texture_assets.get_texture(
+            let mut get_texture_index = |texture: Option<(Arc<ImageView>, Arc<Sampler>)>| {
+                if let Some((image_view, sampler)) = texture {
+                    *image_to_index.entry(image_view.clone()).or_insert_with(|| {
+                        image_view_samplers.push((image_view, sampler));
+                        image_view_samplers.len() - 1
+                    })
+                } else {
+                    u32::MAX as usize
+                }
+            };
+            let texture_index = get_texture_index(texture_assets.get_texture(
                 *texture_asset,
                 image_assets,
                 asset_manager,
                 platform,
                 render_context,
-            ) {
-                *image_to_index.entry(image_view.clone()).or_insert_with(|| {
-                    image_view_samplers.push((image_view, sampler));
-                    image_view_samplers.len() - 1
-                })
-            } else {
-                u32::MAX as usize
-            };
-            instances[index].push(shader::texture::InstanceData::new(
+            ));
+            let normal_map_index = get_texture_index(texture_assets.get_normal_map(
+                *normal_map_asset,
+                image_assets,
+                asset_manager,
+                platform,
+                render_context,
+            ));
+            instances[index].push(shader::model::InstanceData::new(
                 *color,
                 *eid,
                 texture_index,
+                normal_map_index,
+                tiling.to_vec4(),
                 *model_matrix,
             ));
         }
@@ -940,6 +1769,7 @@ fn draw_models(
     for (instances, (vertex_buffer, index_buffer)) in
         zip(instances, zip(vertex_buffers, index_buffers))
     {
+        stats.record("models", instances.len() as u32, index_buffer.len() as u32);
         let instance_buffer = create_buffer(
             instances,
             &render_context.memory_allocator,
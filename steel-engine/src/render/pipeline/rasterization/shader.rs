@@ -152,6 +152,9 @@ pub mod shape {
             src: r"
                 #version 460
 
+                // premultiply color by alpha for multiply blending
+                layout(constant_id = 0) const bool PREMULTIPLY_ALPHA = false;
+
                 layout(location = 0) flat in vec4 in_color;
                 layout(location = 1) flat in uvec2 in_eid;
 
@@ -161,6 +164,9 @@ pub mod shape {
                 void main() {
                     f_color = in_color;
                     f_color = vec4(pow(f_color.xyz, vec3(1.0 / 2.2)), f_color.w); // gamma correction
+                    if (PREMULTIPLY_ALPHA) {
+                        f_color.xyz *= f_color.w;
+                    }
                     if (f_color.w > 0.0001) {
                         f_eid = in_eid;
                     }
@@ -181,6 +187,7 @@ pub mod circle {
 
                 layout(push_constant) uniform PushConstants {
                     mat4 projection_view;
+                    float edge_smoothing;
                 } pcs;
 
                 layout(location = 0) in vec3 position;
@@ -209,6 +216,11 @@ pub mod circle {
             src: r"
                 #version 460
 
+                layout(push_constant) uniform PushConstants {
+                    mat4 projection_view;
+                    float edge_smoothing; // width of the antialiased edge in pixels, 0 means hard edge
+                } pcs;
+
                 layout(location = 0) in vec2 in_position;
                 layout(location = 1) flat in vec4 in_color;
                 layout(location = 2) flat in uvec2 in_eid;
@@ -217,10 +229,14 @@ pub mod circle {
                 layout(location = 1) out uvec2 f_eid;
 
                 void main() {
-                    if (distance(vec2(0, 0), in_position) > 0.5) {
+                    float d = length(in_position);
+                    // use screen-space derivative to keep the edge width constant in pixels at any radius
+                    float edge = fwidth(d) * pcs.edge_smoothing;
+                    float coverage = edge > 0.0 ? 1.0 - smoothstep(0.5 - edge, 0.5, d) : step(d, 0.5);
+                    if (coverage <= 0.0) {
                         discard;
                     }
-                    f_color = in_color;
+                    f_color = vec4(in_color.xyz, in_color.w * coverage);
                     f_color = vec4(pow(f_color.xyz, vec3(1.0 / 2.2)), f_color.w); // gamma correction
                     if (f_color.w > 0.0001) {
                         f_eid = in_eid;
@@ -247,16 +263,26 @@ pub mod texture {
         pub eid: [u32; 2],
         #[format(R32_UINT)]
         pub index: u32,
+        /// xy is the scale and zw is the offset of texture coordinates.
+        #[format(R32G32B32A32_SFLOAT)]
+        pub tiling: [f32; 4],
         #[format(R32G32B32A32_SFLOAT)]
         pub model: [[f32; 4]; 4],
     }
 
     impl InstanceData {
-        pub fn new(color: Vec4, eid: EntityId, index: usize, model: Affine3A) -> Self {
+        pub fn new(
+            color: Vec4,
+            eid: EntityId,
+            index: usize,
+            tiling: Vec4,
+            model: Affine3A,
+        ) -> Self {
             InstanceData {
                 color: color.to_array(),
                 eid: crate::render::canvas::eid_to_u32_array(eid),
                 index: index as u32,
+                tiling: tiling.to_array(),
                 model: Mat4::from(model).to_cols_array_2d(),
             }
         }
@@ -277,7 +303,8 @@ pub mod texture {
                 layout(location = 1) in vec4 color;
                 layout(location = 2) in uvec2 eid;
                 layout(location = 3) in uint index;
-                layout(location = 4) in mat4 model;
+                layout(location = 4) in vec4 tiling;
+                layout(location = 5) in mat4 model;
 
                 layout(location = 0) out vec2 tex_coord;
                 layout(location = 1) out vec4 out_color;
@@ -286,7 +313,7 @@ pub mod texture {
 
                 void main() {
                     gl_Position = pcs.projection_view * model * vec4(position, 1.0);
-                    tex_coord = position.xy * vec2(1.0, -1.0) + vec2(0.5);
+                    tex_coord = (position.xy * vec2(1.0, -1.0) + vec2(0.5)) * tiling.xy + tiling.zw;
                     out_color = color;
                     out_eid = eid;
                     out_index = index;
@@ -304,6 +331,9 @@ pub mod texture {
 
                 layout(set = 0, binding = 0) uniform sampler2D[] tex;
 
+                // premultiply color by alpha for multiply blending
+                layout(constant_id = 0) const bool PREMULTIPLY_ALPHA = false;
+
                 layout(location = 0) in vec2 tex_coord;
                 layout(location = 1) flat in vec4 in_color;
                 layout(location = 2) flat in uvec2 in_eid;
@@ -318,6 +348,9 @@ pub mod texture {
                         discard;
                     }
                     f_color = vec4(pow(f_color.xyz, vec3(1.0 / 2.2)), f_color.w); // gamma correction
+                    if (PREMULTIPLY_ALPHA) {
+                        f_color.xyz *= f_color.w;
+                    }
                     if (f_color.w > 0.0001) {
                         f_eid = in_eid;
                     }
@@ -328,8 +361,9 @@ pub mod texture {
 }
 
 /// The shader to draw models.
-/// Use [super::texture::InstanceData].
 pub mod model {
+    use glam::{Affine3A, Mat4, Vec4};
+    use shipyard::EntityId;
     use vulkano::{buffer::BufferContents, pipeline::graphics::vertex_input::Vertex};
 
     #[derive(BufferContents, Vertex, Clone)]
@@ -339,6 +373,50 @@ pub mod model {
         pub position: [f32; 3],
         #[format(R32G32_SFLOAT)]
         pub tex_coord: [f32; 2],
+        #[format(R32G32B32A32_SFLOAT)]
+        pub vertex_color: [f32; 4],
+        #[format(R32G32B32_SFLOAT)]
+        pub normal: [f32; 3],
+        #[format(R32G32B32A32_SFLOAT)]
+        pub tangent: [f32; 4],
+    }
+
+    #[derive(BufferContents, Vertex, Clone)]
+    #[repr(C)]
+    pub struct InstanceData {
+        #[format(R32G32B32A32_SFLOAT)]
+        pub color: [f32; 4],
+        #[format(R32G32_UINT)]
+        pub eid: [u32; 2],
+        #[format(R32_UINT)]
+        pub index: u32,
+        #[format(R32_UINT)]
+        pub normal_map_index: u32,
+        /// xy is the scale and zw is the offset of texture coordinates.
+        #[format(R32G32B32A32_SFLOAT)]
+        pub tiling: [f32; 4],
+        #[format(R32G32B32A32_SFLOAT)]
+        pub model: [[f32; 4]; 4],
+    }
+
+    impl InstanceData {
+        pub fn new(
+            color: Vec4,
+            eid: EntityId,
+            index: usize,
+            normal_map_index: usize,
+            tiling: Vec4,
+            model: Affine3A,
+        ) -> Self {
+            InstanceData {
+                color: color.to_array(),
+                eid: crate::render::canvas::eid_to_u32_array(eid),
+                index: index as u32,
+                normal_map_index: normal_map_index as u32,
+                tiling: tiling.to_array(),
+                model: Mat4::from(model).to_cols_array_2d(),
+            }
+        }
     }
 
     pub mod vs {
@@ -353,23 +431,34 @@ pub mod model {
 
                 layout(location = 0) in vec3 position;
                 layout(location = 1) in vec2 tex_coord;
+                layout(location = 2) in vec4 vertex_color;
+                layout(location = 3) in vec3 normal;
+                layout(location = 4) in vec4 tangent;
                 // instance data
-                layout(location = 2) in vec4 color;
-                layout(location = 3) in uvec2 eid;
-                layout(location = 4) in uint index;
-                layout(location = 5) in mat4 model;
+                layout(location = 5) in vec4 color;
+                layout(location = 6) in uvec2 eid;
+                layout(location = 7) in uint index;
+                layout(location = 8) in uint normal_map_index;
+                layout(location = 9) in vec4 tiling;
+                layout(location = 10) in mat4 model;
 
                 layout(location = 0) out vec2 out_tex_coord;
                 layout(location = 1) out vec4 out_color;
                 layout(location = 2) out uvec2 out_eid;
                 layout(location = 3) out uint out_index;
+                layout(location = 4) out uint out_normal_map_index;
+                layout(location = 5) out vec3 out_normal;
+                layout(location = 6) out vec4 out_tangent;
 
                 void main() {
                     gl_Position = pcs.projection_view * model * vec4(position, 1.0);
-                    out_tex_coord = tex_coord;
-                    out_color = color;
+                    out_tex_coord = tex_coord * tiling.xy + tiling.zw;
+                    out_color = color * vertex_color;
                     out_eid = eid;
                     out_index = index;
+                    out_normal_map_index = normal_map_index;
+                    out_normal = transpose(inverse(mat3(model))) * normal;
+                    out_tangent = vec4(mat3(model) * tangent.xyz, tangent.w);
                 }
             ",
         }
@@ -385,9 +474,12 @@ pub mod model {
                 layout(set = 0, binding = 0) uniform sampler2D[] tex;
 
                 layout(location = 0) in vec2 tex_coord;
-                layout(location = 1) flat in vec4 in_color;
+                layout(location = 1) in vec4 in_color;
                 layout(location = 2) flat in uvec2 in_eid;
                 layout(location = 3) flat in uint i;
+                layout(location = 4) flat in uint normal_map_index;
+                layout(location = 5) in vec3 in_normal;
+                layout(location = 6) in vec4 in_tangent;
 
                 layout(location = 0) out vec4 f_color;
                 layout(location = 1) out uvec2 f_eid;
@@ -398,6 +490,15 @@ pub mod model {
                     if (i != MAX_UINT) {
                         f_color *= texture(tex[i], tex_coord);
                     }
+                    // the shading normal perturbed by normal map, rasterization pipeline has no light to use it yet
+                    vec3 n = normalize(in_normal);
+                    if (normal_map_index != MAX_UINT) {
+                        vec3 t = normalize(in_tangent.xyz - n * dot(n, in_tangent.xyz));
+                        vec3 b = cross(n, t) * in_tangent.w;
+                        // normal maps are sampled in unorm format, see TextureAssets::get_normal_map
+                        vec3 normal_map = texture(tex[normal_map_index], tex_coord).xyz;
+                        n = normalize(mat3(t, b, n) * (normal_map * 2.0 - 1.0));
+                    }
                     if (f_color.w == 0) {
                         discard;
                     }
@@ -410,3 +511,110 @@ pub mod model {
         }
     }
 }
+
+/// The shader to draw skybox in equirectangular projection behind the scene with a fullscreen triangle.
+pub mod skybox {
+    pub mod vs {
+        vulkano_shaders::shader! {
+            ty: "vertex",
+            src: r"
+                #version 460
+
+                layout(location = 0) out vec2 out_ndc;
+
+                void main() {
+                    vec2 positions[3] = vec2[](vec2(-1.0, -1.0), vec2(3.0, -1.0), vec2(-1.0, 3.0));
+                    out_ndc = positions[gl_VertexIndex];
+                    gl_Position = vec4(out_ndc, 0.0, 1.0); // reverse-Z: far plane is at depth 0
+                }
+            ",
+        }
+    }
+
+    pub mod fs {
+        vulkano_shaders::shader! {
+            ty: "fragment",
+            src: r"
+                #version 460
+
+                layout(push_constant) uniform PushConstants {
+                    mat4 inverse_projection_view;
+                    uvec2 eid;
+                } pcs;
+
+                layout(set = 0, binding = 0) uniform sampler2D tex;
+
+                layout(location = 0) in vec2 ndc;
+
+                layout(location = 0) out vec4 f_color;
+                layout(location = 1) out uvec2 f_eid;
+
+                const float PI = 3.14159265358979;
+
+                void main() {
+                    // unproject two points at different depths to get view direction, which works for both
+                    // perspective and orthographic cameras, reverse-Z: near plane is at depth 1
+                    vec4 near = pcs.inverse_projection_view * vec4(ndc, 1.0, 1.0);
+                    vec4 middle = pcs.inverse_projection_view * vec4(ndc, 0.5, 1.0);
+                    vec3 direction = normalize(middle.xyz / middle.w - near.xyz / near.w);
+                    vec2 uv = vec2(
+                        atan(direction.z, direction.x) / (2.0 * PI) + 0.5,
+                        acos(clamp(direction.y, -1.0, 1.0)) / PI
+                    );
+                    f_color = textureLod(tex, uv, 0.0);
+                    f_color = vec4(pow(f_color.xyz, vec3(1.0 / 2.2)), 1.0); // gamma correction
+                    f_eid = pcs.eid;
+                }
+            ",
+        }
+    }
+}
+
+/// Used by the second subpass of the multisampling render pass to resolve the multisampled images.
+pub mod resolve {
+    pub mod vs {
+        vulkano_shaders::shader! {
+            ty: "vertex",
+            src: r"
+                #version 460
+
+                void main() {
+                    vec2 positions[3] = vec2[](vec2(-1.0, -1.0), vec2(3.0, -1.0), vec2(-1.0, 3.0));
+                    gl_Position = vec4(positions[gl_VertexIndex], 0.0, 1.0);
+                }
+            ",
+        }
+    }
+
+    pub mod fs {
+        vulkano_shaders::shader! {
+            ty: "fragment",
+            src: r"
+                #version 460
+
+                layout(push_constant) uniform PushConstants {
+                    int samples;
+                } pcs;
+
+                layout(input_attachment_index = 0, set = 0, binding = 0) uniform subpassInputMS color_multisample;
+                layout(input_attachment_index = 1, set = 0, binding = 1) uniform usubpassInputMS eid_multisample;
+                layout(input_attachment_index = 2, set = 0, binding = 2) uniform subpassInputMS depth_multisample;
+
+                layout(location = 0) out vec4 f_color;
+                layout(location = 1) out uvec2 f_eid;
+                layout(location = 2) out float f_depth;
+
+                void main() {
+                    vec4 color = vec4(0.0);
+                    for (int i = 0; i < pcs.samples; i++) {
+                        color += subpassLoad(color_multisample, i);
+                    }
+                    f_color = color / float(pcs.samples);
+                    // eid and depth are not averaged, an average of them belongs to no object
+                    f_eid = subpassLoad(eid_multisample, 0).xy;
+                    f_depth = subpassLoad(depth_multisample, 0).x;
+                }
+            ",
+        }
+    }
+}
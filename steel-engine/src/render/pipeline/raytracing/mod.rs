@@ -8,7 +8,11 @@ use crate::{
     asset::AssetManager,
     camera::CameraInfo,
     render::{
-        canvas::Canvas, image::ImageAssets, mesh, model::ModelAssets, texture::TextureAssets,
+        canvas::{Canvas, ModelDraw},
+        image::ImageAssets,
+        mesh,
+        model::ModelAssets,
+        texture::{TextureAssets, TextureTiling},
         FrameRenderInfo, RenderContext,
     },
 };
@@ -98,6 +102,7 @@ pub struct RayTracingSettings {
     /// Max number of bounces the ray can make in the scene.
     pub max_bounces: u32,
     /// The miss color when ray direction is +Y, miss color is linear gradient between top and bottom.
+    /// Miss color is sampled from [crate::render::RenderManager::skybox] instead if it exists.
     pub miss_color_top: Vec3,
     /// The miss color when ray direction is -Y, miss color is linear gradient between top and bottom.
     pub miss_color_bottom: Vec3,
@@ -272,6 +277,7 @@ impl RayTracingPipeline {
         info: &FrameRenderInfo,
         camera: &CameraInfo,
         settings: &RayTracingSettings,
+        skybox: AssetId,
         canvas: &Canvas,
         model_assets: &mut ModelAssets,
         texture_assets: &mut TextureAssets,
@@ -282,15 +288,39 @@ impl RayTracingPipeline {
     ) -> (Box<dyn GpuFuture>, Arc<PrimaryAutoCommandBuffer>) {
         let mut instances = Vec::new();
         let mut texture_resources = IndexSet::new();
-        let mut texture_indices = Vec::new();
+        let mut texture_infos = Vec::new();
         let mut materials = Vec::new();
         let mut obj_descs = Vec::new();
         let mut eids = Vec::new();
 
+        // blend modes are ignored in ray tracing, see BlendMode
+        let rectangles = canvas
+            .rectangles
+            .iter()
+            .cloned()
+            .chain(
+                canvas
+                    .blended_rectangles
+                    .iter()
+                    .map(|(_, model, color, eid)| (*model, *color, Material::default(), *eid)),
+            )
+            .collect();
+        let textures =
+            canvas
+                .textures
+                .iter()
+                .cloned()
+                .chain(canvas.blended_textures.iter().map(
+                    |(_, texture, model, color, tiling, eid)| {
+                        (*texture, *model, *color, Material::default(), *tiling, *eid)
+                    },
+                ))
+                .collect();
+
         let rectangle_blas_future = draw_shapes(
-            &canvas.rectangles,
+            &rectangles,
             context,
-            &mut texture_indices,
+            &mut texture_infos,
             &mut materials,
             &mut eids,
             &mut obj_descs,
@@ -302,7 +332,7 @@ impl RayTracingPipeline {
         let cuboid_blas_future = draw_shapes(
             &canvas.cuboids,
             context,
-            &mut texture_indices,
+            &mut texture_infos,
             &mut materials,
             &mut eids,
             &mut obj_descs,
@@ -312,9 +342,9 @@ impl RayTracingPipeline {
         );
 
         let texture_blas_future = draw_textures(
-            &canvas.textures,
+            &textures,
             context,
-            &mut texture_indices,
+            &mut texture_infos,
             &mut texture_resources,
             &mut materials,
             &mut eids,
@@ -329,7 +359,7 @@ impl RayTracingPipeline {
         let model_blas_future = draw_models(
             &canvas.models,
             context,
-            &mut texture_indices,
+            &mut texture_infos,
             &mut texture_resources,
             &mut materials,
             &mut eids,
@@ -345,12 +375,17 @@ impl RayTracingPipeline {
         let sphere_blas_future = draw_spheres(
             &canvas.spheres,
             context,
-            &mut texture_indices,
+            &mut texture_infos,
             &mut materials,
             &mut eids,
             &mut instances,
         );
 
+        let skybox_index = texture_assets
+            .get_texture(skybox, image_assets, asset_manager, platform, context)
+            .map(|texture| texture_resources.insert_full(texture).0 as u32)
+            .unwrap_or(u32::MAX);
+
         let (tlas, tlas_future) = util::vulkano::create_top_level_acceleration_structure(
             context.memory_allocator.clone(),
             &context.command_buffer_allocator,
@@ -359,12 +394,19 @@ impl RayTracingPipeline {
         );
 
         let material_buffer = create_buffer(materials, &context.memory_allocator);
-        let texture_indices_buffer = create_buffer(texture_indices, &context.memory_allocator);
+        let texture_info_buffer = create_buffer(texture_infos, &context.memory_allocator);
+        let eid_buffer = create_buffer(
+            eids.into_iter()
+                .map(crate::render::canvas::eid_to_u32_array),
+            &context.memory_allocator,
+        );
         let mut descriptor_writes = vec![
             WriteDescriptorSet::acceleration_structure(0, tlas),
             WriteDescriptorSet::image_view(1, info.image.clone()),
             WriteDescriptorSet::buffer(2, material_buffer),
-            WriteDescriptorSet::buffer(4, texture_indices_buffer),
+            WriteDescriptorSet::buffer(4, texture_info_buffer),
+            WriteDescriptorSet::buffer(6, eid_buffer),
+            WriteDescriptorSet::image_view(7, eid_image),
         ];
         if !obj_descs.is_empty() {
             let obj_desc_buffer = create_buffer(obj_descs, &context.memory_allocator);
@@ -388,6 +430,7 @@ impl RayTracingPipeline {
         )
         .unwrap();
 
+        let (viewport_offset, viewport_extent) = camera.viewport_rect(info.window_size);
         let push_constants = shader::raygen::PushConstants {
             camera_type: camera.settings.to_i32() as u32,
             camera_position: camera.position.to_array(),
@@ -402,7 +445,9 @@ impl RayTracingPipeline {
             max_bounces: settings.max_bounces,
             miss_color_top: settings.miss_color_top.to_array(),
             miss_color_bottom: settings.miss_color_bottom.to_array(),
+            skybox_index,
             seed: self.rng.next_u32(),
+            viewport_offset: viewport_offset.to_array(),
         };
 
         let command_buffer = AutoCommandBufferBuilder::primary(
@@ -442,7 +487,7 @@ impl RayTracingPipeline {
             context.ash.device().cmd_push_constants(
                 command_buffer_handle,
                 self.pipeline_layout.handle(),
-                vk::ShaderStageFlags::RAYGEN_KHR,
+                vk::ShaderStageFlags::RAYGEN_KHR | vk::ShaderStageFlags::MISS_KHR,
                 0,
                 std::slice::from_raw_parts(
                     &push_constants as *const shader::raygen::PushConstants as *const u8,
@@ -455,8 +500,8 @@ impl RayTracingPipeline {
                 &self.sbt_region.miss,
                 &self.sbt_region.hit,
                 &self.sbt_region.call,
-                info.image.image().extent()[0],
-                info.image.image().extent()[1],
+                viewport_extent.x,
+                viewport_extent.y,
                 1,
             );
             context
@@ -482,7 +527,7 @@ impl RayTracingPipeline {
 fn draw_shapes(
     shapes: &Vec<(Affine3A, Vec4, Material, EntityId)>,
     context: &RenderContext,
-    texture_indices: &mut Vec<u32>,
+    texture_infos: &mut Vec<shader::closesthit::TextureInfo>,
     materials: &mut Vec<EnumMaterialPod>,
     eids: &mut Vec<EntityId>,
     obj_descs: &mut Vec<shader::closesthit::ObjDesc>,
@@ -497,7 +542,10 @@ fn draw_shapes(
     let mut transforms = Vec::new();
     for (model, color, material, eid) in shapes {
         transforms.push(*model);
-        texture_indices.push(u32::MAX);
+        texture_infos.push(shader::closesthit::TextureInfo::new(
+            u32::MAX,
+            TextureTiling::default(),
+        ));
         materials.push(EnumMaterialPod::from_material(*material, *color));
         eids.push(*eid);
     }
@@ -524,9 +572,9 @@ fn draw_shapes(
 }
 
 fn draw_textures(
-    textures: &Vec<(AssetId, Affine3A, Vec4, Material, EntityId)>,
+    textures: &Vec<(AssetId, Affine3A, Vec4, Material, TextureTiling, EntityId)>,
     context: &RenderContext,
-    texture_indices: &mut Vec<u32>,
+    texture_infos: &mut Vec<shader::closesthit::TextureInfo>,
     texture_resources: &mut IndexSet<(Arc<ImageView>, Arc<Sampler>)>,
     materials: &mut Vec<EnumMaterialPod>,
     eids: &mut Vec<EntityId>,
@@ -542,7 +590,7 @@ fn draw_textures(
     }
 
     let mut transforms = Vec::new();
-    for (texture, model, color, material, eid) in textures {
+    for (texture, model, color, material, tiling, eid) in textures {
         if let Some((image_view, sampler)) =
             texture_assets.get_texture(*texture, image_assets, asset_manager, platform, context)
         {
@@ -554,11 +602,10 @@ fn draw_textures(
                 ));
             transforms.push(model);
             texture_resources.insert((image_view.clone(), sampler.clone()));
-            texture_indices.push(
-                texture_resources
-                    .get_index_of(&(image_view, sampler))
-                    .unwrap() as _,
-            );
+            let texture_index = texture_resources
+                .get_index_of(&(image_view, sampler))
+                .unwrap() as _;
+            texture_infos.push(shader::closesthit::TextureInfo::new(texture_index, *tiling));
             materials.push(EnumMaterialPod::from_material(*material, *color));
             eids.push(*eid);
         }
@@ -595,9 +642,9 @@ fn draw_textures(
 }
 
 fn draw_models(
-    models: &Vec<(AssetId, AssetId, Affine3A, Vec4, Material, EntityId)>,
+    models: &Vec<ModelDraw>,
     context: &RenderContext,
-    texture_indices: &mut Vec<u32>,
+    texture_infos: &mut Vec<shader::closesthit::TextureInfo>,
     texture_resources: &mut IndexSet<(Arc<ImageView>, Arc<Sampler>)>,
     materials: &mut Vec<EnumMaterialPod>,
     eids: &mut Vec<EntityId>,
@@ -615,7 +662,7 @@ fn draw_models(
     }
 
     let mut model_to_index = HashMap::new();
-    for (model_asset, texture_asset, model_matrix, color, material, eid) in models {
+    for (model_asset, texture_asset, _, model_matrix, color, material, tiling, eid) in models {
         if let Some(model) = model_assets.get_model(*model_asset, asset_manager, platform) {
             let index = *model_to_index.entry(*model_asset).or_insert_with(|| {
                 let ((blas, blas_future), (vertex_address, index_address)) =
@@ -669,7 +716,7 @@ fn draw_models(
             } else {
                 u32::MAX
             };
-            texture_indices.push(texture_index);
+            texture_infos.push(shader::closesthit::TextureInfo::new(texture_index, *tiling));
         }
     }
 
@@ -679,7 +726,7 @@ fn draw_models(
 fn draw_spheres(
     spheres: &Vec<(Affine3A, Vec4, Material, EntityId)>,
     context: &RenderContext,
-    texture_indices: &mut Vec<u32>,
+    texture_infos: &mut Vec<shader::closesthit::TextureInfo>,
     materials: &mut Vec<EnumMaterialPod>,
     eids: &mut Vec<EntityId>,
     instances: &mut Vec<(Arc<AccelerationStructure>, u32, Vec<Affine3A>)>,
@@ -700,7 +747,10 @@ fn draw_spheres(
     let mut transforms = Vec::new();
     for (model, color, material, eid) in spheres {
         transforms.push(*model);
-        texture_indices.push(u32::MAX);
+        texture_infos.push(shader::closesthit::TextureInfo::new(
+            u32::MAX,
+            TextureTiling::default(),
+        ));
         materials.push(EnumMaterialPod::from_material(*material, *color));
         eids.push(*eid);
     }
@@ -721,6 +771,15 @@ fn draw_spheres(
     blas_future
 }
 
+impl shader::closesthit::TextureInfo {
+    fn new(index: u32, tiling: TextureTiling) -> Self {
+        shader::closesthit::TextureInfo {
+            tiling: tiling.to_vec4().to_array(),
+            index,
+        }
+    }
+}
+
 fn create_buffer<T: BufferContents>(
     iter: impl IntoIterator<Item = T, IntoIter: ExactSizeIterator>,
     memory_allocator: &Arc<StandardMemoryAllocator>,
@@ -295,6 +295,19 @@ pub mod raygen {
                 return r;
             }
 
+            // Function to generate a ray through the center of pixel without lens offset.
+            Ray get_center_ray(Camera cam, float s, float t) {
+                vec3 look_at = cam.lower_left_corner + s * cam.horizontal + t * cam.vertical;
+                Ray r;
+                if (cam.type == 0) { // orthographic
+                    r.origin = look_at - cam.focus_dist * cam.w;
+                } else { // perspective
+                    r.origin = cam.origin;
+                }
+                r.direction = normalize(look_at - r.origin);
+                return r;
+            }
+
             // =============== Shader ===============
 
             layout(set = 0, binding = 0) uniform accelerationStructureEXT top_level_as;
@@ -303,6 +316,12 @@ pub mod raygen {
                 EnumMaterial materials[];
             };
 
+            layout(set = 0, binding = 6) buffer Eids {
+                uvec2 eids[];
+            };
+
+            layout(set = 0, binding = 7, rg32ui) uniform uimage2D eid_image;
+
             layout(location = 0) rayPayloadEXT HitRecord hit;
 
             layout(push_constant) uniform PushConstants {
@@ -317,6 +336,8 @@ pub mod raygen {
                 vec3 miss_color_top; // miss color is linear gradient from top to bottom
                 uint seed;
                 vec3 miss_color_bottom;
+                uint skybox_index; // MAX_UINT means no skybox
+                uvec2 viewport_offset; // the pixel offset of the viewport of camera in out_image
             } pcs;
 
             void main() {
@@ -343,6 +364,30 @@ pub mod raygen {
                 float tmin = 0.001;
                 float tmax = 100000.0;
 
+                ivec2 pos = ivec2(launch_id.xy);
+                pos.y = int(launch_size.y) - 1 - pos.y;
+                pos += ivec2(pcs.viewport_offset);
+
+                // eid image stores the entity id hit by a single ray through pixel center, which is never accumulated
+                // or anti-aliased, so that picking gets crisp entity ids no matter how many samples are traced
+                const uint MAX_UINT = 4294967295u;
+                Ray eid_ray = get_center_ray(
+                    camera,
+                    (float(launch_id.x) + 0.5) / float(launch_size.x),
+                    (float(launch_id.y) + 0.5) / float(launch_size.y)
+                );
+                hit = default_HitRecord();
+                traceRayEXT(
+                    top_level_as,
+                    gl_RayFlagsOpaqueEXT,
+                    cull_mask,
+                    0, 0, 0,
+                    eid_ray.origin, tmin, eid_ray.direction, tmax,
+                    0
+                );
+                uvec2 eid = hit.is_miss ? uvec2(MAX_UINT, MAX_UINT) : eids[hit.material];
+                imageStore(eid_image, pos, uvec4(eid, 0, 0));
+
                 vec3 final_color = vec3(0.0);
 
                 for (uint i = 0; i < pcs.samples; i++) {
@@ -383,9 +428,6 @@ pub mod raygen {
                 final_color = final_color / float(pcs.samples);
                 final_color = pow(final_color, vec3(1.0 / 2.2)); // gamma correction
 
-                ivec2 pos = ivec2(launch_id.xy);
-                pos.y = int(launch_size.y) - 1 - pos.y;
-
                 imageStore(out_image, pos, vec4(final_color, 1.0));
             }
         ",
@@ -399,6 +441,9 @@ pub mod miss {
         src: r"
             #version 460
             #extension GL_EXT_ray_tracing : require
+            #extension GL_EXT_nonuniform_qualifier : require
+
+            layout(set = 0, binding = 5) uniform sampler2D[] tex;
 
             layout(push_constant) uniform PushConstants {
                 vec3 camera_position;
@@ -412,6 +457,8 @@ pub mod miss {
                 vec3 miss_color_top; // miss color is linear gradient between top and bottom
                 uint seed;
                 vec3 miss_color_bottom;
+                uint skybox_index; // MAX_UINT means no skybox
+                uvec2 viewport_offset; // the pixel offset of the viewport of camera in out_image
             } pcs;
 
             layout(location = 0) rayPayloadInEXT HitRecord {
@@ -424,9 +471,21 @@ pub mod miss {
             } hit;
 
             void main() {
+                const uint MAX_UINT = 4294967295u;
+                const float PI = 3.14159265358979;
                 vec3 world_ray_direction = normalize(gl_WorldRayDirectionEXT);
-                float t = 0.5 * (world_ray_direction.y + 1.0);
-                vec3 color = mix(pcs.miss_color_bottom, pcs.miss_color_top, t);
+                vec3 color;
+                if (pcs.skybox_index != MAX_UINT) {
+                    // sample skybox in equirectangular projection
+                    vec2 uv = vec2(
+                        atan(world_ray_direction.z, world_ray_direction.x) / (2.0 * PI) + 0.5,
+                        acos(clamp(world_ray_direction.y, -1.0, 1.0)) / PI
+                    );
+                    color = textureLod(tex[pcs.skybox_index], uv, 0.0).xyz;
+                } else {
+                    float t = 0.5 * (world_ray_direction.y + 1.0);
+                    color = mix(pcs.miss_color_bottom, pcs.miss_color_top, t);
+                }
 
                 hit.is_miss = true;
                 hit.position = color;
@@ -491,7 +550,11 @@ pub mod closesthit {
             layout(buffer_reference, scalar) buffer Vertices { Vertex v[]; }; // positions of an object
             layout(buffer_reference, scalar) buffer Indices { uvec3 i[]; }; // triangle indices
 
-            layout(set = 0, binding = 4, scalar) buffer TextureIndices { uint i[]; } tex_i;
+            struct TextureInfo {
+                vec4 tiling; // xy is scale and zw is offset of texture coordinates
+                uint index; // MAX_UINT means no texture
+            };
+            layout(set = 0, binding = 4, scalar) buffer TextureInfos { TextureInfo i[]; } tex_i;
             layout(set = 0, binding = 5) uniform sampler2D[] tex;
 
             void main() {
@@ -522,8 +585,10 @@ pub mod closesthit {
                 vec2 tex_coord = v0.tex_coord * barycentrics.x + v1.tex_coord * barycentrics.y + v2.tex_coord * barycentrics.z;
                 vec3 tex_color = vec3(1.0);
                 const uint MAX_UINT = 4294967295u;
-                if (tex_i.i[gl_InstanceID] != MAX_UINT) {
-                    tex_color = texture(tex[tex_i.i[gl_InstanceID]], tex_coord).xyz;
+                TextureInfo tex_info = tex_i.i[gl_InstanceID];
+                if (tex_info.index != MAX_UINT) {
+                    tex_coord = tex_coord * tex_info.tiling.xy + tex_info.tiling.zw;
+                    tex_color = texture(tex[tex_info.index], tex_coord).xyz;
                 }
 
                 // return hit record
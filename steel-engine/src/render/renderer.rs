@@ -1,5 +1,9 @@
 use crate::{
-    edit::Edit, hierarchy::Parent, render::canvas::Canvas, shape::Shape, transform::Transform,
+    edit::Edit,
+    hierarchy::Parent,
+    render::{canvas::Canvas, texture::TextureTiling},
+    shape::Shape,
+    transform::Transform,
 };
 use glam::{Affine3A, Vec3, Vec4};
 use parry3d::shape::ShapeType;
@@ -19,6 +23,8 @@ pub enum RenderObject {
     Model {
         model_asset: AssetId,
         texture_asset: AssetId,
+        /// The normal map in tangent space used to perturb the shading normal, only used by rasterization pipeline.
+        normal_map_asset: AssetId,
     },
 }
 
@@ -36,6 +42,7 @@ impl RenderObject {
             1 => Self::Model {
                 model_asset: AssetId::default(),
                 texture_asset: AssetId::default(),
+                normal_map_asset: AssetId::default(),
             },
             _ => Self::Shape(Shape::default()),
         }
@@ -79,9 +86,11 @@ impl Edit for Renderer {
             RenderObject::Model {
                 model_asset,
                 texture_asset,
+                normal_map_asset,
             } => {
                 data.add_value("model_asset", Value::Asset(*model_asset));
                 data.add_value("texture_asset", Value::Asset(*texture_asset));
+                data.add_value("normal_map_asset", Value::Asset(*normal_map_asset));
             }
         }
         data.insert_with_limit("color", Value::Vec4(self.color), Limit::Vec4Color)
@@ -97,6 +106,7 @@ impl Edit for Renderer {
                 RenderObject::Model {
                     model_asset,
                     texture_asset,
+                    normal_map_asset,
                 } => {
                     if let Some(Value::Asset(a)) = data.get("model_asset") {
                         *model_asset = *a;
@@ -104,6 +114,9 @@ impl Edit for Renderer {
                     if let Some(Value::Asset(a)) = data.get("texture_asset") {
                         *texture_asset = *a;
                     }
+                    if let Some(Value::Asset(a)) = data.get("normal_map_asset") {
+                        *normal_map_asset = *a;
+                    }
                 }
             }
         }
@@ -117,6 +130,7 @@ impl Edit for Renderer {
 pub fn renderer_to_canvas_system(
     renderers: View<Renderer>,
     materials: View<Material>,
+    tilings: View<TextureTiling>,
     transforms: View<Transform>,
     parents: View<Parent>,
     mut canvas: UniqueViewMut<Canvas>,
@@ -161,12 +175,15 @@ pub fn renderer_to_canvas_system(
             RenderObject::Model {
                 model_asset,
                 texture_asset,
-            } => canvas.model(
+                normal_map_asset,
+            } => canvas.model_tiled(
                 *model_asset,
                 *texture_asset,
+                *normal_map_asset,
                 model,
                 renderer.color,
                 material,
+                tilings.get(eid).cloned().unwrap_or_default(),
                 eid,
             ),
         }
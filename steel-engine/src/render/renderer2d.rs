@@ -1,9 +1,18 @@
 use crate::{
-    edit::Edit, hierarchy::Parent, render::canvas::Canvas, shape2d::Shape2D, transform::Transform,
+    edit::Edit,
+    hierarchy::Parent,
+    render::{
+        blend::BlendMode,
+        canvas::Canvas,
+        layer::{SortingLayer, SortingLayers, SORTED_DEPTH_RANGE},
+        texture::TextureTiling,
+    },
+    shape2d::Shape2D,
+    transform::Transform,
 };
 use glam::{Affine3A, Vec3, Vec4};
 use parry2d::shape::ShapeType;
-use shipyard::{Component, Get, IntoIter, IntoWithId, UniqueViewMut, View};
+use shipyard::{Component, Get, IntoIter, IntoWithId, UniqueView, UniqueViewMut, View};
 use std::collections::HashMap;
 use steel_common::{
     asset::AssetId,
@@ -41,6 +50,7 @@ impl RenderObject2D {
 }
 
 /// Renderer2D component is used to draw a 2D shape or 2D texture on an entity.
+/// Add a [BlendMode] component to the entity to draw with additive or multiply blending.
 #[derive(Component, Debug)]
 pub struct Renderer2D {
     pub object: RenderObject2D,
@@ -98,27 +108,63 @@ impl Edit for Renderer2D {
 }
 
 /// Add drawing data to the [Canvas] unique according to the [Renderer2D] components.
+/// The z values are offset according to the [SortingLayer] components, see [SortingLayers::z_offset],
+/// and coplanar objects are also spread apart if [SortingLayers::sorted_depth] is true.
 pub fn renderer2d_to_canvas_system(
     renderers2d: View<Renderer2D>,
-    materials: View<Material>,
+    (materials, tilings, blend_modes): (View<Material>, View<TextureTiling>, View<BlendMode>),
     transforms: View<Transform>,
     parents: View<Parent>,
+    sorting_layer: View<SortingLayer>,
+    sorting_layers: UniqueView<SortingLayers>,
     mut canvas: UniqueViewMut<Canvas>,
 ) {
     let mut model_cache = Some(HashMap::new());
     let mut scale_cache = Some(HashMap::new());
-    for (eid, (renderer2d, _)) in (&renderers2d, &transforms).iter().with_id() {
-        let scale =
-            Transform::entity_final_scale(eid, &parents, &transforms, &mut scale_cache).unwrap();
-        let model_without_scale = Transform::entity_final_model_without_scale(
-            eid,
-            &parents,
-            &transforms,
-            &mut model_cache,
-        )
-        .unwrap();
+    let mut objects = (&renderers2d, &transforms)
+        .iter()
+        .with_id()
+        .map(|(eid, (renderer2d, _))| {
+            let scale = Transform::entity_final_scale(eid, &parents, &transforms, &mut scale_cache)
+                .unwrap();
+            let model_without_scale = Transform::entity_final_model_without_scale(
+                eid,
+                &parents,
+                &transforms,
+                &mut model_cache,
+            )
+            .unwrap();
+            (eid, renderer2d, scale, model_without_scale)
+        })
+        .collect::<Vec<_>>();
+    if sorting_layers.sorted_depth {
+        objects.sort_by(|(eid1, _, _, model1), (eid2, _, _, model2)| {
+            let key1 = sorting_layers.sorting_key(sorting_layer.get(*eid1).ok());
+            let key2 = sorting_layers.sorting_key(sorting_layer.get(*eid2).ok());
+            key1.cmp(&key2)
+                .then(model1.translation.z.total_cmp(&model2.translation.z))
+                .then(eid1.index().cmp(&eid2.index()))
+        });
+        // spread the objects with the same sorting key evenly in front of their z, so that they never z-fight
+        for objects in objects.chunk_by_mut(|(eid1, _, _, model1), (eid2, _, _, model2)| {
+            sorting_layers.sorting_key(sorting_layer.get(*eid1).ok())
+                == sorting_layers.sorting_key(sorting_layer.get(*eid2).ok())
+                && model1.translation.z == model2.translation.z
+        }) {
+            let count = objects.len() as f32;
+            for (i, (_, _, _, model_without_scale)) in objects.iter_mut().enumerate() {
+                model_without_scale.translation.z += i as f32 / count * SORTED_DEPTH_RANGE;
+            }
+        }
+    }
+    for (eid, _, _, model_without_scale) in objects.iter_mut() {
+        model_without_scale.translation.z += sorting_layers.z_offset(sorting_layer.get(*eid).ok());
+    }
+
+    for (eid, renderer2d, scale, model_without_scale) in objects {
         let model = model_without_scale * Affine3A::from_scale(scale);
         let material = materials.get(eid).cloned().unwrap_or_default();
+        let blend_mode = blend_modes.get(eid).cloned().unwrap_or_default();
         match &renderer2d.object {
             RenderObject2D::Shape(shape) => match shape.shape_type() {
                 ShapeType::Ball => {
@@ -138,12 +184,21 @@ pub fn renderer2d_to_canvas_system(
                         scale.z,
                     );
                     let model = model_without_scale * Affine3A::from_scale(scale);
-                    canvas.rectangle(model, renderer2d.color, material, eid);
+                    if blend_mode == BlendMode::Alpha {
+                        canvas.rectangle(model, renderer2d.color, material, eid);
+                    } else {
+                        canvas.blended_rectangle(blend_mode, model, renderer2d.color, eid);
+                    }
                 }
                 _ => (),
             },
             RenderObject2D::Texture(asset) => {
-                canvas.texture(*asset, model, renderer2d.color, material, eid)
+                let tiling = tilings.get(eid).cloned().unwrap_or_default();
+                if blend_mode == BlendMode::Alpha {
+                    canvas.texture_tiled(*asset, model, renderer2d.color, material, tiling, eid)
+                } else {
+                    canvas.blended_texture(blend_mode, *asset, model, renderer2d.color, tiling, eid)
+                }
             }
         }
     }
@@ -0,0 +1,282 @@
+use super::{mesh::MeshData, pipeline::raytracing::material::Material};
+use crate::{edit::Edit, hierarchy::Parent, render::canvas::Canvas, transform::Transform};
+use glam::{Affine3A, UVec2, Vec2, Vec3, Vec4};
+use shipyard::{Component, Get, IntoIter, IntoWithId, UniqueViewMut, View};
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex, Weak},
+};
+use steel_common::data::{Data, Limit, Value};
+
+/// The procedural 3D primitive drawn by [Shape3D].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Primitive {
+    Box {
+        size: Vec3,
+    },
+    Cylinder {
+        radius: f32,
+        height: f32,
+        segments: u32,
+    },
+    Cone {
+        radius: f32,
+        height: f32,
+        segments: u32,
+    },
+    Plane {
+        size: Vec2,
+        subdivisions: UVec2,
+    },
+}
+
+impl Default for Primitive {
+    fn default() -> Self {
+        Self::Box { size: Vec3::ONE }
+    }
+}
+
+impl Primitive {
+    pub fn to_i32(&self) -> i32 {
+        match self {
+            Self::Box { .. } => 0,
+            Self::Cylinder { .. } => 1,
+            Self::Cone { .. } => 2,
+            Self::Plane { .. } => 3,
+        }
+    }
+
+    pub fn from_i32(i: i32) -> Self {
+        match i {
+            1 => Self::Cylinder {
+                radius: 0.5,
+                height: 1.0,
+                segments: 32,
+            },
+            2 => Self::Cone {
+                radius: 0.5,
+                height: 1.0,
+                segments: 32,
+            },
+            3 => Self::Plane {
+                size: Vec2::ONE,
+                subdivisions: UVec2::ONE,
+            },
+            _ => Self::default(),
+        }
+    }
+
+    pub fn enum_vector() -> Vec<(i32, String)> {
+        vec![
+            (0, "Box".into()),
+            (1, "Cylinder".into()),
+            (2, "Cone".into()),
+            (3, "Plane".into()),
+        ]
+    }
+
+    /// Get the mesh of this primitive which is shared by all shapes with the same primitive, so that
+    /// they are drawn in one draw call. The mesh is generated if no shape uses this primitive now.
+    pub fn shared_mesh(&self) -> Arc<MeshData> {
+        let Ok(mut meshes) = MESHES.lock() else {
+            return Arc::new(self.mesh());
+        };
+        meshes.retain(|(_, mesh)| mesh.strong_count() > 0);
+        if let Some(mesh) = meshes
+            .iter()
+            .find(|(primitive, _)| primitive == self)
+            .and_then(|(_, mesh)| mesh.upgrade())
+        {
+            return mesh;
+        }
+        let mesh = Arc::new(self.mesh());
+        meshes.push((*self, Arc::downgrade(&mesh)));
+        mesh
+    }
+
+    /// Generate the mesh of this primitive.
+    pub fn mesh(&self) -> MeshData {
+        match *self {
+            Self::Box { size } => MeshData::cuboid(size),
+            Self::Cylinder {
+                radius,
+                height,
+                segments,
+            } => MeshData::cylinder(radius, height, segments),
+            Self::Cone {
+                radius,
+                height,
+                segments,
+            } => MeshData::cone(radius, height, segments),
+            Self::Plane { size, subdivisions } => MeshData::plane(size, subdivisions),
+        }
+    }
+}
+
+/// The meshes which are used by [Shape3D] components, see [Primitive::shared_mesh].
+static MESHES: Mutex<Vec<(Primitive, Weak<MeshData>)>> = Mutex::new(Vec::new());
+
+/// Shape3D component is used to draw a procedural 3D primitive like box, cylinder, cone, or plane on an entity,
+/// which is useful for greyboxing levels without importing models. Shape3D is only drawn by the rasterization pipeline.
+#[derive(Component, Debug)]
+pub struct Shape3D {
+    primitive: Primitive,
+    /// The mesh of primitive, which is shared by shapes with the same primitive and is changed when primitive is changed.
+    mesh: Arc<MeshData>,
+    pub color: Vec4,
+}
+
+impl Default for Shape3D {
+    fn default() -> Self {
+        Self::new(Primitive::default(), Vec4::ONE /* white */)
+    }
+}
+
+impl Shape3D {
+    /// Create a Shape3D component.
+    pub fn new(primitive: Primitive, color: Vec4) -> Self {
+        Shape3D {
+            primitive,
+            mesh: primitive.shared_mesh(),
+            color,
+        }
+    }
+
+    /// Get the primitive of this shape.
+    pub fn primitive(&self) -> Primitive {
+        self.primitive
+    }
+
+    /// Set the primitive of this shape, the mesh is regenerated if primitive is changed.
+    pub fn set_primitive(&mut self, primitive: Primitive) {
+        if self.primitive != primitive {
+            self.primitive = primitive;
+            self.mesh = primitive.shared_mesh();
+        }
+    }
+
+    /// Get the mesh generated from the primitive of this shape.
+    pub fn mesh(&self) -> &Arc<MeshData> {
+        &self.mesh
+    }
+}
+
+impl Edit for Shape3D {
+    fn name() -> &'static str {
+        "Shape3D"
+    }
+
+    fn get_data(&self) -> Data {
+        let mut data = Data::new();
+        data.add_value_with_limit(
+            "primitive",
+            Value::Int32(self.primitive.to_i32()),
+            Limit::Int32Enum(Primitive::enum_vector()),
+        );
+        match self.primitive {
+            Primitive::Box { size } => data.add_value("size", Value::Vec3(size)),
+            Primitive::Cylinder {
+                radius,
+                height,
+                segments,
+            }
+            | Primitive::Cone {
+                radius,
+                height,
+                segments,
+            } => {
+                data.add_value("radius", Value::Float32(radius));
+                data.add_value("height", Value::Float32(height));
+                data.add_value_with_limit(
+                    "segments",
+                    Value::UInt32(segments),
+                    Limit::UInt32Range(3..=1024),
+                );
+            }
+            Primitive::Plane { size, subdivisions } => {
+                data.add_value("size", Value::Vec2(size));
+                data.add_value_with_limit(
+                    "subdivisions",
+                    Value::UVec2(subdivisions),
+                    Limit::UInt32Range(1..=128),
+                );
+            }
+        }
+        data.insert_with_limit("color", Value::Vec4(self.color), Limit::Vec4Color)
+    }
+
+    fn set_data(&mut self, data: &Data) {
+        if let Some(Value::Int32(primitive)) = data.get("primitive") {
+            let mut primitive = if self.primitive.to_i32() != *primitive {
+                Primitive::from_i32(*primitive)
+            } else {
+                self.primitive
+            };
+            match &mut primitive {
+                Primitive::Box { size } => {
+                    if let Some(Value::Vec3(v)) = data.get("size") {
+                        *size = *v;
+                    }
+                }
+                Primitive::Cylinder {
+                    radius,
+                    height,
+                    segments,
+                }
+                | Primitive::Cone {
+                    radius,
+                    height,
+                    segments,
+                } => {
+                    if let Some(Value::Float32(f)) = data.get("radius") {
+                        *radius = *f;
+                    }
+                    if let Some(Value::Float32(f)) = data.get("height") {
+                        *height = *f;
+                    }
+                    if let Some(Value::UInt32(u)) = data.get("segments") {
+                        *segments = *u;
+                    }
+                }
+                Primitive::Plane { size, subdivisions } => {
+                    if let Some(Value::Vec2(v)) = data.get("size") {
+                        *size = *v;
+                    }
+                    if let Some(Value::UVec2(v)) = data.get("subdivisions") {
+                        *subdivisions = *v;
+                    }
+                }
+            }
+            self.set_primitive(primitive);
+        }
+        if let Some(Value::Vec4(v)) = data.get("color") {
+            self.color = *v
+        }
+    }
+}
+
+/// Add drawing data to the [Canvas] unique according to the [Shape3D] components.
+pub fn shape3d_to_canvas_system(
+    shapes: View<Shape3D>,
+    materials: View<Material>,
+    transforms: View<Transform>,
+    parents: View<Parent>,
+    mut canvas: UniqueViewMut<Canvas>,
+) {
+    let mut model_cache = Some(HashMap::new());
+    let mut scale_cache = Some(HashMap::new());
+    for (eid, (shape, _)) in (&shapes, &transforms).iter().with_id() {
+        let scale =
+            Transform::entity_final_scale(eid, &parents, &transforms, &mut scale_cache).unwrap();
+        let model_without_scale = Transform::entity_final_model_without_scale(
+            eid,
+            &parents,
+            &transforms,
+            &mut model_cache,
+        )
+        .unwrap();
+        let model = model_without_scale * Affine3A::from_scale(scale);
+        let material = materials.get(eid).cloned().unwrap_or_default();
+        canvas.mesh(shape.mesh.clone(), model, shape.color, material, eid);
+    }
+}
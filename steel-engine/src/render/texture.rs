@@ -1,8 +1,10 @@
 use super::{image::ImageAssets, RenderContext};
-use crate::asset::AssetManager;
+use crate::{asset::AssetManager, edit::Edit};
+use glam::{Vec2, Vec4};
 use image::{DynamicImage, GenericImageView};
-use shipyard::Unique;
+use shipyard::{Component, Unique};
 use std::{collections::HashMap, error::Error, sync::Arc};
+use steel_common::data::{Data, Value};
 use steel_common::{asset::AssetId, platform::Platform};
 use vulkano::{
     buffer::{Buffer, BufferCreateInfo, BufferUsage},
@@ -13,16 +15,52 @@ use vulkano::{
     format::Format,
     image::{
         sampler::{Sampler, SamplerCreateInfo},
-        view::ImageView,
-        Image, ImageCreateInfo, ImageUsage,
+        view::{ImageView, ImageViewCreateInfo},
+        Image, ImageCreateFlags, ImageCreateInfo, ImageUsage,
     },
     memory::allocator::{AllocationCreateInfo, MemoryTypeFilter},
     sync::GpuFuture,
 };
 
+/// The TextureTiling component scales and offsets the texture coordinates of the texture or model drawn by
+/// [crate::render::renderer2d::Renderer2D] or [crate::render::renderer::Renderer] on the same entity,
+/// the texture coordinates become uv * scale + offset. Textures repeat outside [0, 1], so a scale of 4
+/// tiles a texture 4 times, which is useful for floors, walls, and backgrounds.
+#[derive(Component, Edit, Debug, Clone, Copy, PartialEq)]
+pub struct TextureTiling {
+    pub scale: Vec2,
+    pub offset: Vec2,
+}
+
+impl Default for TextureTiling {
+    fn default() -> Self {
+        TextureTiling {
+            scale: Vec2::ONE,
+            offset: Vec2::ZERO,
+        }
+    }
+}
+
+impl TextureTiling {
+    /// Create a TextureTiling component which tiles a texture count times without offset.
+    pub fn new(count: Vec2) -> Self {
+        TextureTiling {
+            scale: count,
+            offset: Vec2::ZERO,
+        }
+    }
+
+    /// Pack scale and offset into a Vec4 for shaders, xy is scale and zw is offset.
+    pub(crate) fn to_vec4(self) -> Vec4 {
+        Vec4::new(self.scale.x, self.scale.y, self.offset.x, self.offset.y)
+    }
+}
+
 struct TextureAsset {
     image: Arc<DynamicImage>,
     data: (Arc<ImageView>, Arc<Sampler>),
+    /// The view of the same image in unorm format, which is used by normal maps.
+    unorm_view: Arc<ImageView>,
 }
 
 #[derive(Unique, Default)]
@@ -40,24 +78,62 @@ impl TextureAssets {
         platform: &Platform,
         render_context: &RenderContext,
     ) -> Option<(Arc<ImageView>, Arc<Sampler>)> {
+        self.get_texture_asset(
+            asset_id,
+            image_assets,
+            asset_manager,
+            platform,
+            render_context,
+        )
+        .map(|texture_asset| texture_asset.data.clone())
+    }
+
+    /// Get the texture like [TextureAssets::get_texture], but the image view is in unorm format instead of srgb,
+    /// so that sampled values are the raw values in the image file, which is needed by normal maps.
+    pub fn get_normal_map(
+        &mut self,
+        asset_id: AssetId,
+        image_assets: &mut ImageAssets,
+        asset_manager: &mut AssetManager,
+        platform: &Platform,
+        render_context: &RenderContext,
+    ) -> Option<(Arc<ImageView>, Arc<Sampler>)> {
+        self.get_texture_asset(
+            asset_id,
+            image_assets,
+            asset_manager,
+            platform,
+            render_context,
+        )
+        .map(|texture_asset| {
+            (
+                texture_asset.unorm_view.clone(),
+                texture_asset.data.1.clone(),
+            )
+        })
+    }
+
+    fn get_texture_asset(
+        &mut self,
+        asset_id: AssetId,
+        image_assets: &mut ImageAssets,
+        asset_manager: &mut AssetManager,
+        platform: &Platform,
+        render_context: &RenderContext,
+    ) -> Option<&TextureAsset> {
         if let Some(image) = image_assets.get_image(asset_id, asset_manager, platform) {
-            if let Some(texture2d_asset) = self.textures.get(&asset_id) {
-                if Arc::ptr_eq(&image, &texture2d_asset.image) {
-                    // cache is still valid
-                    return Some(texture2d_asset.data.clone());
-                }
+            let cache_valid = self
+                .textures
+                .get(&asset_id)
+                .is_some_and(|texture_asset| Arc::ptr_eq(&image, &texture_asset.image));
+            if cache_valid {
+                return self.textures.get(&asset_id);
             }
             // cache is not valid, reload data
-            match Self::get_texture_from_image(&image, render_context) {
-                Ok(data) => {
-                    self.textures.insert(
-                        asset_id,
-                        TextureAsset {
-                            image: image.clone(),
-                            data: data.clone(),
-                        },
-                    );
-                    return Some(data);
+            match Self::get_texture_from_image(image, render_context) {
+                Ok(texture_asset) => {
+                    self.textures.insert(asset_id, texture_asset);
+                    return self.textures.get(&asset_id);
                 }
                 Err(e) => log::error!("Texture2DAssets::get_texture: error: {}", e),
             }
@@ -67,9 +143,9 @@ impl TextureAssets {
     }
 
     fn get_texture_from_image(
-        dynamic_image: &DynamicImage,
+        dynamic_image: Arc<DynamicImage>,
         render_context: &RenderContext,
-    ) -> Result<(Arc<ImageView>, Arc<Sampler>), Box<dyn Error>> {
+    ) -> Result<TextureAsset, Box<dyn Error>> {
         let image_staging_buffer = Buffer::new_slice(
             render_context.memory_allocator.clone(),
             BufferCreateInfo {
@@ -89,6 +165,8 @@ impl TextureAssets {
         let image = Image::new(
             render_context.memory_allocator.clone(),
             ImageCreateInfo {
+                // mutable format so that normal maps can view this image in unorm format
+                flags: ImageCreateFlags::MUTABLE_FORMAT,
                 usage: ImageUsage::TRANSFER_DST | ImageUsage::SAMPLED,
                 format: Format::R8G8B8A8_SRGB,
                 extent: [
@@ -104,6 +182,13 @@ impl TextureAssets {
             },
         )?;
         let image_view = ImageView::new_default(image.clone())?;
+        let unorm_view = ImageView::new(
+            image.clone(),
+            ImageViewCreateInfo {
+                format: Format::R8G8B8A8_UNORM,
+                ..ImageViewCreateInfo::from_image(&image)
+            },
+        )?;
         let sampler = Sampler::new(
             render_context.device.clone(),
             SamplerCreateInfo::simple_repeat_linear_no_mipmap(),
@@ -122,6 +207,10 @@ impl TextureAssets {
             .execute(render_context.graphics_queue.clone())?
             .then_signal_fence_and_flush()?
             .wait(None)?;
-        Ok((image_view, sampler))
+        Ok(TextureAsset {
+            image: dynamic_image,
+            data: (image_view, sampler),
+            unorm_view,
+        })
     }
 }
@@ -0,0 +1,186 @@
+use crate::{
+    camera::CameraInfo,
+    render::{canvas::Canvas, pipeline::raytracing::material::Material},
+    window::Window,
+};
+use glam::{Affine3A, Mat4, Vec2, Vec3, Vec4, Vec4Swizzles};
+use shipyard::{EntityId, Unique, UniqueView, UniqueViewMut};
+use steel_common::asset::AssetId;
+
+/// The ndc depth of elements anchored to screen, which is right behind the near plane of camera.
+const SCREEN_DEPTH: f32 = 0.001;
+/// The ndc depth that each element is moved toward camera more than the previous element, so that later elements
+/// are drawn in front of earlier elements at the same anchor, like the fill of a [WorldUi::bar] over its background.
+const ELEMENT_DEPTH_BIAS: f32 = 0.000001;
+
+/// Where a [WorldUi] element is positioned.
+#[derive(Clone, Copy, Debug)]
+pub enum UiAnchor {
+    /// A position in world space which is projected through the camera, like the head of a unit.
+    World(Vec3),
+    /// A position in pixels in the game window, (0, 0) is the top left corner.
+    Screen(Vec2),
+}
+
+enum UiElement {
+    Rect { color: Vec4 },
+    Image { asset: AssetId, color: Vec4 },
+}
+
+/// WorldUi unique is an immediate-mode UI layer which is drawn through the [Canvas], so that you can draw
+/// elements anchored to world positions like health bars above units without projecting them manually.
+/// Element offsets and sizes are in pixels, so elements keep their size on screen when the camera zooms.
+/// Elements are projected through the first camera and added to the canvas in [crate::app::Schedule::PostUpdate],
+/// so you should draw them every frame in [crate::app::Schedule::Update] or earlier. Later elements are drawn
+/// in front of earlier elements. Limitations:
+/// - Elements are projected with the size of game window and the first camera, so they are not placed correctly
+///   in other cameras, and they are placed in the scene window of steel-editor as in the game window.
+/// - Text is not supported because the canvas has no text primitive yet, you can still use egui for labels.
+/// # Example
+/// ```rust
+/// use glam::{Vec2, Vec4};
+/// use shipyard::{IntoIter, IntoWithId, UniqueViewMut, View};
+/// use steel::{render::world_ui::{UiAnchor, WorldUi}, transform::Transform};
+///
+/// fn health_bar_system(transforms: View<Transform>, mut world_ui: UniqueViewMut<WorldUi>) {
+///     for (eid, transform) in transforms.iter().with_id() {
+///         world_ui.bar(
+///             UiAnchor::World(transform.position),
+///             Vec2::new(0.0, -40.0),
+///             Vec2::new(50.0, 6.0),
+///             0.7,
+///             (Vec4::new(0.0, 1.0, 0.0, 1.0), Vec4::new(0.2, 0.2, 0.2, 1.0)),
+///             eid,
+///         );
+///     }
+/// }
+/// ```
+#[derive(Unique, Default)]
+pub struct WorldUi {
+    /// (anchor, offset, size, element, eid)
+    elements: Vec<(UiAnchor, Vec2, Vec2, UiElement, EntityId)>,
+}
+
+impl WorldUi {
+    /// Draw a rectangle of size in pixels, which is centered at anchor moved by offset in pixels.
+    /// The eid is the entity that owns this element, which is used like the eid of [Canvas] drawing data.
+    pub fn rect(&mut self, anchor: UiAnchor, offset: Vec2, size: Vec2, color: Vec4, eid: EntityId) {
+        self.elements
+            .push((anchor, offset, size, UiElement::Rect { color }, eid));
+    }
+
+    /// Draw a horizontal bar of size in pixels like [WorldUi::rect], which is filled from left by fraction
+    /// in [0, 1] with fill color, and the rest is drawn with background color. This is useful for health bars.
+    /// colors: (fill, background).
+    pub fn bar(
+        &mut self,
+        anchor: UiAnchor,
+        offset: Vec2,
+        size: Vec2,
+        fraction: f32,
+        (fill, background): (Vec4, Vec4),
+        eid: EntityId,
+    ) {
+        let fraction = fraction.clamp(0.0, 1.0);
+        self.rect(anchor, offset, size, background, eid);
+        let fill_size = Vec2::new(size.x * fraction, size.y);
+        let fill_offset = offset - Vec2::new((size.x - fill_size.x) / 2.0, 0.0);
+        self.rect(anchor, fill_offset, fill_size, fill, eid);
+    }
+
+    /// Draw a texture asset of size in pixels like [WorldUi::rect], which is multiplied by color.
+    pub fn image(
+        &mut self,
+        anchor: UiAnchor,
+        offset: Vec2,
+        size: Vec2,
+        asset: AssetId,
+        color: Vec4,
+        eid: EntityId,
+    ) {
+        self.elements
+            .push((anchor, offset, size, UiElement::Image { asset, color }, eid));
+    }
+
+    /// Clear all elements.
+    pub fn clear(&mut self) {
+        self.elements.clear();
+    }
+
+    /// Whether there is no element.
+    pub(crate) fn is_empty(&self) -> bool {
+        self.elements.is_empty()
+    }
+}
+
+/// Add drawing data of all [WorldUi] elements to the [Canvas], then clear WorldUi.
+pub fn world_ui_to_canvas_system(
+    mut world_ui: UniqueViewMut<WorldUi>,
+    camera: UniqueView<CameraInfo>,
+    window: UniqueView<Window>,
+    mut canvas: UniqueViewMut<Canvas>,
+) {
+    let window_size = window.size();
+    if world_ui.elements.is_empty() || window_size.x == 0 || window_size.y == 0 {
+        world_ui.clear();
+        return;
+    }
+    let (viewport_offset, viewport_extent) = camera.viewport_rect(window_size);
+    let projection_view = camera.projection_view(&viewport_extent);
+    let inverse_projection_view = projection_view.inverse();
+    // the ndc size of one pixel, y is down in ndc
+    let pixel = 2.0 / viewport_extent.as_vec2();
+
+    for (i, (anchor, offset, size, element, eid)) in world_ui.elements.drain(..).enumerate() {
+        let Some(mut ndc) =
+            anchor_to_ndc(anchor, projection_view, viewport_offset.as_vec2(), pixel)
+        else {
+            continue;
+        };
+        ndc.z = (ndc.z - i as f32 * ELEMENT_DEPTH_BIAS).max(0.0);
+        let center = ndc.truncate() + offset * pixel;
+        let half = size * pixel / 2.0;
+        let unproject = |p: Vec2| {
+            let p = inverse_projection_view * p.extend(ndc.z).extend(1.0);
+            p.xyz() / p.w
+        };
+        let left = unproject(center - Vec2::new(half.x, 0.0));
+        let right = unproject(center + Vec2::new(half.x, 0.0));
+        let top = unproject(center - Vec2::new(0.0, half.y));
+        let bottom = unproject(center + Vec2::new(0.0, half.y));
+        let x_axis = right - left;
+        let y_axis = top - bottom;
+        let model = Affine3A::from_cols(
+            x_axis.into(),
+            y_axis.into(),
+            x_axis.cross(y_axis).normalize_or_zero().into(),
+            unproject(center).into(),
+        );
+        match element {
+            UiElement::Rect { color } => {
+                canvas.rectangle(model, color, Material::default(), eid);
+            }
+            UiElement::Image { asset, color } => {
+                canvas.texture(asset, model, color, Material::default(), eid)
+            }
+        }
+    }
+}
+
+/// Convert anchor to ndc, returns None if anchor is behind the camera.
+fn anchor_to_ndc(
+    anchor: UiAnchor,
+    projection_view: Mat4,
+    viewport_offset: Vec2,
+    pixel: Vec2,
+) -> Option<Vec3> {
+    match anchor {
+        UiAnchor::World(position) => {
+            let clip = projection_view * position.extend(1.0);
+            (clip.w > 0.0).then(|| clip.xyz() / clip.w)
+        }
+        UiAnchor::Screen(position) => {
+            Some(((position - viewport_offset) * pixel - Vec2::ONE).extend(SCREEN_DEPTH))
+        }
+    }
+}
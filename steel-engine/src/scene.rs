@@ -3,19 +3,30 @@ use crate::{
     data::{ComponentRegistry, LoadScenePrefabsParam, PrefabAssets, UniqueRegistry, WorldDataExt},
 };
 use shipyard::{Unique, UniqueView, UniqueViewMut, World};
-use std::collections::HashMap;
+use std::{
+    collections::HashMap,
+    error::Error,
+    fs,
+    path::{Path, PathBuf},
+};
 use steel_common::{
     asset::AssetId,
-    data::{EntityIdWithPath, SceneData},
+    data::{EntityIdWithPath, SceneData, WorldData},
     platform::Platform,
 };
 
 /// The SceneManager unique. You can use SceneManager::current_scene to get the current scene
-/// and use SceneManager::switch_scene to change scene at the start of next frame.
+/// and use SceneManager::switch_scene to change scene at the start of next frame,
+/// or use SceneManager::reload_current_scene to load the current scene again.
+/// You can also use SceneManager::clear_scene to reset the world in place, for example to restart a level.
+/// For save games, use SceneManager::save_to_file and SceneManager::load_from_file.
 #[derive(Unique)]
 pub struct SceneManager {
     current_scene: Option<AssetId>,
     to_scene: Option<AssetId>,
+    clear_scene: bool,
+    save_to_file: Option<PathBuf>,
+    load_from_file: Option<PathBuf>,
 }
 
 impl SceneManager {
@@ -24,6 +35,9 @@ impl SceneManager {
         SceneManager {
             current_scene: None,
             to_scene: scene,
+            clear_scene: false,
+            save_to_file: None,
+            load_from_file: None,
         }
     }
 
@@ -37,12 +51,71 @@ impl SceneManager {
         self.to_scene = Some(scene);
     }
 
-    /// Load the scene which is set by SceneManager::switch_scene.
+    /// Reload the current scene from its scene asset at the start of next frame, which discards all runtime changes.
+    /// Does nothing if there is no current scene.
+    pub fn reload_current_scene(&mut self) {
+        if let Some(current_scene) = self.current_scene {
+            self.to_scene = Some(current_scene);
+        } else {
+            log::warn!("SceneManager::reload_current_scene: there is no current scene");
+        }
+    }
+
+    /// Destroy all entities and reset uniques to their registered defaults at the start of next frame.
+    /// Unlike SceneManager::switch_scene, this does not read any scene file, and the current scene is unchanged.
+    pub fn clear_scene(&mut self) {
+        self.clear_scene = true;
+    }
+
+    /// Save the current world with persistent uniques to a scene file at path at the start of next frame,
+    /// which can be loaded by SceneManager::load_from_file later, for example to implement "save game".
+    pub fn save_to_file(&mut self, path: impl Into<PathBuf>) {
+        self.save_to_file = Some(path.into());
+    }
+
+    /// Load the world from a scene file at path which is saved by SceneManager::save_to_file at the start of
+    /// next frame, for example to implement "continue". The current scene is unchanged.
+    pub fn load_from_file(&mut self, path: impl Into<PathBuf>) {
+        self.load_from_file = Some(path.into());
+    }
+
+    /// Save the world which is requested by SceneManager::save_to_file,
+    /// clear the scene which is requested by SceneManager::clear_scene,
+    /// load the world which is requested by SceneManager::load_from_file,
+    /// and load the scene which is set by SceneManager::switch_scene.
     pub fn maintain_system(
         world: &mut World,
         component_registry: &ComponentRegistry,
         unique_registry: &UniqueRegistry,
     ) {
+        let (save_to_file, clear_scene, load_from_file) =
+            world.run(|mut scene_manager: UniqueViewMut<SceneManager>| {
+                (
+                    scene_manager.save_to_file.take(),
+                    std::mem::take(&mut scene_manager.clear_scene),
+                    scene_manager.load_from_file.take(),
+                )
+            });
+        if let Some(path) = save_to_file {
+            if let Err(e) = Self::save_file(world, component_registry, unique_registry, &path) {
+                log::error!(
+                    "SceneManager::maintain_system: failed to save scene to {}, error={e}",
+                    path.display()
+                );
+            }
+        }
+        if clear_scene {
+            Self::clear(world, unique_registry);
+        }
+        if let Some(path) = load_from_file {
+            if let Err(e) = Self::load_file(world, component_registry, unique_registry, &path) {
+                log::error!(
+                    "SceneManager::maintain_system: failed to load scene from {}, error={e}",
+                    path.display()
+                );
+            }
+        }
+
         let scene_data_and_scene = world.run(
             |mut scene_manager: UniqueViewMut<SceneManager>,
              mut asset_manager: UniqueViewMut<AssetManager>,
@@ -61,16 +134,21 @@ impl SceneManager {
         if let Some((scene_data, scene)) = scene_data_and_scene {
             Self::load(world, &scene_data, component_registry, unique_registry);
             Self::set_current_scene(world, Some(scene));
+            world.run_workload("scene_loaded").unwrap();
         }
     }
 
     /// Clear world and load world from world_data, also be sure to call Self::set_current_scene if scene has changed.
+    /// [crate::app::Schedule::SceneUnloaded] is run before clearing world, and the caller should run
+    /// [crate::app::Schedule::SceneLoaded] after current scene is updated.
     pub(crate) fn load(
         world: &mut World,
         scene_data: &SceneData,
         component_registry: &ComponentRegistry,
         unique_registry: &UniqueRegistry,
     ) {
+        world.run_workload("scene_unloaded").unwrap();
+
         // clear all entities in ecs world
         world.clear();
 
@@ -91,6 +169,12 @@ impl SceneManager {
                 },
             )
         };
+        if let Some(cycle) = scene_data
+            .entities
+            .find_nested_prefab_cycle(None, get_prefab_data_fn)
+        {
+            log::error!("SceneManager::load: prefab nests itself: {cycle:?}, the recursion is stopped at the nested self");
+        }
         let (world_data, entity_map) = scene_data.to_world_data(get_prefab_data_fn);
 
         // add world_data into ecs world
@@ -121,6 +205,101 @@ impl SceneManager {
         world.remove_unique::<LoadScenePrefabsParam>().unwrap();
     }
 
+    /// Save all entities and persistent uniques in world to [SceneData].
+    pub(crate) fn save(
+        world: &World,
+        component_registry: &ComponentRegistry,
+        unique_registry: &UniqueRegistry,
+    ) -> SceneData {
+        let mut world_data = WorldData::default();
+        for component_fn in component_registry.values() {
+            component_fn.save_to_data(&mut world_data, world);
+        }
+        for unique_fn in unique_registry.values() {
+            if unique_fn.persistent {
+                (unique_fn.save_to_data)(&mut world_data, world);
+            }
+        }
+        let get_prefab_data_fn = |prefab_asset: AssetId| {
+            world.run(
+                |mut prefab_assets: UniqueViewMut<PrefabAssets>,
+                 mut asset_manager: UniqueViewMut<AssetManager>,
+                 platform: UniqueView<Platform>| {
+                    prefab_assets.get_prefab_data(
+                        prefab_asset,
+                        &mut asset_manager,
+                        platform.as_ref(),
+                    )
+                },
+            )
+        };
+        let mut scene_data = SceneData::new(&world_data, get_prefab_data_fn);
+        scene_data.cut();
+        scene_data
+    }
+
+    /// Save world to a scene file at path, see [SceneManager::save].
+    pub(crate) fn save_file(
+        world: &World,
+        component_registry: &ComponentRegistry,
+        unique_registry: &UniqueRegistry,
+        path: &Path,
+    ) -> Result<(), Box<dyn Error>> {
+        let scene_data = Self::save(world, component_registry, unique_registry);
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, serde_json::to_vec(&scene_data)?)?;
+        log::info!("SceneManager::save_file: path={}", path.display());
+        Ok(())
+    }
+
+    /// Clear world and load world from a scene file at path, then run [crate::app::Schedule::SceneLoaded].
+    /// The current scene is unchanged.
+    pub(crate) fn load_file(
+        world: &mut World,
+        component_registry: &ComponentRegistry,
+        unique_registry: &UniqueRegistry,
+        path: &Path,
+    ) -> Result<(), Box<dyn Error>> {
+        let scene_data = serde_json::from_slice::<SceneData>(&fs::read(path)?)?;
+        Self::load(world, &scene_data, component_registry, unique_registry);
+        world.run_workload("scene_loaded").unwrap();
+        log::info!("SceneManager::load_file: path={}", path.display());
+        Ok(())
+    }
+
+    /// Destroy all entities and reset uniques to their default data in unique_registry,
+    /// or to their Default values if they are transient, see [crate::app::SteelApp::mark_unique_transient].
+    /// [crate::app::Schedule::SceneUnloaded] and [crate::app::Schedule::SceneLoaded] are run around clearing.
+    pub(crate) fn clear(world: &mut World, unique_registry: &UniqueRegistry) {
+        world.run_workload("scene_unloaded").unwrap();
+
+        // clear all entities in ecs world
+        world.clear();
+
+        // clear hierachy track data since all entities are destroyed
+        world.run(crate::hierarchy::clear_track_data_system);
+
+        // reset uniques which have default data, transient uniques are reset to their Default values instead
+        let mut world_data = WorldData::default();
+        for (name, unique_fn) in unique_registry.iter() {
+            if let Some(default_data) = &unique_fn.default_data {
+                world_data
+                    .uniques
+                    .insert(name.to_string(), default_data.clone());
+            }
+        }
+        for unique_fn in unique_registry.values() {
+            match unique_fn.reset {
+                Some(reset) => reset(world),
+                None => (unique_fn.load_from_scene_data)(world, &world_data),
+            }
+        }
+
+        world.run_workload("scene_loaded").unwrap();
+    }
+
     /// Update scene_manager.current_scene to the scene.
     pub(crate) fn set_current_scene(world: &mut World, scene: Option<AssetId>) {
         world.run(
@@ -0,0 +1,174 @@
+use crate::{
+    app::{Plugin, Schedule, SteelApp},
+    hierarchy::Parent,
+    transform::Transform,
+};
+use glam::{IVec3, Vec3};
+use shipyard::{EntityId, IntoIter, IntoWithId, Unique, UniqueViewMut, View};
+use std::collections::HashMap;
+
+/// SpatialIndex unique is a uniform grid of the final positions of all entities with [Transform] component,
+/// which is rebuilt every frame by [spatial_index_update_system]. You can use it for simple proximity logic
+/// like finding entities in radius or nearest entities without adding physics colliders.
+#[derive(Unique)]
+pub struct SpatialIndex {
+    /// The size of each grid cell, which should be close to the common query radius. The new value
+    /// takes effect when the index is rebuilt in next frame.
+    pub cell_size: f32,
+    cells: HashMap<IVec3, Vec<(EntityId, Vec3)>>,
+    /// The min and max cell coordinates which contain entities.
+    bounds: (IVec3, IVec3),
+}
+
+impl Default for SpatialIndex {
+    fn default() -> Self {
+        SpatialIndex {
+            cell_size: 1.0,
+            cells: HashMap::new(),
+            bounds: (IVec3::ZERO, IVec3::ZERO),
+        }
+    }
+}
+
+impl SpatialIndex {
+    /// Get all entities whose positions are within radius r of center.
+    pub fn query_radius(&self, center: Vec3, r: f32) -> impl Iterator<Item = EntityId> + '_ {
+        let min = self.cell(center - r).max(self.bounds.0);
+        let max = self.cell(center + r).min(self.bounds.1);
+        (min.x..=max.x)
+            .flat_map(move |x| (min.y..=max.y).map(move |y| (x, y)))
+            .flat_map(move |(x, y)| (min.z..=max.z).map(move |z| IVec3::new(x, y, z)))
+            .filter_map(|cell| self.cells.get(&cell))
+            .flatten()
+            .filter(move |(_, position)| position.distance_squared(center) <= r * r)
+            .map(|(eid, _)| *eid)
+    }
+
+    /// Get at most n entities which are nearest to point, sorted from near to far.
+    pub fn nearest(&self, point: Vec3, n: usize) -> Vec<EntityId> {
+        if n == 0 || self.cells.is_empty() {
+            return Vec::new();
+        }
+        // cell coordinates are computed in i64 so that offsets from a far point do not overflow
+        let center = self.cell(point).to_array().map(i64::from);
+        let min = self.bounds.0.to_array().map(i64::from);
+        let max = self.bounds.1.to_array().map(i64::from);
+        let max_shell = (0..3)
+            .map(|i| (center[i] - min[i]).max(max[i] - center[i]))
+            .max()
+            .unwrap()
+            .max(0);
+        let mut candidates = Vec::new();
+        let add_cell = |candidates: &mut Vec<(f32, EntityId)>, cell: [i64; 3]| {
+            let cell = IVec3::new(cell[0] as i32, cell[1] as i32, cell[2] as i32);
+            if let Some(entities) = self.cells.get(&cell) {
+                candidates.extend(
+                    entities
+                        .iter()
+                        .map(|(eid, position)| (position.distance_squared(point), *eid)),
+                );
+            }
+        };
+        // search cells in growing shells around the cell of point, which are clipped by the bounds of
+        // cells, and stop when n candidates are closer than any cell which is not searched yet
+        for k in 0..=max_shell {
+            let shell_cells = if k == 0 {
+                1
+            } else {
+                k.saturating_mul(k).saturating_mul(24).saturating_add(2)
+            };
+            if shell_cells > self.cells.len() as i64 {
+                // the shell is larger than all cells, so search the remaining cells directly
+                for cell in self.cells.keys() {
+                    let cell = cell.to_array().map(i64::from);
+                    if (0..3).any(|i| (cell[i] - center[i]).abs() >= k) {
+                        add_cell(&mut candidates, cell);
+                    }
+                }
+                break;
+            }
+            let range = |i: usize| (center[i] - k).max(min[i])..=(center[i] + k).min(max[i]);
+            for x in range(0) {
+                for y in range(1) {
+                    if (x - center[0]).abs() == k || (y - center[1]).abs() == k {
+                        for z in range(2) {
+                            add_cell(&mut candidates, [x, y, z]);
+                        }
+                    } else {
+                        for z in [center[2] - k, center[2] + k] {
+                            if range(2).contains(&z) {
+                                add_cell(&mut candidates, [x, y, z]);
+                            }
+                        }
+                    }
+                }
+            }
+            if candidates.len() >= n {
+                candidates.select_nth_unstable_by(n - 1, |a, b| a.0.total_cmp(&b.0));
+                let searched_distance = self.searched_distance(point, center, k);
+                if candidates[n - 1].0 <= searched_distance * searched_distance {
+                    break;
+                }
+            }
+        }
+        candidates.sort_unstable_by(|a, b| a.0.total_cmp(&b.0));
+        candidates.into_iter().take(n).map(|(_, eid)| eid).collect()
+    }
+
+    /// Get the min distance from point to the cells outside of the cube of cells within k cells of center.
+    fn searched_distance(&self, point: Vec3, center: [i64; 3], k: i64) -> f32 {
+        (0..3)
+            .map(|i| {
+                let low = (center[i] - k) as f32 * self.cell_size;
+                let high = (center[i] + k + 1) as f32 * self.cell_size;
+                (point[i] - low).min(high - point[i])
+            })
+            .fold(f32::MAX, f32::min)
+            .max(0.0)
+    }
+
+    /// Get the coordinate of the cell which contains position.
+    fn cell(&self, position: Vec3) -> IVec3 {
+        (position / self.cell_size).floor().as_ivec3()
+    }
+
+    fn rebuild(&mut self, entities: impl Iterator<Item = (EntityId, Vec3)>) {
+        self.cells.clear();
+        self.bounds = (IVec3::MAX, IVec3::MIN);
+        for (eid, position) in entities {
+            let cell = self.cell(position);
+            self.bounds = (self.bounds.0.min(cell), self.bounds.1.max(cell));
+            self.cells.entry(cell).or_default().push((eid, position));
+        }
+        if self.cells.is_empty() {
+            self.bounds = (IVec3::ZERO, IVec3::ZERO);
+        }
+    }
+}
+
+/// Rebuild [SpatialIndex] from the final positions of all entities with [Transform] component.
+pub fn spatial_index_update_system(
+    transforms: View<Transform>,
+    parents: View<Parent>,
+    mut spatial_index: UniqueViewMut<SpatialIndex>,
+) {
+    let mut model_cache = Some(HashMap::new());
+    spatial_index.rebuild(transforms.iter().with_id().filter_map(|(eid, _)| {
+        Transform::entity_final_model_without_scale(eid, &parents, &transforms, &mut model_cache)
+            .map(|model| (eid, Vec3::from(model.translation)))
+    }));
+}
+
+/// The spatial index plugin. This plugin contains:
+/// - [SpatialIndex]
+/// - [spatial_index_update_system]
+pub struct SpatialIndexPlugin;
+
+impl Plugin for SpatialIndexPlugin {
+    fn apply(self, app: SteelApp) -> SteelApp {
+        app.add_unique(SpatialIndex::default()).add_system(
+            Schedule::PreUpdate,
+            crate::spatial::spatial_index_update_system,
+        )
+    }
+}
@@ -0,0 +1,74 @@
+use crate::edit::Edit;
+use shipyard::{Component, EntityId, IntoIter, IntoWithId, Unique, UniqueViewMut, View};
+use std::collections::HashMap;
+use steel_common::data::{Data, Value};
+
+/// The Tags component contains tag strings of an entity, like "Enemy" or "Pickup",
+/// which can be used to group entities across component types. Use [TagIndex] to find entities by tag.
+#[derive(Component, Edit, Default, Debug)]
+pub struct Tags(pub Vec<String>);
+
+impl Tags {
+    /// Create a Tags component.
+    pub fn new(tags: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        let mut t = Tags::default();
+        for tag in tags {
+            t.add(tag);
+        }
+        t
+    }
+
+    /// Check if tag is in this Tags.
+    pub fn has(&self, tag: impl AsRef<str>) -> bool {
+        self.0.iter().any(|t| t == tag.as_ref())
+    }
+
+    /// Add tag if it is not in this Tags yet.
+    pub fn add(&mut self, tag: impl Into<String>) {
+        let tag = tag.into();
+        if !self.has(&tag) {
+            self.0.push(tag);
+        }
+    }
+
+    /// Remove tag from this Tags.
+    pub fn remove(&mut self, tag: impl AsRef<str>) {
+        self.0.retain(|t| t != tag.as_ref());
+    }
+}
+
+/// TagIndex unique maps each tag to the entities which have it in their [Tags] component,
+/// which is rebuilt every frame by [tag_index_update_system]. Changes of [Tags] components
+/// are visible in TagIndex from next frame.
+#[derive(Unique, Default)]
+pub struct TagIndex {
+    entities: HashMap<String, Vec<EntityId>>,
+}
+
+impl TagIndex {
+    /// Get all entities with tag.
+    pub fn entities_with_tag(&self, tag: impl AsRef<str>) -> &[EntityId] {
+        self.entities
+            .get(tag.as_ref())
+            .map(|entities| entities.as_slice())
+            .unwrap_or_default()
+    }
+
+    /// Get all tags which are used by at least one entity.
+    pub fn tags(&self) -> impl Iterator<Item = &str> {
+        self.entities.keys().map(|tag| tag.as_str())
+    }
+}
+
+/// Rebuild [TagIndex] from all [Tags] components.
+pub fn tag_index_update_system(tags: View<Tags>, mut tag_index: UniqueViewMut<TagIndex>) {
+    tag_index.entities.clear();
+    for (eid, tags) in tags.iter().with_id() {
+        for tag in tags.0.iter() {
+            let entities = tag_index.entities.entry(tag.clone()).or_default();
+            if !entities.contains(&eid) {
+                entities.push(eid);
+            }
+        }
+    }
+}
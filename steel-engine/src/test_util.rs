@@ -0,0 +1,139 @@
+//! Testing utilities for plugin authors, which are enabled by the "test-util" feature.
+
+use crate::{data::Limit, edit::Edit};
+
+/// Assert that the [crate::data::Data] of e survives a round-trip through serialization,
+/// [Edit::from_data], and [Edit::set_data]. Read-only values are skipped when checking
+/// [Edit::set_data] because they should never be modified by it.
+/// # Example
+/// ```rust
+/// use steel::{
+///     data::{Data, Value},
+///     edit::Edit,
+///     test_util::assert_data_round_trip,
+/// };
+/// use shipyard::Component;
+///
+/// #[derive(Component, Edit, Default)]
+/// pub struct TestComponent {
+///     pub int: i32,
+///     pub string: String,
+/// }
+///
+/// assert_data_round_trip(&TestComponent { int: 1, string: "text".into() });
+/// ```
+pub fn assert_data_round_trip<E: Edit + Default>(e: &E) {
+    let data = e.get_data();
+
+    let json = serde_json::to_string(&data)
+        .unwrap_or_else(|err| panic!("{}: failed to serialize data {data:?}: {err}", E::name()));
+    let deserialized: crate::data::Data = serde_json::from_str(&json)
+        .unwrap_or_else(|err| panic!("{}: failed to deserialize data {json}: {err}", E::name()));
+    assert_eq!(
+        deserialized.values,
+        data.values,
+        "{}: data changed after serialization round-trip",
+        E::name()
+    );
+
+    let loaded = E::from_data(&data);
+    assert_eq!(
+        loaded.get_data().values,
+        data.values,
+        "{}: data changed after from_data round-trip",
+        E::name()
+    );
+
+    let mut edited = E::default();
+    edited.set_data(&data);
+    let edited_data = edited.get_data();
+    for (name, value) in &data.values {
+        if matches!(data.limits.get(name), Some(Limit::ReadOnly)) {
+            continue;
+        }
+        assert_eq!(
+            edited_data.get(name),
+            Some(value),
+            "{}: value {name} changed after set_data round-trip",
+            E::name()
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::assert_data_round_trip;
+    use crate::{
+        animation::Animator,
+        behavior::Behavior,
+        camera::{Camera, CameraConfiner, CameraFollow},
+        data::Prefab,
+        hierarchy::{Children, Parent},
+        interpolation::InterpolateTransform,
+        name::Name,
+        physics2d::{Collider2D, RigidBody2D},
+        pool::Pooled,
+        render::{
+            blend::BlendMode,
+            layer::{HideInGame, RenderLayers, SortingLayer},
+            pipeline::raytracing::material::Material,
+            renderer::Renderer,
+            renderer2d::Renderer2D,
+            shape3d::{Primitive, Shape3D},
+            texture::TextureTiling,
+        },
+        tag::Tags,
+        transform::Transform,
+    };
+    use glam::{Quat, Vec2, Vec3, Vec4};
+
+    #[test]
+    fn builtin_components_data_round_trip() {
+        assert_data_round_trip(&Name::default());
+        assert_data_round_trip(&Tags::new(["Enemy", "Pickup"]));
+        assert_data_round_trip(&Prefab::default());
+        assert_data_round_trip(&Parent::default());
+        assert_data_round_trip(&Children::default());
+        assert_data_round_trip(&Transform::default());
+        assert_data_round_trip(&Transform {
+            position: Vec3::new(1.0, -2.0, 3.0),
+            rotation: Quat::from_rotation_z(-1.0),
+            scale: Vec3::new(2.0, 0.5, 1.0),
+        });
+        assert_data_round_trip(&Camera::default());
+        assert_data_round_trip(&Camera {
+            viewport: Vec4::new(0.75, 0.0, 0.25, 0.25),
+            order: 1,
+            ..Default::default()
+        });
+        assert_data_round_trip(&CameraFollow::default());
+        assert_data_round_trip(&CameraConfiner::default());
+        assert_data_round_trip(&Animator::default());
+        assert_data_round_trip(&Behavior::default());
+        assert_data_round_trip(&Behavior::new("Rise"));
+        assert_data_round_trip(&Renderer::default());
+        assert_data_round_trip(&Renderer2D::default());
+        assert_data_round_trip(&Shape3D::default());
+        assert_data_round_trip(&Shape3D::new(
+            Primitive::from_i32(1),
+            Vec4::new(1.0, 0.0, 0.0, 1.0),
+        ));
+        assert_data_round_trip(&Material::default());
+        assert_data_round_trip(&TextureTiling::default());
+        assert_data_round_trip(&TextureTiling::new(Vec2::new(4.0, 2.0)));
+        assert_data_round_trip(&BlendMode::default());
+        assert_data_round_trip(&BlendMode::Additive);
+        assert_data_round_trip(&RenderLayers::default());
+        assert_data_round_trip(&HideInGame);
+        assert_data_round_trip(&InterpolateTransform);
+        assert_data_round_trip(&SortingLayer::default());
+        // The layer names are not registered by SortingLayers in this test, the layer must be kept by name.
+        assert_data_round_trip(&SortingLayer::new("Foreground", -3));
+        assert_data_round_trip(&RigidBody2D::default());
+        assert_data_round_trip(&Collider2D::default());
+        assert_data_round_trip(&Pooled::default());
+        assert_data_round_trip(&Pooled {
+            kind: "Bullet".into(),
+        });
+    }
+}
@@ -6,8 +6,26 @@ use std::time::Instant;
 /// and use Time::delta() to get the time spent in last frame.
 #[derive(Unique)]
 pub struct Time {
+    /// The speed of game time, [Time::delta] and [Time::elapsed] are multiplied by this.
+    /// 1.0 is normal speed, 0.0 pauses game time.
+    pub scale: f32,
+    /// The number of times per second the [crate::app::Schedule::Update] schedule runs, independently of
+    /// the render frame rate. If greater than 0, Update runs zero or more times per frame at a fixed cadence,
+    /// and [Time::delta] is the fixed step during Update. 0.0 runs Update once every frame.
+    /// Note that [crate::input::Input] is still updated once per frame, so with a fixed update rate, edge states
+    /// like key pressed or released are missed in frames without Update and are seen repeatedly in frames with
+    /// several Updates. Read them in [crate::app::Schedule::PreUpdate] and keep them until an Update handles them.
+    /// For the same reason, drawing must be done in [crate::app::Schedule::PostUpdate] which runs every frame.
+    pub update_rate: f32,
     now: f32,
     delta: f32,
+    unscaled_delta: f32,
+    elapsed: f64,
+    frame_count: u64,
+    frame_delta: f32,
+    frame_unscaled_delta: f32,
+    update_accumulator: FixedStepAccumulator,
+    update_steps: u32,
     start_instant: Instant,
     last_instant: Instant,
 }
@@ -17,8 +35,17 @@ impl Time {
     pub fn new() -> Time {
         let now_instant = Instant::now();
         Time {
+            scale: 1.0,
+            update_rate: 0.0,
             now: 0.0,
             delta: 0.0,
+            unscaled_delta: 0.0,
+            elapsed: 0.0,
+            frame_count: 0,
+            frame_delta: 0.0,
+            frame_unscaled_delta: 0.0,
+            update_accumulator: FixedStepAccumulator::default(),
+            update_steps: 0,
             start_instant: now_instant,
             last_instant: now_instant,
         }
@@ -29,25 +56,133 @@ impl Time {
         self.now
     }
 
-    /// Get the number of seconds spent in last frame.
+    /// Get the number of seconds spent in last frame, which is multiplied by [Time::scale].
     pub fn delta(&self) -> f32 {
         self.delta
     }
 
+    /// Get the number of seconds spent in last frame, which is not affected by [Time::scale].
+    pub fn unscaled_delta(&self) -> f32 {
+        self.unscaled_delta
+    }
+
+    /// Get the sum of [Time::delta] of all frames since game start. This is f64 so that it keeps
+    /// precision when game runs for a long time, which is useful for shader time and oscillations.
+    pub fn elapsed(&self) -> f64 {
+        self.elapsed
+    }
+
+    /// Get the sum of [Time::unscaled_delta] of all frames since game start.
+    pub fn unscaled_elapsed(&self) -> f64 {
+        (self.last_instant - self.start_instant).as_secs_f64()
+    }
+
+    /// Get the number of frames since game start, which is 1 in the first frame.
+    pub fn frame_count(&self) -> u64 {
+        self.frame_count
+    }
+
+    /// Get the fraction in [0, 1] of the next fixed update step that has passed since the last
+    /// [crate::app::Schedule::Update], which can be used to interpolate between the last two updates
+    /// when rendering. Always 1.0 if [Time::update_rate] is not greater than 0.
+    pub fn update_alpha(&self) -> f32 {
+        if self.update_rate > 0.0 {
+            (self.update_accumulator.remainder() * self.update_rate).min(1.0)
+        } else {
+            1.0
+        }
+    }
+
+    /// Start the next [crate::app::Schedule::Update] of this frame, returns false if there is no more update
+    /// to run in this frame. During fixed update steps [Time::delta] is set to the fixed step, and it is
+    /// restored to the frame delta when no update is left.
+    pub(crate) fn next_update_step(&mut self) -> bool {
+        if self.update_steps == 0 {
+            self.delta = self.frame_delta;
+            self.unscaled_delta = self.frame_unscaled_delta;
+            return false;
+        }
+        self.update_steps -= 1;
+        if self.update_rate > 0.0 {
+            self.unscaled_delta = 1.0 / self.update_rate;
+            self.delta = self.unscaled_delta * self.scale;
+        }
+        true
+    }
+
     /// Reset time so that now is the game start time.
     pub fn reset(&mut self) {
         let now_instant = Instant::now();
         self.now = 0.0;
         self.delta = 0.0;
+        self.unscaled_delta = 0.0;
+        self.elapsed = 0.0;
+        self.frame_count = 0;
+        self.frame_delta = 0.0;
+        self.frame_unscaled_delta = 0.0;
+        self.update_accumulator.reset();
+        self.update_steps = 0;
         self.start_instant = now_instant;
         self.last_instant = now_instant;
     }
 }
 
-/// Update Time::now and Time::delta.
+/// The maximum number of fixed update steps in one frame, and of physics steps in one update when
+/// [crate::physics2d::Physics2DManager::fixed_dt] is used, so that a long frame does not make the game
+/// spend even longer frames to catch up.
+pub const MAX_FIXED_STEPS: u32 = 5;
+
+/// FixedStepAccumulator accumulates elapsed time and splits it into fixed steps. It is used by
+/// [Time::update_rate] and [crate::physics2d::Physics2DManager::fixed_dt].
+#[derive(Default)]
+pub(crate) struct FixedStepAccumulator {
+    accumulator: f32,
+}
+
+impl FixedStepAccumulator {
+    /// Add delta seconds and return how many steps of step seconds to run now, which is at most [MAX_FIXED_STEPS].
+    /// The time of the steps which are more than MAX_FIXED_STEPS is dropped.
+    pub fn accumulate(&mut self, delta: f32, step: f32) -> u32 {
+        self.accumulator += delta;
+        let steps = (self.accumulator / step) as u32;
+        let steps_to_run = steps.min(MAX_FIXED_STEPS);
+        self.accumulator -= steps_to_run as f32 * step;
+        if steps > MAX_FIXED_STEPS {
+            // drop the steps we can not catch up with
+            self.accumulator = self.accumulator.min(step);
+        }
+        steps_to_run
+    }
+
+    /// Get the seconds which are accumulated but not stepped yet.
+    pub fn remainder(&self) -> f32 {
+        self.accumulator
+    }
+
+    /// Drop all accumulated time.
+    pub fn reset(&mut self) {
+        self.accumulator = 0.0;
+    }
+}
+
+/// Update Time::now, Time::delta, Time::elapsed and Time::frame_count,
+/// also count how many times [crate::app::Schedule::Update] runs in this frame.
 pub fn time_maintain_system(mut time: UniqueViewMut<Time>) {
     let now_instant = Instant::now();
     time.now = (now_instant - time.start_instant).as_secs_f32();
-    time.delta = (now_instant - time.last_instant).as_secs_f32();
+    time.unscaled_delta = (now_instant - time.last_instant).as_secs_f32();
+    time.delta = time.unscaled_delta * time.scale;
+    time.elapsed += time.delta as f64;
+    time.frame_count += 1;
     time.last_instant = now_instant;
+    time.frame_delta = time.delta;
+    time.frame_unscaled_delta = time.unscaled_delta;
+
+    if time.update_rate > 0.0 {
+        let (delta, step) = (time.unscaled_delta, 1.0 / time.update_rate);
+        time.update_steps = time.update_accumulator.accumulate(delta, step);
+    } else {
+        time.update_accumulator.reset();
+        time.update_steps = 1;
+    }
 }
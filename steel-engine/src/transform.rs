@@ -7,7 +7,7 @@ use steel_common::data::{Data, Limit, Value};
 /// The Transform component defines position, rotation, and scale of an entity.
 /// If this entity has any ancestor which also has Transform component,
 /// the transform values are relative to this entity's parent.
-#[derive(Component, Debug)]
+#[derive(Component, Debug, Clone, PartialEq)]
 pub struct Transform {
     pub position: Vec3,
     pub rotation: Quat,
@@ -15,6 +15,16 @@ pub struct Transform {
 }
 
 impl Transform {
+    /// Interpolate between self and other by t, where 0.0 returns self and 1.0 returns other.
+    /// Position and scale are interpolated linearly, and rotation is interpolated spherically.
+    pub fn lerp(&self, other: &Transform, t: f32) -> Transform {
+        Transform {
+            position: self.position.lerp(other.position, t),
+            rotation: self.rotation.slerp(other.rotation, t),
+            scale: self.scale.lerp(other.scale, t),
+        }
+    }
+
     /// Get the model matrix of this transform.
     pub fn model(&self) -> Affine3A {
         Affine3A::from_scale_rotation_translation(self.scale, self.rotation, self.position)
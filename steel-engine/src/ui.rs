@@ -10,6 +10,32 @@ impl EguiContext {
     pub fn new(ctx: egui::Context) -> Self {
         EguiContext(ctx)
     }
+
+    /// Show ui in an area anchored to the screen, margin is the distance to the anchored screen edges.
+    /// The screen is the game window in steel-editor, and is the whole window in steel-client.
+    /// # Example
+    /// ```rust
+    /// use shipyard::UniqueView;
+    /// use steel::ui::EguiContext;
+    ///
+    /// fn hud_system(egui_ctx: UniqueView<EguiContext>) {
+    ///     egui_ctx.anchored("score", egui::Align2::RIGHT_TOP, egui::vec2(10.0, 10.0), |ui| {
+    ///         ui.label("Score: 0");
+    ///     });
+    /// }
+    /// ```
+    pub fn anchored<R>(
+        &self,
+        id: impl std::hash::Hash,
+        anchor: egui::Align2,
+        margin: egui::Vec2,
+        add_contents: impl FnOnce(&mut egui::Ui) -> R,
+    ) -> R {
+        egui::Area::new(egui::Id::new(id))
+            .anchor(anchor, anchor_offset(anchor, margin))
+            .show(&self.0, add_contents)
+            .inner
+    }
 }
 
 impl std::ops::Deref for EguiContext {
@@ -19,3 +45,37 @@ impl std::ops::Deref for EguiContext {
         &self.0
     }
 }
+
+/// Get a rect with size which is anchored in container, margin is the distance to the anchored container edges.
+/// This is useful to put a widget in a ui with [egui::Ui::put], for example:
+/// ```rust
+/// use shipyard::UniqueView;
+/// use steel::ui::{anchored_rect, EguiContext};
+///
+/// fn menu_system(egui_ctx: UniqueView<EguiContext>) {
+///     egui::CentralPanel::default().show(&egui_ctx, |ui| {
+///         let rect = anchored_rect(ui.max_rect(), egui::Align2::CENTER_BOTTOM, egui::vec2(200.0, 50.0), egui::vec2(0.0, 20.0));
+///         ui.put(rect, egui::Button::new("Start"));
+///     });
+/// }
+/// ```
+pub fn anchored_rect(
+    container: egui::Rect,
+    anchor: egui::Align2,
+    size: egui::Vec2,
+    margin: egui::Vec2,
+) -> egui::Rect {
+    anchor
+        .align_size_within_rect(size, container)
+        .translate(anchor_offset(anchor, margin))
+}
+
+/// Convert margin to the offset from anchor toward the center.
+fn anchor_offset(anchor: egui::Align2, margin: egui::Vec2) -> egui::Vec2 {
+    let sign = |align: egui::Align| match align {
+        egui::Align::Min => 1.0,
+        egui::Align::Center => 0.0,
+        egui::Align::Max => -1.0,
+    };
+    egui::vec2(sign(anchor.x()) * margin.x, sign(anchor.y()) * margin.y)
+}
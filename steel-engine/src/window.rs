@@ -0,0 +1,118 @@
+use glam::UVec2;
+use shipyard::Unique;
+use steel_common::app::{FullscreenMode, WindowRequest};
+
+/// Window unique can be used to query and change the game window. Changes are applied to the winit window
+/// by steel-client at the end of the frame, and are ignored in editor where the game window is a part of the editor.
+#[derive(Unique)]
+pub struct Window {
+    title: String,
+    size: UVec2,
+    fullscreen: FullscreenMode,
+    vsync: bool,
+    transparent: bool,
+    target_fps: Option<u32>,
+    request: WindowRequest,
+}
+
+impl Default for Window {
+    fn default() -> Self {
+        let title = String::from("Steel Game");
+        Window {
+            request: WindowRequest {
+                title: Some(title.clone()),
+                ..Default::default()
+            },
+            title,
+            size: UVec2::ZERO,
+            fullscreen: FullscreenMode::Windowed,
+            vsync: true,
+            transparent: false,
+            target_fps: None,
+        }
+    }
+}
+
+impl Window {
+    /// Get the title of the game window.
+    pub fn title(&self) -> &str {
+        &self.title
+    }
+
+    /// Set the title of the game window.
+    pub fn set_title(&mut self, title: impl Into<String>) {
+        self.title = title.into();
+        self.request.title = Some(self.title.clone());
+    }
+
+    /// Get the size of the game window in pixels. This is zero before the game window is drawn for the first time.
+    pub fn size(&self) -> UVec2 {
+        self.size
+    }
+
+    /// Request to resize the game window to size in pixels. [Window::size] will be updated
+    /// after the game window is actually resized, which may never happen on some platforms.
+    pub fn set_size(&mut self, size: UVec2) {
+        self.request.size = Some(size);
+    }
+
+    /// Get the fullscreen mode of the game window.
+    pub fn fullscreen(&self) -> FullscreenMode {
+        self.fullscreen
+    }
+
+    /// Set the fullscreen mode of the game window.
+    pub fn set_fullscreen(&mut self, fullscreen: FullscreenMode) {
+        self.fullscreen = fullscreen;
+        self.request.fullscreen = Some(fullscreen);
+    }
+
+    /// Get whether vertical synchronization is enabled, default is true.
+    pub fn vsync(&self) -> bool {
+        self.vsync
+    }
+
+    /// Enable or disable vertical synchronization, the swapchain is recreated with the new present mode.
+    pub fn set_vsync(&mut self, vsync: bool) {
+        self.vsync = vsync;
+        self.request.vsync = Some(vsync);
+    }
+
+    /// Get whether the game window has a transparent background, default is false.
+    pub fn transparent(&self) -> bool {
+        self.transparent
+    }
+
+    /// Request a transparent background for the game window, which is useful for desktop overlays and widgets.
+    /// This only takes effect when the game window is created, so it should be called in
+    /// [crate::app::Schedule::Init]. The background is cleared to the clear color of
+    /// [crate::render::pipeline::rasterization::RasterizationSettings], whose alpha should be less than 1.
+    /// The window stays opaque if transparent windows are not supported by the platform.
+    pub fn set_transparent(&mut self, transparent: bool) {
+        self.transparent = transparent;
+        self.request.transparent = Some(transparent);
+    }
+
+    /// Get the target frame rate, None means that the frame rate is not limited, default is None.
+    pub fn target_fps(&self) -> Option<u32> {
+        self.target_fps
+    }
+
+    /// Limit the frame rate to target_fps by waiting between frames, which reduces cpu and gpu usage
+    /// for simple games and menus. This is independent of vsync, the lower frame rate wins.
+    /// None or zero means that the frame rate is not limited.
+    pub fn set_target_fps(&mut self, target_fps: Option<u32>) {
+        self.target_fps = target_fps.filter(|fps| *fps > 0);
+        self.request.target_fps = Some(self.target_fps);
+    }
+
+    /// Update the size of the game window, this is called when drawing the game window.
+    pub(crate) fn update_size(&mut self, size: UVec2) {
+        self.size = size;
+    }
+
+    /// Take the window changes requested since last time.
+    pub(crate) fn take_request(&mut self) -> WindowRequest {
+        std::mem::take(&mut self.request)
+    }
+}
@@ -3,7 +3,7 @@ use quote::{quote, ToTokens};
 use std::{iter::zip, str::FromStr};
 use syn::{self, Index};
 
-pub fn impl_edit_macro_derive(ast: &syn::DeriveInput) -> TokenStream {
+pub fn impl_edit_macro_derive(ast: &syn::DeriveInput) -> syn::Result<TokenStream> {
     let name = &ast.ident;
     let name_fn = quote! {
         fn name() -> &'static str { stringify!(#name) }
@@ -15,8 +15,8 @@ pub fn impl_edit_macro_derive(ast: &syn::DeriveInput) -> TokenStream {
             syn::Fields::Unnamed(fields) => fields.unnamed.iter().collect::<Vec<_>>(),
             syn::Fields::Unit => Vec::new(),
         },
-        syn::Data::Enum(_) => panic!("Not yet supported Edit derive macro in Enum"),
-        syn::Data::Union(_) => panic!("Not yet supported Edit derive macro in Union"),
+        syn::Data::Enum(_) => return Err(syn::Error::new_spanned(name, "Not yet supported Edit derive macro in Enum")),
+        syn::Data::Union(_) => return Err(syn::Error::new_spanned(name, "Not yet supported Edit derive macro in Union")),
     };
     let (field_accessors, (value_types, (value_names, value_limits))): (Vec<_>, (Vec<_>, (Vec<_>, Vec<_>))) = fields.into_iter().enumerate().filter_map(|(i, field)| {
         let field_accessor = match &field.ident {
@@ -49,6 +49,24 @@ pub fn impl_edit_macro_derive(ast: &syn::DeriveInput) -> TokenStream {
                     "UVec4" => quote! { Value::UVec4 },
                     "EntityId" => quote! { Value::Entity },
                     "AssetId" => quote! { Value::Asset },
+                    "MinMax" | "Range" => {
+                        let generic_arg = match &type_last_segment.arguments {
+                            syn::PathArguments::AngleBracketed(generic_arguments) => generic_arguments.args.first().unwrap(),
+                            _ => return None,
+                        };
+                        match generic_arg {
+                            syn::GenericArgument::Type(syn::Type::Path(generic_path)) => {
+                                let generic_type_last_ident = &generic_path.path.segments.last()
+                                    .unwrap_or_else(|| panic!("No type path segment for field {field_accessor:?}")).ident;
+                                match generic_type_last_ident.to_string().as_str() {
+                                    "f32" => quote! { Value::RangeFloat32 },
+                                    "i32" => quote! { Value::RangeInt32 },
+                                    _ => return None,
+                                }
+                            }
+                            _ => return None,
+                        }
+                    }
                     "Vec" => {
                         let generic_arg = match &type_last_segment.arguments {
                             syn::PathArguments::AngleBracketed(generic_arguments) => generic_arguments.args.first().unwrap(),
@@ -81,42 +99,16 @@ pub fn impl_edit_macro_derive(ast: &syn::DeriveInput) -> TokenStream {
             _ => return None,
         };
 
-        let mut value_name = field_accessor.to_string();
-        let mut value_limit = None;
-        field.attrs.iter().for_each(|attr| {
-            if attr.path().is_ident("edit") {
-                if let syn::Meta::List(meta) = &attr.meta {
-                    if let Err(err) = meta.parse_nested_meta(|meta| {
-                        if meta.path.is_ident("name") {
-                            if let syn::Expr::Lit(syn::ExprLit { lit: syn::Lit::Str(lit), .. }) = meta.value()?.parse()? {
-                                value_name = lit.value();
-                            } else {
-                                return Err(meta.error("name must be a string literal"));
-                            }
-                        } else if meta.path.is_ident("limit") {
-                            if let syn::Expr::Lit(syn::ExprLit { lit: syn::Lit::Str(lit), .. }) = meta.value()?.parse()? {
-                                value_limit = Some(TokenStream::from_str(&lit.value())?);
-                            } else {
-                                return Err(meta.error("limit must be a string literal"));
-                            }
-                        } else {
-                            return Err(meta.error("unsupported edit property"));
-                        }
-                        Ok(())
-                    }) {
-                        panic!("source={:?}, error={}", err.span().source_text(), err.to_string());
-                    }
-                } else {
-                    panic!("edit attribute content should be key value pair list, example: #[edit(limit = \"Limit::ReadOnly\", name = \"foo\")]");
-                }
-            }
-        });
+        let (value_name, value_limit) = match parse_edit_attrs(field, &field_accessor, &value_type) {
+            Ok(name_and_limit) => name_and_limit,
+            Err(err) => return Some(Err(err)),
+        };
 
-        Some((field_accessor, (value_type, (value_name, value_limit))))
-    }).unzip();
+        Some(Ok((field_accessor, (value_type, (value_name, value_limit)))))
+    }).collect::<syn::Result<Vec<_>>>()?.into_iter().unzip();
 
     let insert_values = zip(&value_types, &field_accessors)
-        .map(|(value_type, field_accessor)| quote! { #value_type (self.#field_accessor.clone()) })
+        .map(|(value_type, field_accessor)| quote! { #value_type (self.#field_accessor.clone().into()) })
         .collect::<Vec<_>>();
     let insert_tokens = zip(&value_names, zip(insert_values, &value_limits))
         .map(|(value_name, (insert_value, value_limit))| {
@@ -135,7 +127,7 @@ pub fn impl_edit_macro_derive(ast: &syn::DeriveInput) -> TokenStream {
 
     let set_datas = zip(value_limits, zip(value_types, zip(value_names, field_accessors)))
         .filter(|(value_limit, _)| !value_limit.as_ref().is_some_and(|limit| limit.to_string().contains("ReadOnly")))
-        .map(|(_, (value_type, (value_name, field_accessor)))| quote! { if let Some(#value_type (v)) = data.get(#value_name) { self.#field_accessor = v.clone() } })
+        .map(|(_, (value_type, (value_name, field_accessor)))| quote! { if let Some(#value_type (v)) = data.get(#value_name) { self.#field_accessor = v.clone().into() } })
         .collect::<Vec<_>>();
     let set_data_fn = quote! {
         fn set_data(&mut self, data: &Data) {
@@ -143,13 +135,84 @@ pub fn impl_edit_macro_derive(ast: &syn::DeriveInput) -> TokenStream {
         }
     };
 
-    quote! {
+    Ok(quote! {
         impl Edit for #name {
             #name_fn
             #get_data_fn
             #set_data_fn
         }
+    })
+}
+
+/// Parse the edit attributes of a field, returns the value name and value limit.
+fn parse_edit_attrs(field: &syn::Field, field_accessor: &FieldAccessor, value_type: &TokenStream) -> syn::Result<(String, Option<TokenStream>)> {
+    let mut value_name = field_accessor.to_string();
+    let mut value_limit = None;
+    let mut value_step = None;
+    let mut value_asset = None;
+    for attr in field.attrs.iter().filter(|attr| attr.path().is_ident("edit")) {
+        let syn::Meta::List(meta) = &attr.meta else {
+            return Err(syn::Error::new_spanned(attr, "edit attribute content should be key value pair list, example: #[edit(limit = \"Limit::ReadOnly\", name = \"foo\")]"));
+        };
+        meta.parse_nested_meta(|meta| {
+            if meta.path.is_ident("name") {
+                if let syn::Expr::Lit(syn::ExprLit { lit: syn::Lit::Str(lit), .. }) = meta.value()?.parse()? {
+                    value_name = lit.value();
+                } else {
+                    return Err(meta.error("name must be a string literal"));
+                }
+            } else if meta.path.is_ident("limit") {
+                if value_step.is_some() || value_asset.is_some() {
+                    return Err(meta.error("limit can not be used together with step or asset"));
+                }
+                if let syn::Expr::Lit(syn::ExprLit { lit: syn::Lit::Str(lit), .. }) = meta.value()?.parse()? {
+                    value_limit = Some(TokenStream::from_str(&lit.value())?);
+                } else {
+                    return Err(meta.error("limit must be a string literal"));
+                }
+            } else if meta.path.is_ident("step") {
+                if value_type.to_string() != quote! { Value::Int32 }.to_string() {
+                    return Err(meta.error("step can only be used on i32 field"));
+                }
+                if value_limit.is_some() || value_asset.is_some() {
+                    return Err(meta.error("step can not be used together with limit or asset, use limit = \"Limit::Int32RangeStep(range, step)\" instead"));
+                }
+                if let syn::Expr::Lit(syn::ExprLit { lit: syn::Lit::Int(lit), .. }) = meta.value()?.parse()? {
+                    let step = lit.base10_parse::<i32>()?;
+                    if step <= 0 {
+                        return Err(meta.error("step must be greater than 0"));
+                    }
+                    value_step = Some(step);
+                } else {
+                    return Err(meta.error("step must be an integer literal"));
+                }
+            } else if meta.path.is_ident("asset") {
+                if value_type.to_string() != quote! { Value::Asset }.to_string() {
+                    return Err(meta.error("asset can only be used on AssetId field"));
+                }
+                if value_limit.is_some() || value_step.is_some() {
+                    return Err(meta.error("asset can not be used together with limit or step"));
+                }
+                if let syn::Expr::Lit(syn::ExprLit { lit: syn::Lit::Str(lit), .. }) = meta.value()?.parse()? {
+                    value_asset = Some(lit.value());
+                } else {
+                    return Err(meta.error("asset must be a string literal"));
+                }
+            } else {
+                return Err(meta.error("unsupported edit property"));
+            }
+            Ok(())
+        })?;
+    }
+
+    if let Some(step) = value_step {
+        // start the range at a multiple of step, so that values are snapped to multiples of step
+        value_limit = Some(quote! { Limit::Int32RangeStep(i32::MIN / #step * #step..=i32::MAX, #step) });
+    }
+    if let Some(asset) = value_asset {
+        value_limit = Some(quote! { Limit::asset_type(#asset) });
     }
+    Ok((value_name, value_limit))
 }
 
 #[derive(Debug)]